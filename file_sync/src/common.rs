@@ -2,7 +2,12 @@ pub use derive_new::new;
 
 pub use reqwest::{Body, Client};
 
-pub use tokio::{io::AsyncReadExt, task};
+pub use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    sync::mpsc::{unbounded_channel, UnboundedSender},
+    task,
+    time::Instant,
+};
 
 pub use getset::Getters;
 
@@ -19,10 +24,13 @@ pub use flexi_logger::{Age, Cleanup, Criterion, FileSpec, Logger, Naming, Record
 
 pub use actix_web::{
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
-    web, App, Error, HttpResponse, HttpServer,
+    web, App, Error, HttpRequest, HttpResponse, HttpResponseBuilder, HttpServer,
 };
 
-pub use hotwatch::{Event, EventKind as WatchEventKind, Hotwatch};
+pub use hotwatch::{
+    notify::event::{ModifyKind, RenameMode},
+    Event, EventKind as WatchEventKind, Hotwatch,
+};
 
 pub use sha2::{Digest, Sha256};
 
@@ -38,6 +46,10 @@ pub use once_cell::sync::Lazy as once_lazy;
 
 pub use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
+pub use rand::Rng;
+
+pub use flate2::{read::GzDecoder, write::GzEncoder, Compression as GzCompression};
+
 pub use dotenv::dotenv;
 
 
@@ -47,9 +59,7 @@ pub use std::{
     fs::File,
     io::{BufReader, Read, Write},
     path::{Path, PathBuf},
-    sync::{
-        mpsc::{channel, Sender},
-        Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard,
-    },
-    task::{Context, Poll}
+    sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard},
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
 };
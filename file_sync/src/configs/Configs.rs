@@ -1,8 +1,10 @@
 use crate::common::*;
 
 use crate::configs::server_config::*;
+use crate::configs::watch_root_config::*;
 
 use crate::utils_modules::io_utils::*;
+use crate::utils_modules::pattern_utils::*;
 
 use crate::model::monitoring_path_info::*;
 
@@ -41,21 +43,161 @@ pub fn get_config_read() -> Result<RwLockReadGuard<'static, Configs>, anyhow::Er
         .map_err(|e| anyhow!("Failed to acquire the read lock due to poisoning: {:?}", e))
 }
 
-#[doc = "모니터링 파일의 정확한 위치 리스트를 반환하는 함수"]
-pub fn get_monitoring_file_detail_path() -> Result<Vec<MonitoringPathInfo>, anyhow::Error> {
-    let config: RwLockReadGuard<'_, Configs> = get_config_read()?;
-    let watch_path: &String = config.server.watch_path();
-    let file_list: &Vec<String> = config.server.specific_files();
+#[doc = "파일을 전달할 slave 목록을 반환하는 함수 - 라우팅 규칙이 적용되며 설정되지 않은 경우 빈 목록"]
+/// # Arguments
+/// * `file_name` - slave 에 전달되는 파일 경로 (slave 의 감시 디렉토리 기준 상대경로)
+///
+/// # Returns
+/// * Result<Vec<String>, anyhow::Error>
+pub fn get_target_slave_url_list(file_name: &str) -> Result<Vec<String>, anyhow::Error> {
+    let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+    server_config.server.get_target_slaves(file_name)
+}
+
+#[doc = "라우팅 규칙상 해당 IP 의 slave 에 파일이 전달되는 경우 그 slave 의 주소를 반환하는 함수 - pull 모드에서 사용"]
+/// # Arguments
+/// * `file_name` - slave 에 전달되는 파일 경로 (slave 의 감시 디렉토리 기준 상대경로)
+/// * `slave_ip`  - 요청을 보낸 slave 의 IP
+///
+/// # Returns
+/// * Result<Option<String>, anyhow::Error> - 전달 대상이 아닌 경우 None
+pub fn get_routed_slave_url(file_name: &str, slave_ip: &str) -> Result<Option<String>, anyhow::Error> {
+    Ok(get_target_slave_url_list(file_name)?
+        .into_iter()
+        .find(|slave_url| get_address_ip(slave_url) == slave_ip))
+}
 
-    let monitor_file_list: Vec<MonitoringPathInfo> = file_list
+#[doc = "파일을 특정 slave 에 저장할 경로를 반환하는 함수 - 경로 매핑 설정이 없다면 기본 경로"]
+/// # Arguments
+/// * `file_name` - slave 에 전달되는 파일 경로 (기본 경로)
+/// * `slave_url` - 파일을 전달받는 slave 주소
+///
+/// # Returns
+/// * Result<String, anyhow::Error>
+pub fn get_slave_dest_file_name(file_name: &str, slave_url: &str) -> Result<String, anyhow::Error> {
+    let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+    Ok(server_config.server.get_dest_file_name(file_name, slave_url))
+}
+
+#[doc = "파일을 각 slave 에 저장할 경로 목록을 반환하는 함수"]
+/// # Arguments
+/// * `file_name` - slave 에 전달되는 파일 경로 (기본 경로)
+/// * `slave_url` - 파일을 전달받는 slave 목록
+///
+/// # Returns
+/// * Result<Vec<String>, anyhow::Error> - slave_url 과 같은 순서
+pub fn get_dest_file_names(file_name: &str, slave_url: &[String]) -> Result<Vec<String>, anyhow::Error> {
+    let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+    Ok(slave_url
         .iter()
-        .map(|file_path| {
-            MonitoringPathInfo::new(
+        .map(|url| server_config.server.get_dest_file_name(file_name, url))
+        .collect())
+}
+
+#[doc = "이벤트가 발생한 파일이 모니터링 대상인 경우 해당 파일의 모니터링 정보를 반환하는 함수"]
+/// # Arguments
+/// * `file_path` - 이벤트가 발생한 파일의 절대경로
+///
+/// # Returns
+/// * Result<Option<MonitoringPathInfo>, anyhow::Error> - 모니터링 대상이 아닌 경우 None
+pub fn get_monitoring_file_info(
+    file_path: &str,
+) -> Result<Option<MonitoringPathInfo>, anyhow::Error> {
+    let watch_roots: Vec<WatchRootConfig> = {
+        let config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+        config.server.get_watch_roots()
+    };
+
+    let event_file_path: &Path = Path::new(file_path);
+
+    /* 감시 디렉토리가 겹치는 경우에는 먼저 설정된 감시 디렉토리를 우선한다. */
+    for watch_root in watch_roots {
+        let watch_path: &Path = Path::new(watch_root.watch_path());
+
+        /*
+            hotwatch 는 정규화된 절대경로로 이벤트를 넘겨주므로
+            설정된 감시 경로가 상대경로인 경우에는 정규화된 경로로 한번 더 비교해준다.
+        */
+        let relative_path: Option<String> =
+            get_relative_path_str(watch_path, event_file_path).or_else(|| {
+                let canonical_watch_path: PathBuf = watch_path.canonicalize().ok()?;
+                let cleaned_watch_path: String =
+                    canonical_watch_path.to_str()?.replace(r"\\?\", "");
+                get_relative_path_str(Path::new(&cleaned_watch_path), event_file_path)
+            });
+
+        let relative_path: String = match relative_path {
+            Some(relative_path) => relative_path,
+            None => continue,
+        };
+
+        if is_match_file_patterns(watch_root.specific_files(), &relative_path)? {
+            return Ok(Some(MonitoringPathInfo::new(
+                watch_root.name().to_string(),
+                watch_root.get_dest_file_path(&relative_path),
                 file_path.to_string(),
-                format!("{}{}", watch_path, file_path),
-            )
-        })
-        .collect();
+            )));
+        }
+    }
+
+    Ok(None)
+}
+
+#[doc = "모든 감시 디렉토리를 탐색하여 현재 존재하는 모니터링 대상 파일 목록을 반환하는 함수"]
+/// # Returns
+/// * Result<Vec<MonitoringPathInfo>, anyhow::Error>
+pub fn get_monitoring_file_list() -> Result<Vec<MonitoringPathInfo>, anyhow::Error> {
+    let watch_roots: Vec<WatchRootConfig> = {
+        let config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+        config.server.get_watch_roots()
+    };
+
+    let mut monitor_file_list: Vec<MonitoringPathInfo> = Vec::new();
+
+    for watch_root in watch_roots {
+        /* hotwatch 이벤트와 동일한 경로(해시 저장소의 키)가 되도록 정규화된 경로로 탐색한다. */
+        let watch_path: PathBuf = match Path::new(watch_root.watch_path()).canonicalize() {
+            Ok(watch_path) => PathBuf::from(
+                watch_path
+                    .to_str()
+                    .map(|path| path.replace(r"\\?\", ""))
+                    .unwrap_or_default(),
+            ),
+            Err(e) => {
+                error!(
+                    "[Error][get_monitoring_file_list()] Cannot access '{}' : {:?}",
+                    watch_root.watch_path(),
+                    e
+                );
+                continue;
+            }
+        };
+
+        for file_path in get_all_file_paths(&watch_path)? {
+            let relative_path: String = match get_relative_path_str(&watch_path, &file_path) {
+                Some(relative_path) => relative_path,
+                None => continue,
+            };
+
+            let full_file_path: &str = match file_path.to_str() {
+                Some(full_file_path) => full_file_path,
+                None => continue,
+            };
+
+            /* 감시 디렉토리가 겹치는 경우 동일한 파일을 두번 동기화하지 않는다. */
+            let already_exists: bool = monitor_file_list
+                .iter()
+                .any(|monitor_file| monitor_file.full_file_path() == full_file_path);
+
+            if !already_exists && is_match_file_patterns(watch_root.specific_files(), &relative_path)? {
+                monitor_file_list.push(MonitoringPathInfo::new(
+                    watch_root.name().to_string(),
+                    watch_root.get_dest_file_path(&relative_path),
+                    full_file_path.to_string(),
+                ));
+            }
+        }
+    }
 
     Ok(monitor_file_list)
 }
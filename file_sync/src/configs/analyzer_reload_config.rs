@@ -0,0 +1,9 @@
+use crate::common::*;
+
+#[doc = "파일별 검색 분석기 리로드 설정 하나 - 파일이 반영된 뒤 리로드할 인덱스 목록"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct AnalyzerReloadConfig {
+    pub files: Vec<String>,     /* 수신한 파일 경로 기준 패턴 (예: `synonyms_ko*.txt`, `!*.bak`) */
+    pub indices: Vec<String>,   /* 해당 파일을 사전으로 사용하는 분석기가 설정된 인덱스 목록 */
+}
@@ -0,0 +1,11 @@
+use crate::common::*;
+
+#[doc = "파일별 백업 보관 정책 하나에 대한 설정 - 지정한 조건들은 모두 적용된다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct BackupRetentionConfig {
+    pub files: Vec<String>,             /* 백업된 파일 경로 기준 패턴 (예: `synonyms_ko*.txt`, `!*.bak`) */
+    pub keep_last: Option<usize>,       /* 최근 백업을 N 개까지만 보관 */
+    pub max_total_mb: Option<u64>,      /* 파일의 백업 전체 크기를 X MB 까지만 보관 (가장 최근 백업은 항상 보관) */
+    pub daily_after_days: Option<i64>,  /* N 일이 지난 백업은 하루에 하나(그 날의 마지막 백업)만 보관 */
+}
@@ -0,0 +1,36 @@
+use crate::common::*;
+
+use crate::model::file_meta_info::*;
+
+#[doc = "slave 가 master 의 파일 메타정보 대신 강제로 적용할 권한/소유자 설정"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct FileMetaOverrideConfig {
+    pub mode: Option<String>,   /* 8진수 문자열 (예: "0644") */
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+}
+
+impl FileMetaOverrideConfig {
+    #[doc = "master 가 보낸 파일 메타정보에 강제 설정을 덮어써주는 함수"]
+    /// # Arguments
+    /// * `file_meta` - master 가 보낸 파일 메타정보
+    ///
+    /// # Returns
+    /// * Result<FileMetaInfo, anyhow::Error> - mode 가 8진수가 아닌 경우 에러
+    pub fn apply(&self, file_meta: &FileMetaInfo) -> Result<FileMetaInfo, anyhow::Error> {
+        let mode: Option<u32> = match self.mode() {
+            Some(mode) => Some(u32::from_str_radix(mode, 8).map_err(|e| {
+                anyhow!("[Error][FileMetaOverrideConfig->apply()] Invalid mode '{}': {:?}", mode, e)
+            })?),
+            None => *file_meta.mode(),
+        };
+
+        Ok(FileMetaInfo::new(
+            mode,
+            self.uid().or(*file_meta.uid()),
+            self.gid().or(*file_meta.gid()),
+            *file_meta.mtime(),
+        ))
+    }
+}
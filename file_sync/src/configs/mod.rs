@@ -1,2 +1,9 @@
+pub mod analyzer_reload_config;
+pub mod backup_retention_config;
 pub mod configs;
+pub mod file_meta_override_config;
+pub mod path_mapping_config;
+pub mod routing_rule_config;
 pub mod server_config;
+pub mod sync_hook_config;
+pub mod watch_root_config;
@@ -0,0 +1,11 @@
+use crate::common::*;
+
+#[doc = "파일 하나를 특정 slave 또는 slave 그룹에서 다른 경로로 저장하도록 지정하는 설정"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct PathMappingConfig {
+    pub file: String,                   /* slave 에 전달되는 파일 경로 (기본 경로) */
+    pub dest: String,                   /* 대상 slave 의 감시 디렉토리 기준 저장 경로 */
+    pub slaves: Option<Vec<String>>,    /* 적용할 slave 주소 목록 */
+    pub groups: Option<Vec<String>>,    /* 적용할 slave 그룹 이름 목록 */
+}
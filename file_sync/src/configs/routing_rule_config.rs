@@ -0,0 +1,9 @@
+use crate::common::*;
+
+#[doc = "파일을 어떤 slave 그룹에 보낼지 정하는 라우팅 규칙 하나에 대한 설정"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct RoutingRuleConfig {
+    pub files: Vec<String>,     /* slave 에 전달되는 파일 경로 기준 패턴 (예: `synonyms_ko*.txt`, `!*.bak`) */
+    pub groups: Vec<String>,    /* 일치하는 파일을 전달할 slave 그룹 이름 목록 */
+}
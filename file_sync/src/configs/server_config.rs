@@ -1,5 +1,15 @@
 use crate::common::*;
 
+use crate::configs::analyzer_reload_config::*;
+use crate::configs::backup_retention_config::*;
+use crate::configs::file_meta_override_config::*;
+use crate::configs::path_mapping_config::*;
+use crate::configs::routing_rule_config::*;
+use crate::configs::sync_hook_config::*;
+use crate::configs::watch_root_config::*;
+
+use crate::utils_modules::pattern_utils::*;
+
 #[derive(Debug, Deserialize, Serialize, Getters)]
 #[getset(get = "pub")]
 pub struct ServerConfig {
@@ -7,8 +17,11 @@ pub struct ServerConfig {
     pub host: String,
     pub master_address: Option<Vec<String>>,
     pub slave_address: Option<Vec<String>>,
+    #[serde(default)]
     pub watch_path: String,
+    #[serde(default)]
     pub specific_files: Vec<String>,
+    pub watch_roots: Option<Vec<WatchRootConfig>>,
     pub io_bound_improvement: bool,
     pub slave_backup_path: Option<String>,
     pub elastic_host: Option<Vec<String>>,
@@ -18,6 +31,29 @@ pub struct ServerConfig {
     pub hash_storage_path: Option<String>,
     pub secure_mode: Option<bool>,
     pub key_directory: Option<String>,
+    pub anti_entropy_interval_secs: Option<u64>,
+    pub sync_mode: Option<String>,
+    pub pull_master_url: Option<String>,
+    pub pull_interval_secs: Option<u64>,
+    pub outbox_retry_deadline_secs: Option<u64>,
+    pub debounce_quiet_period_ms: Option<u64>,
+    pub local_change_policy: Option<String>,
+    pub review_master_url: Option<String>,
+    pub review_path: Option<String>,
+    pub slave_groups: Option<HashMap<String, Vec<String>>>,
+    pub routing_rules: Option<Vec<RoutingRuleConfig>>,
+    pub path_mappings: Option<Vec<PathMappingConfig>>,
+    pub preserve_owner: Option<bool>,
+    pub file_meta_override: Option<FileMetaOverrideConfig>,
+    pub history_path: Option<String>,
+    pub history_max_versions: Option<usize>,
+    pub admin_address: Option<Vec<String>>,
+    pub backup_compression: Option<String>,
+    pub backup_cleanup_interval_secs: Option<u64>,
+    pub backup_retention: Option<Vec<BackupRetentionConfig>>,
+    pub sync_hooks: Option<Vec<SyncHookConfig>>,
+    pub slave_elastic_url: Option<String>,
+    pub analyzer_reloads: Option<Vec<AnalyzerReloadConfig>>,
 }
 
 impl ServerConfig {
@@ -26,6 +62,19 @@ impl ServerConfig {
         self.watch_path().clone()
     }
 
+    #[doc = "master 가 감시하는 디렉토리 목록 - watch_roots 가 없다면 watch_path/specific_files 를 하나의 감시 디렉토리로 사용한다."]
+    pub fn get_watch_roots(&self) -> Vec<WatchRootConfig> {
+        match self.watch_roots() {
+            Some(watch_roots) if !watch_roots.is_empty() => watch_roots.clone(),
+            _ => vec![WatchRootConfig::new(
+                "default".to_string(),
+                self.get_watch_dir_path(),
+                self.specific_files().clone(),
+                None,
+            )],
+        }
+    }
+
     #[doc = "보안 모드 활성화 여부"]
     pub fn is_secure_mode(&self) -> bool {
         match self.secure_mode() {
@@ -34,6 +83,225 @@ impl ServerConfig {
         }
     }
 
+    #[doc = "slave 의 상태를 주기적으로 점검하는 간격(초) - 설정하지 않거나 0 인 경우 점검하지 않는다."]
+    pub fn get_anti_entropy_interval(&self) -> Option<Duration> {
+        match self.anti_entropy_interval_secs() {
+            Some(secs) if *secs > 0 => Some(Duration::from_secs(*secs)),
+            _ => None,
+        }
+    }
+
+    #[doc = "relay 여부 - master 로부터 받은 파일을 반영한 뒤 자신의 slave_address 로 다시 전달한다."]
+    pub fn is_relay_role(&self) -> bool {
+        self.role() == "relay"
+    }
+
+    #[doc = "pull 모드 여부 - slave 가 master 로부터 변경된 파일을 직접 가져간다. (기본값: push)"]
+    pub fn is_pull_mode(&self) -> bool {
+        matches!(self.sync_mode().as_deref(), Some("pull"))
+    }
+
+    #[doc = "pull 모드에서 slave 가 master 를 조회하는 간격 (기본값: 30초)"]
+    pub fn get_pull_interval(&self) -> Duration {
+        Duration::from_secs(self.pull_interval_secs().unwrap_or(30).max(1))
+    }
+
+    #[doc = "전송에 실패한 파일을 재시도하는 최대 기간 - 이 기간이 지나면 포기한다. (기본값: 24시간)"]
+    pub fn get_outbox_retry_deadline(&self) -> Duration {
+        Duration::from_secs(self.outbox_retry_deadline_secs().unwrap_or(86400))
+    }
+
+    #[doc = "파일 이벤트가 멈춘 뒤 동기화를 시작하기까지 기다리는 시간 (기본값: 500ms) - 0 인 경우 이벤트마다 바로 처리한다."]
+    pub fn get_debounce_quiet_period(&self) -> Duration {
+        Duration::from_millis(self.debounce_quiet_period_ms().unwrap_or(500))
+    }
+
+    #[doc = "master 파일의 소유자(uid/gid) 정보도 slave 에 전달할지 여부 (기본값: false)"]
+    pub fn is_preserve_owner(&self) -> bool {
+        self.preserve_owner().unwrap_or(false)
+    }
+
+    #[doc = "slave 에서 직접 수정된 파일의 처리 정책 - report(기본값), revert, forward"]
+    pub fn get_local_change_policy(&self) -> String {
+        self.local_change_policy()
+            .clone()
+            .unwrap_or_else(|| "report".to_string())
+    }
+
+    #[doc = "pull 모드에서 master 에 접근을 허용할 slave 의 IP 목록 - slave_address 에서 포트를 제외한 값"]
+    pub fn get_slave_ip_list(&self) -> Vec<String> {
+        self.slave_address()
+            .clone()
+            .unwrap_or_default()
+            .iter()
+            .map(|address| get_address_ip(address).to_string())
+            .collect()
+    }
+
+    #[doc = "master 에서 배포 이력 조회/롤백을 요청할 수 있는 관리자 IP 목록 - admin_address 에서 포트를 제외한 값"]
+    pub fn get_admin_ip_list(&self) -> Vec<String> {
+        self.admin_address()
+            .clone()
+            .unwrap_or_default()
+            .iter()
+            .map(|address| get_address_ip(address).to_string())
+            .collect()
+    }
+
+    #[doc = "master 의 배포 이력 보관 여부 - history_path 가 설정된 경우 배포한 버전을 보관하고 롤백할 수 있다."]
+    pub fn is_history_enabled(&self) -> bool {
+        self.history_path().is_some()
+    }
+
+    #[doc = "파일별로 보관하는 배포 이력의 최대 개수 (기본값: 10)"]
+    pub fn get_history_max_versions(&self) -> usize {
+        self.history_max_versions().unwrap_or(10).max(2)
+    }
+
+    #[doc = "백업 파일 압축 방식 - none(기본값), gzip, zstd"]
+    pub fn get_backup_compression(&self) -> String {
+        self.backup_compression()
+            .clone()
+            .unwrap_or_else(|| "none".to_string())
+    }
+
+    #[doc = "오래된 백업을 정리하는 주기 (기본값: 1시간)"]
+    pub fn get_backup_cleanup_interval(&self) -> Duration {
+        Duration::from_secs(self.backup_cleanup_interval_secs().unwrap_or(3600).max(1))
+    }
+
+    #[doc = "백업된 파일에 적용되는 보관 정책 - 처음으로 일치하는 정책 (없다면 backup_days 만 적용된다.)"]
+    /// # Arguments
+    /// * `file_name` - 백업된 파일 경로 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<Option<&BackupRetentionConfig>, anyhow::Error>
+    pub fn get_backup_retention(&self, file_name: &str) -> Result<Option<&BackupRetentionConfig>, anyhow::Error> {
+        for backup_retention in self.backup_retention().as_deref().unwrap_or_default() {
+            if is_match_file_patterns(backup_retention.files(), file_name)? {
+                return Ok(Some(backup_retention));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[doc = "수신한 파일에 적용되는 동기화 훅 - 처음으로 일치하는 설정 (없다면 훅을 실행하지 않는다.)"]
+    /// # Arguments
+    /// * `file_name` - 수신한 파일 경로 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<Option<SyncHookConfig>, anyhow::Error>
+    pub fn get_sync_hook(&self, file_name: &str) -> Result<Option<SyncHookConfig>, anyhow::Error> {
+        for sync_hook in self.sync_hooks().as_deref().unwrap_or_default() {
+            if is_match_file_patterns(sync_hook.files(), file_name)? {
+                return Ok(Some(sync_hook.clone()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[doc = "수신한 파일을 사전으로 사용하는 인덱스 목록 - 일치하는 모든 설정의 인덱스 (없다면 리로드하지 않는다.)"]
+    /// # Arguments
+    /// * `file_name` - 수신한 파일 경로 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<Vec<String>, anyhow::Error>
+    pub fn get_analyzer_reload_indices(&self, file_name: &str) -> Result<Vec<String>, anyhow::Error> {
+        let mut indices: Vec<String> = Vec::new();
+
+        for analyzer_reload in self.analyzer_reloads().as_deref().unwrap_or_default() {
+            if !is_match_file_patterns(analyzer_reload.files(), file_name)? {
+                continue;
+            }
+
+            for index in analyzer_reload.indices() {
+                if !indices.contains(index) {
+                    indices.push(index.clone());
+                }
+            }
+        }
+
+        Ok(indices)
+    }
+
+    #[doc = "파일을 전달할 slave 목록 - 처음으로 일치하는 라우팅 규칙의 그룹에 속한 slave 들 (일치하는 규칙이 없다면 slave_address 전체)"]
+    /// # Arguments
+    /// * `file_name` - slave 에 전달되는 파일 경로 (slave 의 감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<Vec<String>, anyhow::Error> - 존재하지 않는 그룹을 지정한 경우 에러
+    pub fn get_target_slaves(&self, file_name: &str) -> Result<Vec<String>, anyhow::Error> {
+        for routing_rule in self.routing_rules().as_deref().unwrap_or_default() {
+            if !is_match_file_patterns(routing_rule.files(), file_name)? {
+                continue;
+            }
+
+            let mut target_slaves: Vec<String> = Vec::new();
+
+            for group_name in routing_rule.groups() {
+                let group_slaves: &Vec<String> = self
+                    .slave_groups()
+                    .as_ref()
+                    .and_then(|slave_groups| slave_groups.get(group_name))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "[Error][get_target_slaves()] The slave group '{}' does not exist.",
+                            group_name
+                        )
+                    })?;
+
+                for slave in group_slaves {
+                    if !target_slaves.contains(slave) {
+                        target_slaves.push(slave.clone());
+                    }
+                }
+            }
+
+            return Ok(target_slaves);
+        }
+
+        Ok(self.slave_address().clone().unwrap_or_default())
+    }
+
+    #[doc = "파일을 특정 slave 에 저장할 경로 - slave 주소로 지정한 설정이 그룹으로 지정한 설정보다 우선한다. (설정이 없다면 기본 경로)"]
+    /// # Arguments
+    /// * `file_name` - slave 에 전달되는 파일 경로 (기본 경로)
+    /// * `slave_url` - 파일을 전달받는 slave 주소
+    ///
+    /// # Returns
+    /// * String - 대상 slave 의 감시 디렉토리 기준 저장 경로
+    pub fn get_dest_file_name(&self, file_name: &str, slave_url: &str) -> String {
+        let path_mappings: Vec<&PathMappingConfig> = self
+            .path_mappings()
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|path_mapping| path_mapping.file() == file_name)
+            .collect();
+
+        let is_group_member = |group_name: &String| -> bool {
+            self.slave_groups()
+                .as_ref()
+                .and_then(|slave_groups| slave_groups.get(group_name))
+                .is_some_and(|group_slaves| group_slaves.iter().any(|slave| slave == slave_url))
+        };
+
+        let slave_mapping: Option<&&PathMappingConfig> = path_mappings.iter().find(|path_mapping| {
+            path_mapping.slaves().iter().flatten().any(|slave| slave == slave_url)
+        });
+
+        let group_mapping: Option<&&PathMappingConfig> = path_mappings
+            .iter()
+            .find(|path_mapping| path_mapping.groups().iter().flatten().any(is_group_member));
+
+        match slave_mapping.or(group_mapping) {
+            Some(path_mapping) => path_mapping.dest().trim_matches('/').to_string(),
+            None => file_name.to_string(),
+        }
+    }
+
     #[doc = "키 디렉토리 경로"]
     pub fn get_key_directory(&self) -> String {
         self.key_directory()
@@ -42,3 +310,124 @@ impl ServerConfig {
             .unwrap_or_else(|| "keys".to_string())
     }
 }
+
+#[doc = "'ip:port' 형식의 주소에서 IP 만 반환해주는 함수"]
+/// # Arguments
+/// * `address` - 서버 주소 (예: 192.168.0.1:9000)
+///
+/// # Returns
+/// * &str
+pub fn get_address_ip(address: &str) -> &str {
+    match address.rsplit_once(':') {
+        Some((ip, _port)) => ip,
+        None => address,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SERVER_CONFIG: &str = r#"
+role = "master"
+host = "127.0.0.1:9000"
+io_bound_improvement = false
+slave_address = ["10.0.0.1:9001", "10.0.0.2:9001", "10.0.0.3:9001"]
+
+[slave_groups]
+search = ["10.0.0.1:9001", "10.0.0.2:9001"]
+batch = ["10.0.0.2:9001", "10.0.0.3:9001"]
+empty = []
+
+[[routing_rules]]
+files = ["synonyms_ko*.txt", "!*_old.txt"]
+groups = ["search", "batch"]
+
+[[routing_rules]]
+files = ["synonyms*.txt"]
+groups = ["batch"]
+
+[[routing_rules]]
+files = ["stopwords.txt"]
+groups = ["unknown"]
+
+[[path_mappings]]
+file = "synonyms.txt"
+dest = "/dict/synonyms_search.txt"
+groups = ["search"]
+
+[[path_mappings]]
+file = "synonyms.txt"
+dest = "dict/synonyms_node2.txt"
+slaves = ["10.0.0.2:9001"]
+"#;
+
+    fn get_test_server_config() -> ServerConfig {
+        toml::from_str(TEST_SERVER_CONFIG).expect("failed to parse the test server config")
+    }
+
+    #[test]
+    fn target_slaves_follow_first_matching_rule() {
+        let server_config: ServerConfig = get_test_server_config();
+
+        assert_eq!(
+            server_config.get_target_slaves("synonyms_ko.txt").unwrap(),
+            vec!["10.0.0.1:9001", "10.0.0.2:9001", "10.0.0.3:9001"]
+        );
+        assert_eq!(
+            server_config.get_target_slaves("synonyms_en.txt").unwrap(),
+            vec!["10.0.0.2:9001", "10.0.0.3:9001"]
+        );
+    }
+
+    #[test]
+    fn target_slaves_skip_excluded_rule() {
+        let server_config: ServerConfig = get_test_server_config();
+
+        /* 첫 번째 규칙의 제외 패턴에 걸리면 다음 규칙으로 넘어간다. */
+        assert_eq!(
+            server_config.get_target_slaves("synonyms_ko_old.txt").unwrap(),
+            vec!["10.0.0.2:9001", "10.0.0.3:9001"]
+        );
+    }
+
+    #[test]
+    fn target_slaves_fall_back_to_slave_address() {
+        let server_config: ServerConfig = get_test_server_config();
+
+        assert_eq!(
+            server_config.get_target_slaves("userdict.txt").unwrap(),
+            vec!["10.0.0.1:9001", "10.0.0.2:9001", "10.0.0.3:9001"]
+        );
+    }
+
+    #[test]
+    fn target_slaves_reject_unknown_group() {
+        let server_config: ServerConfig = get_test_server_config();
+
+        assert!(server_config.get_target_slaves("stopwords.txt").is_err());
+    }
+
+    #[test]
+    fn dest_file_name_prefers_slave_over_group() {
+        let server_config: ServerConfig = get_test_server_config();
+
+        /* 10.0.0.2 는 search 그룹에도 속하지만 주소로 지정한 설정이 우선한다. */
+        assert_eq!(
+            server_config.get_dest_file_name("synonyms.txt", "10.0.0.2:9001"),
+            "dict/synonyms_node2.txt"
+        );
+        assert_eq!(
+            server_config.get_dest_file_name("synonyms.txt", "10.0.0.1:9001"),
+            "dict/synonyms_search.txt"
+        );
+    }
+
+    #[test]
+    fn dest_file_name_defaults_to_file_name() {
+        let server_config: ServerConfig = get_test_server_config();
+
+        assert_eq!(server_config.get_dest_file_name("synonyms.txt", "10.0.0.3:9001"), "synonyms.txt");
+        assert_eq!(server_config.get_dest_file_name("stopwords.txt", "10.0.0.2:9001"), "stopwords.txt");
+    }
+}
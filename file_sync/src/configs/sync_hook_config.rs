@@ -0,0 +1,18 @@
+use crate::common::*;
+
+#[doc = "파일별 동기화 훅 설정 하나 - slave 가 파일을 교체하기 전/후에 실행할 명령"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct SyncHookConfig {
+    pub files: Vec<String>,             /* 수신한 파일 경로 기준 패턴 (예: `synonyms_ko*.txt`, `!*.bak`) */
+    pub pre_write: Option<String>,      /* 교체 전에 임시 파일을 검증하는 명령 -> 실패하면 수신을 거절한다. */
+    pub post_write: Option<String>,     /* 교체 후에 실행하는 명령 -> 실패하면 백업으로 되돌린다. */
+    pub timeout_secs: Option<u64>,      /* 명령 하나의 최대 실행 시간 (기본값: 30초) */
+}
+
+impl SyncHookConfig {
+    #[doc = "훅 명령 하나의 최대 실행 시간 (기본값: 30초)"]
+    pub fn get_timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs().unwrap_or(30).max(1))
+    }
+}
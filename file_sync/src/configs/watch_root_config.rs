@@ -0,0 +1,28 @@
+use crate::common::*;
+
+#[doc = "master 가 감시하는 디렉토리(watch root) 하나에 대한 설정"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct WatchRootConfig {
+    pub name: String,
+    pub watch_path: String,
+    pub specific_files: Vec<String>,
+    pub dest_path: Option<String>,
+}
+
+impl WatchRootConfig {
+    #[doc = "감시 디렉토리 기준 상대경로를 slave 에 전달할 파일 경로로 변환해주는 함수"]
+    /// # Arguments
+    /// * `relative_path` - 감시 디렉토리 기준 상대경로
+    ///
+    /// # Returns
+    /// * String - slave 의 감시 디렉토리 기준 상대경로 (dest_path 가 지정된 경우 dest_path 하위)
+    pub fn get_dest_file_path(&self, relative_path: &str) -> String {
+        match self.dest_path() {
+            Some(dest_path) if !dest_path.trim_matches('/').is_empty() => {
+                format!("{}/{}", dest_path.trim_matches('/'), relative_path)
+            }
+            _ => relative_path.to_string(),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::common::*;
 
-use crate::traits::service::{file_service::*, request_service::*};
+use crate::traits::service::{
+    backup_service::*, file_service::*, hash_storage_service::*, history_service::*, outbox_service::*,
+    receive_service::*, request_service::*,
+};
 
 use crate::handler::master_handler::*;
 use crate::handler::slave_handler::*;
@@ -11,7 +14,15 @@ use crate::configs::configs::*;
 pub struct MainController<R, F>
 where
     R: RequestService + Sync + Send + 'static,
-    F: FileService + Sync + Send + 'static,
+    F: FileService
+        + HashStorageService
+        + HistoryService
+        + ReceiveService
+        + OutboxService
+        + BackupService
+        + Sync
+        + Send
+        + 'static,
 {
     req_service: Arc<R>,
     file_service: Arc<F>,
@@ -20,7 +31,15 @@ where
 impl<R, F> MainController<R, F>
 where
     R: RequestService + Sync + Send + 'static,
-    F: FileService + Sync + Send + 'static,
+    F: FileService
+        + HashStorageService
+        + HistoryService
+        + ReceiveService
+        + OutboxService
+        + BackupService
+        + Sync
+        + Send
+        + 'static,
 {
     pub fn new(req_service: Arc<R>, file_service: Arc<F>) -> Self {
         Self {
@@ -55,7 +74,7 @@ where
                 }
             }
         } else {
-            /* System role 이 slave 인 경우 */
+            /* System role 이 slave 또는 relay 인 경우 */
             let slave_handler: SlaveHandler<R, F> =
                 SlaveHandler::new(self.req_service.clone(), self.file_service.clone());
 
@@ -0,0 +1,63 @@
+use crate::common::*;
+
+#[doc = "Elasticsearch 요청의 최대 대기 시간"]
+pub const ELASTIC_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+static ELASTIC_CLIENT: once_lazy<Arc<ElasticClient>> = once_lazy::new(initialize_elastic_client);
+
+pub fn initialize_elastic_client() -> Arc<ElasticClient> {
+    let client: Client = Client::new();
+    Arc::new(ElasticClient::new(client))
+}
+
+pub fn get_elastic_client() -> Arc<ElasticClient> {
+    let elastic_client: &once_lazy<Arc<ElasticClient>> = &ELASTIC_CLIENT;
+    Arc::clone(elastic_client)
+}
+
+#[derive(Debug, Getters, Clone, new)]
+pub struct ElasticClient {
+    pub client: Client,
+}
+
+impl ElasticClient {
+    #[doc = "인덱스의 검색 분석기를 리로드해주는 함수 - `POST /<index>/_reload_search_analyzers`"]
+    /// 사전 파일을 사용하는 updateable 분석기가 변경된 사전 내용을 다시 읽어들인다.
+    ///
+    /// # Arguments
+    /// * `elastic_url` - Elasticsearch 주소 (예: http://localhost:9200)
+    /// * `index`       - 리로드할 인덱스
+    /// * `elastic_id`  - 기본 인증 아이디 (인증을 사용하지 않는 경우 None)
+    /// * `elastic_pw`  - 기본 인증 비밀번호
+    ///
+    /// # Returns
+    /// * Result<(u16, serde_json::Value), anyhow::Error> - 응답 코드와 응답 본문 (본문이 json 이 아닌 경우 문자열)
+    pub async fn reload_search_analyzers(
+        &self,
+        elastic_url: &str,
+        index: &str,
+        elastic_id: Option<&str>,
+        elastic_pw: Option<&str>,
+    ) -> Result<(u16, serde_json::Value), anyhow::Error> {
+        let url: String = format!(
+            "{}/{}/_reload_search_analyzers",
+            elastic_url.trim_end_matches('/'),
+            index
+        );
+
+        let mut request: reqwest::RequestBuilder = self.client.post(&url).timeout(ELASTIC_REQUEST_TIMEOUT);
+
+        if let Some(elastic_id) = elastic_id {
+            request = request.basic_auth(elastic_id, elastic_pw);
+        }
+
+        let response: reqwest::Response = request.send().await?;
+        let status_code: u16 = response.status().as_u16();
+        let response_text: String = response.text().await?;
+
+        let response_body: serde_json::Value = serde_json::from_str(&response_text)
+            .unwrap_or(serde_json::Value::String(response_text));
+
+        Ok((status_code, response_body))
+    }
+}
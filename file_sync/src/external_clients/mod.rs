@@ -1,2 +1,3 @@
+pub mod elastic_client;
 pub mod file_transfer_client;
 pub mod secure_file_transfer_client;
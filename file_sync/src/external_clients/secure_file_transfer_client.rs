@@ -1,5 +1,9 @@
 use crate::common::*;
 
+use crate::model::analyzer_reload_result::*;
+use crate::model::transfer_info::*;
+
+use crate::utils_modules::hash_utils::*;
 use crate::utils_modules::tls_utils::*;
 
 use hyper::Request;
@@ -9,7 +13,7 @@ use hyper_util::{
     client::legacy::Client,
     client::legacy::connect::HttpConnector
 };
-use http_body_util::Full;
+use http_body_util::{BodyExt, Full};
 use bytes::Bytes;
 use rustls::ClientConfig;
 
@@ -56,36 +60,68 @@ impl SecureFileTransferClient {
     /// # Arguments
     /// * `url`         - 요청(request)대상이 되는 서버의 url
     /// * `file_data`   - 파일 스트림 데이터
+    /// * `transfer_info` - 파일 데이터의 SHA-256 값과 버전
     /// * `file_path`   - 대상 파일
     /// * `from_host`   - 요청(request)을 보내는 호스트 주소
     /// * `to_host`     - 요청(request)을 받는 호스트 주소
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
+    /// * Result<(), anyhow::Error> - slave 가 검증한 해시값이 일치하는 경우에만 성공
     pub async fn send_file_to_url(
         &self,
         url: &str,
         file_data: &[u8],
+        transfer_info: &TransferInfo,
         file_path: &str,
         from_host: &str,
         to_host: &str,
     ) -> Result<(), anyhow::Error> {
         let body: Full<Bytes> = Full::new(Bytes::from(file_data.to_vec()));
 
-        let req: Request<Full<Bytes>> = Request::post(url)
+        let mut req_builder: hyper::http::request::Builder = Request::post(url)
             .header("Content-Type", "multipart/form-data")
             .header("X-File-Path", file_path)
             .header("X-From-Host", from_host)
-            .header("X-To-Host", to_host)
-            .body(body)?;
+            .header("X-To-Host", to_host);
+
+        for (header_name, header_value) in transfer_info.get_headers() {
+            req_builder = req_builder.header(header_name, header_value);
+        }
+
+        let req: Request<Full<Bytes>> = req_builder.body(body)?;
 
         let response: hyper::Response<hyper::body::Incoming> = self.client.request(req).await?;
 
         if response.status().is_success() {
+            /* slave 가 실제로 기록한 내용의 해시값이 일치해야 동기화된 것으로 본다. */
+            let verified_hash: &str = response
+                .headers()
+                .get(CONTENT_SHA256_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+
+            if verified_hash != transfer_info.content_hash() {
+                return Err(anyhow!(
+                    "[ERROR][SecureFileTransferClient->send_file_to_url] Content digest mismatch: {} // expected: {} // received: {} // file_path: {} // from_host: {} // to_host: {}",
+                    url, transfer_info.content_hash(), verified_hash, file_path, from_host, to_host
+                ));
+            }
+
             info!(
                 "Secure file transfer successful: {} // file_path: {} // from_host: {} // to_host: {}",
                 url, file_path, from_host, to_host
             );
+
+            /* slave 가 검색 분석기를 리로드한 경우 결과를 함께 남긴다. */
+            report_analyzer_reload(
+                response
+                    .headers()
+                    .get(ANALYZER_RELOAD_HEADER)
+                    .and_then(|value| value.to_str().ok()),
+                file_path,
+                from_host,
+                to_host,
+            );
             Ok(())
         } else {
             Err(anyhow!(
@@ -95,4 +131,104 @@ impl SecureFileTransferClient {
             ))
         }
     }
-}
\ No newline at end of file
+
+    #[doc = "HTTPS 요청을 처리 해주는 함수 - 파일 내용 없이 삭제/이름변경 같은 작업만 slave 에 전파해주는 함수"]
+    /// # Arguments
+    /// * `url`         - 요청(request)대상이 되는 서버의 url
+    /// * `operation`   - 전파할 작업 이름 (delete, rename)
+    /// * `file_path`   - 대상 파일
+    /// * `from_host`   - 요청(request)을 보내는 호스트 주소
+    /// * `to_host`     - 요청(request)을 받는 호스트 주소
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn send_operation_to_url(
+        &self,
+        url: &str,
+        operation: &str,
+        file_path: &str,
+        from_host: &str,
+        to_host: &str,
+    ) -> Result<(), anyhow::Error> {
+        let req: Request<Full<Bytes>> = Request::post(url)
+            .header("X-File-Path", file_path)
+            .header("X-From-Host", from_host)
+            .header("X-To-Host", to_host)
+            .body(Full::new(Bytes::new()))?;
+
+        let response: hyper::Response<hyper::body::Incoming> = self.client.request(req).await?;
+
+        if response.status().is_success() {
+            info!(
+                "Secure file {} successful: {} // file_path: {} // from_host: {} // to_host: {}",
+                operation, url, file_path, from_host, to_host
+            );
+
+            /* 이름변경된 파일로 검색 분석기를 리로드한 경우 결과를 함께 남긴다. */
+            report_analyzer_reload(
+                response
+                    .headers()
+                    .get(ANALYZER_RELOAD_HEADER)
+                    .and_then(|value| value.to_str().ok()),
+                file_path,
+                from_host,
+                to_host,
+            );
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "[ERROR][SecureFileTransferClient->send_operation_to_url] Secure file {} failed: {} // {} // file_path: {} // from_host: {} // to_host: {}",
+                operation,
+                response.status(),
+                url, file_path, from_host, to_host
+            ))
+        }
+    }
+
+    #[doc = "HTTPS GET 요청의 json 응답을 객체로 변환해주는 함수 - slave 의 manifest 조회 등에 사용"]
+    /// # Arguments
+    /// * `url` - 요청(request)대상이 되는 서버의 url
+    ///
+    /// # Returns
+    /// * Result<T, anyhow::Error>
+    pub async fn get_json_from_url<T: DeserializeOwned>(&self, url: &str) -> Result<T, anyhow::Error> {
+        let req: Request<Full<Bytes>> = Request::get(url).body(Full::new(Bytes::new()))?;
+
+        let response: hyper::Response<hyper::body::Incoming> = self.client.request(req).await?;
+
+        if response.status().is_success() {
+            let body: Bytes = response.into_body().collect().await?.to_bytes();
+            let res_json: T = serde_json::from_slice(&body)?;
+            Ok(res_json)
+        } else {
+            Err(anyhow!(
+                "[ERROR][SecureFileTransferClient->get_json_from_url] Failed to get response: {} // {}",
+                response.status(),
+                url
+            ))
+        }
+    }
+
+    #[doc = "HTTPS GET 요청으로 파일 데이터를 내려받는 함수 - pull 모드에서 master 의 파일을 가져올 때 사용"]
+    /// # Arguments
+    /// * `url` - 요청(request)대상이 되는 서버의 url
+    ///
+    /// # Returns
+    /// * Result<Vec<u8>, anyhow::Error>
+    pub async fn get_bytes_from_url(&self, url: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let req: Request<Full<Bytes>> = Request::get(url).body(Full::new(Bytes::new()))?;
+
+        let response: hyper::Response<hyper::body::Incoming> = self.client.request(req).await?;
+
+        if response.status().is_success() {
+            let body: Bytes = response.into_body().collect().await?.to_bytes();
+            Ok(body.to_vec())
+        } else {
+            Err(anyhow!(
+                "[ERROR][SecureFileTransferClient->get_bytes_from_url] Failed to download file: {} // {}",
+                response.status(),
+                url
+            ))
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use crate::common::*;
+
+use crate::traits::service::request_service::*;
+
+use crate::configs::configs::*;
+
+use crate::model::analyzer_reload_result::*;
+use crate::model::elastic_msg::*;
+
+use crate::utils_modules::logger_utils::*;
+
+#[derive(Debug)]
+pub struct AnalyzerReloadHandler<R>
+where
+    R: RequestService + Sync + Send + 'static,
+{
+    req_service: Arc<R>,
+    elastic_url: String, /* slave 로컬 Elasticsearch 주소 */
+}
+
+impl<R> AnalyzerReloadHandler<R>
+where
+    R: RequestService + Sync + Send + 'static,
+{
+    pub fn new(req_service: Arc<R>, elastic_url: String) -> Self {
+        Self {
+            req_service,
+            elastic_url,
+        }
+    }
+
+    #[doc = "반영된 파일을 사전으로 사용하는 인덱스들의 검색 분석기를 리로드해주는 함수"]
+    /// 파일은 이미 반영되었으므로 리로드에 실패하더라도 에러를 반환하지 않고 결과에 기록한다.
+    ///
+    /// # Arguments
+    /// * `file_name` - 반영된 파일의 이름 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<Option<AnalyzerReloadReport>, anyhow::Error> - 리로드할 인덱스가 설정되지 않은 파일인 경우 None
+    pub async fn reload_file_analyzers(
+        &self,
+        file_name: &str,
+    ) -> Result<Option<AnalyzerReloadReport>, anyhow::Error> {
+        let slave_host: String;
+        let indices: Vec<String>;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            slave_host = server_config.server.host().to_string();
+            indices = server_config.server.get_analyzer_reload_indices(file_name)?;
+        }
+
+        if indices.is_empty() {
+            return Ok(None);
+        }
+
+        let results: Vec<Result<AnalyzerReloadResult, anyhow::Error>> = join_all(
+            indices
+                .iter()
+                .map(|index| self.req_service.reload_search_analyzers(&self.elastic_url, index)),
+        )
+        .await;
+
+        let results: Vec<AnalyzerReloadResult> = results.into_iter().collect::<Result<_, _>>()?;
+        let reload_report: AnalyzerReloadReport = AnalyzerReloadReport::new(file_name.to_string(), results);
+
+        if reload_report.is_success() {
+            info!(
+                "Reloaded search analyzers for '{}' : {}",
+                file_name,
+                reload_report.get_summary()
+            );
+        } else {
+            error!(
+                "[Error][reload_file_analyzers()] Failed to reload search analyzers of {:?} for '{}'",
+                reload_report.get_failed_indices(),
+                file_name
+            );
+        }
+
+        let task_event: ElasticMsg = ElasticMsg::new(
+            &slave_host,
+            &self.elastic_url,
+            file_name,
+            reload_report.get_task_status(),
+            &reload_report.get_report(),
+        )?;
+        write_task_event_log(&task_event);
+
+        Ok(Some(reload_report))
+    }
+}
+
+#[doc = "검색 분석기 리로드 결과를 상위 서버에 보낼 응답에 담아주는 함수"]
+/// 헤더에는 요약(성공한 인덱스 수/전체 인덱스 수)을, 본문에는 인덱스별 결과를 담는다.
+///
+/// # Arguments
+/// * `response`        - 상위 서버에 보낼 응답 빌더
+/// * `message`         - 응답 본문
+/// * `reload_report`   - 검색 분석기 리로드 결과
+pub fn insert_analyzer_reload_report(
+    response: &mut HttpResponseBuilder,
+    message: &mut String,
+    reload_report: &AnalyzerReloadReport,
+) {
+    message.push_str(&format!("\n{}", reload_report.get_report()));
+    response.insert_header((ANALYZER_RELOAD_HEADER, reload_report.get_summary()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::service::request_service_impl::*;
+
+    use crate::env_config::env_config::*;
+
+    /* 모든 테스트가 같은 전역 설정을 사용하므로 한번만 기록한다. */
+    static TEST_CONFIG: std::sync::Once = std::sync::Once::new();
+
+    const TEST_CONFIG_TOML: &str = r#"
+[server]
+role = "slave"
+host = "127.0.0.1:9000"
+io_bound_improvement = false
+
+[[server.analyzer_reloads]]
+files = ["dict/synonyms*.txt"]
+indices = ["dict_ok", "dict_partial", "dict_missing"]
+
+[[server.analyzer_reloads]]
+files = ["dict/stopwords.txt"]
+indices = ["dict_ok"]
+"#;
+
+    /* 검색 분석기 리로드 설정이 담긴 설정 파일을 전역 설정으로 지정한다. */
+    fn init_test_config() {
+        TEST_CONFIG.call_once(|| {
+            let config_dir: PathBuf = std::env::temp_dir().join(format!("file_sync_test_{}", std::process::id()));
+            fs::create_dir_all(&config_dir).expect("failed to create the test config directory");
+
+            let config_path: PathBuf = config_dir.join("config.toml");
+            fs::write(&config_path, TEST_CONFIG_TOML).expect("failed to write the test config");
+
+            std::env::set_var("CONFIG_FILE_PATH", &config_path);
+            assert_eq!(CONFIG_FILE_PATH.as_str(), config_path.to_string_lossy());
+        });
+    }
+
+    /* 인덱스 이름에 따라 다른 결과를 응답하는 Elasticsearch 모의 핸들러 */
+    async fn mock_reload_handler(index: web::Path<String>) -> HttpResponse {
+        match index.as_str() {
+            "dict_ok" => HttpResponse::Ok().json(serde_json::json!({
+                "_shards": { "total": 2, "successful": 2, "failed": 0 },
+                "reload_details": [
+                    { "index": "dict_ok", "reloaded_analyzers": ["synonym_search"], "reloaded_node_ids": ["node_1"] }
+                ]
+            })),
+            "dict_partial" => HttpResponse::Ok().json(serde_json::json!({
+                "_shards": { "total": 2, "successful": 1, "failed": 1 },
+                "reload_details": [
+                    { "index": "dict_partial", "reloaded_analyzers": ["synonym_search"], "reloaded_node_ids": ["node_1"] }
+                ]
+            })),
+            _ => HttpResponse::NotFound().json(serde_json::json!({
+                "error": {
+                    "type": "index_not_found_exception",
+                    "reason": format!("no such index [{}]", index.as_str())
+                },
+                "status": 404
+            })),
+        }
+    }
+
+    /* 로컬 포트에 모의 Elasticsearch 를 띄우고 주소를 반환한다. */
+    fn start_mock_elastic() -> String {
+        let server = HttpServer::new(|| {
+            App::new().route(
+                "/{index}/_reload_search_analyzers",
+                web::post().to(mock_reload_handler),
+            )
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .expect("failed to bind the mock elasticsearch");
+
+        let mock_addr: std::net::SocketAddr = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+
+        format!("http://{}/", mock_addr)
+    }
+
+    /* 연결이 거부되는 주소 - 포트를 할당받은 뒤 바로 닫는다. */
+    fn get_closed_url() -> String {
+        let listener: std::net::TcpListener =
+            std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind a local port");
+        let closed_addr: std::net::SocketAddr = listener.local_addr().expect("failed to get the local address");
+        drop(listener);
+
+        format!("http://{}", closed_addr)
+    }
+
+    fn get_test_handler(elastic_url: String) -> AnalyzerReloadHandler<RequestServiceImpl> {
+        init_test_config();
+        AnalyzerReloadHandler::new(Arc::new(RequestServiceImpl::new()), elastic_url)
+    }
+
+    #[actix_web::test]
+    async fn reload_configured_indices() {
+        let handler: AnalyzerReloadHandler<RequestServiceImpl> = get_test_handler(start_mock_elastic());
+
+        let reload_report: AnalyzerReloadReport = handler
+            .reload_file_analyzers("dict/stopwords.txt")
+            .await
+            .expect("reload failed")
+            .expect("indices are configured for the file");
+
+        assert!(reload_report.is_success());
+        assert_eq!(reload_report.get_summary(), "1/1");
+        assert_eq!(reload_report.get_task_status(), ANALYZER_RELOAD_STATUS);
+        assert_eq!(reload_report.results()[0].index(), "dict_ok");
+    }
+
+    #[actix_web::test]
+    async fn reload_reports_failed_indices() {
+        let handler: AnalyzerReloadHandler<RequestServiceImpl> = get_test_handler(start_mock_elastic());
+
+        let reload_report: AnalyzerReloadReport = handler
+            .reload_file_analyzers("dict/synonyms_ko.txt")
+            .await
+            .expect("reload failed")
+            .expect("indices are configured for the file");
+
+        assert!(!reload_report.is_success());
+        assert_eq!(reload_report.get_summary(), "1/3");
+        assert_eq!(
+            reload_report.get_failed_indices(),
+            vec!["dict_partial".to_string(), "dict_missing".to_string()]
+        );
+        assert_eq!(reload_report.get_task_status(), ANALYZER_RELOAD_FAILED_STATUS);
+    }
+
+    #[actix_web::test]
+    async fn reload_connection_failure() {
+        let handler: AnalyzerReloadHandler<RequestServiceImpl> = get_test_handler(get_closed_url());
+
+        let reload_report: AnalyzerReloadReport = handler
+            .reload_file_analyzers("dict/stopwords.txt")
+            .await
+            .expect("a connection failure is recorded in the report")
+            .expect("indices are configured for the file");
+
+        assert!(!reload_report.is_success());
+        assert_eq!(*reload_report.results()[0].status_code(), None);
+        assert!(reload_report.results()[0].detail().starts_with("request failed:"));
+    }
+
+    #[actix_web::test]
+    async fn reload_skips_unmapped_file() {
+        let handler: AnalyzerReloadHandler<RequestServiceImpl> = get_test_handler(get_closed_url());
+
+        let reload_report: Option<AnalyzerReloadReport> = handler
+            .reload_file_analyzers("dict/user_dict.txt")
+            .await
+            .expect("reload failed");
+
+        assert!(reload_report.is_none());
+    }
+
+    #[actix_web::test]
+    async fn reload_report_reaches_response_header() {
+        let handler: AnalyzerReloadHandler<RequestServiceImpl> = get_test_handler(start_mock_elastic());
+
+        let reload_report: AnalyzerReloadReport = handler
+            .reload_file_analyzers("dict/synonyms_ko.txt")
+            .await
+            .expect("reload failed")
+            .expect("indices are configured for the file");
+
+        let mut response: HttpResponseBuilder = HttpResponse::Ok();
+        let mut message: String = "File uploaded successfully".to_string();
+        insert_analyzer_reload_report(&mut response, &mut message, &reload_report);
+
+        let response: HttpResponse = response.body(message.clone());
+        let reload_summary: Option<&str> = response
+            .headers()
+            .get(ANALYZER_RELOAD_HEADER)
+            .and_then(|value| value.to_str().ok());
+
+        assert_eq!(reload_summary, Some("1/3"));
+        assert!(message.starts_with("File uploaded successfully\n[analyzer reload 1/3]"));
+        assert!(message.contains("\ndict_missing : failed (status 404: no such index [dict_missing])"));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::common::*;
+
+use crate::traits::service::{hash_storage_service::*, request_service::*};
+
+use crate::configs::configs::*;
+
+use crate::model::elastic_msg::*;
+use crate::model::manifest_info::*;
+use crate::model::monitoring_path_info::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::logger_utils::*;
+
+#[derive(Debug)]
+pub struct AntiEntropyHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: HashStorageService + Sync + Send + 'static,
+{
+    req_service: Arc<R>,
+    file_service: Arc<F>,
+}
+
+impl<R, F> AntiEntropyHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: HashStorageService + Sync + Send + 'static,
+{
+    pub fn new(req_service: Arc<R>, file_service: Arc<F>) -> Self {
+        Self {
+            req_service,
+            file_service,
+        }
+    }
+
+    #[doc = "설정된 간격마다 slave 들의 상태를 점검하고 어긋난 파일을 복구해주는 함수 - 백그라운드 태스크로 실행된다."]
+    /// # Arguments
+    /// * `interval` - 점검 간격
+    pub async fn run(&self, interval: Duration) {
+        info!("Start anti-entropy loop. interval: {:?}", interval);
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            if let Err(e) = self.repair_slaves().await {
+                error!("[Error][AntiEntropyHandler->run()] {:?}", e);
+            }
+        }
+    }
+
+    #[doc = "각 slave 의 manifest 를 master 의 해시 저장소와 비교하여, 다르거나 누락된 파일만 다시 보내주는 함수"]
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn repair_slaves(&self) -> Result<(), anyhow::Error> {
+        let slave_url: Vec<String>;
+        let from_host: String;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            slave_url = server_config.server.slave_address().clone().unwrap_or_default();
+            from_host = server_config.server.host().to_string();
+        }
+
+        let monitor_file_list: Vec<MonitoringPathInfo> = get_monitoring_file_list()?;
+
+        for url in slave_url {
+            let manifest: ManifestInfo = match self.req_service.get_slave_manifest(&url).await {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    error!(
+                        "[Error][repair_slaves()] Failed to get manifest from '{}' : {:?}",
+                        url, e
+                    );
+                    continue;
+                }
+            };
+
+            for monitoring_file in &monitor_file_list {
+                /* 라우팅 규칙상 해당 slave 에 전달되지 않는 파일은 비교하지 않는다. */
+                if !get_target_slave_url_list(monitoring_file.file_path())?.contains(&url) {
+                    continue;
+                }
+
+                /* 경로 매핑이 설정된 경우 slave 에는 다른 경로로 저장되어 있다. */
+                let dest_file_name: String =
+                    get_slave_dest_file_name(monitoring_file.file_path(), &url)?;
+
+                let storage_hash: Vec<u8> = self
+                    .file_service
+                    .get_storage_hash(monitoring_file.full_file_path())?;
+                let master_hash: String = hash_to_hex(&storage_hash);
+
+                /* 아직 해시 저장소에 반영되지 않은 파일은 이벤트 루프에서 처리된다. */
+                if master_hash.is_empty() {
+                    continue;
+                }
+
+                let same_content: bool = manifest.get_file_hash(&dest_file_name) == Some(master_hash.as_str());
+
+                /*
+                    해시 저장소가 초기화된 경우 slave 가 master 보다 높은 버전을 가지고 있을 수 있다.
+                    - 낮은 버전은 slave 에서 거절되므로 slave 의 버전 이상으로 다시 맞춰준다. (내용이 다르면 더 높은 버전으로)
+                */
+                if let Some(slave_version) = manifest.get_file_version(&dest_file_name) {
+                    let min_version: u64 = if same_content { slave_version } else { slave_version + 1 };
+
+                    if self
+                        .file_service
+                        .rebase_storage_version(monitoring_file.full_file_path(), min_version)?
+                    {
+                        warn!(
+                            "[Warn][repair_slaves()] The version of '{}' was rebased to {} from the manifest of '{}'.",
+                            monitoring_file.full_file_path(),
+                            min_version,
+                            url
+                        );
+                    }
+                }
+
+                /* 이미 같은 내용을 가지고 있다면 수신 확인 정보만 맞춰준다. */
+                if same_content {
+                    self.file_service.update_acked_hash(
+                        monitoring_file.full_file_path(),
+                        &url,
+                        storage_hash,
+                    )?;
+                    continue;
+                }
+
+                info!(
+                    "The slave '{}' has a missing or stale file. : {}",
+                    url,
+                    dest_file_name
+                );
+
+                let repair_res: Result<(), anyhow::Error> = self
+                    .req_service
+                    .send_info_to_target_slave(
+                        monitoring_file.full_file_path(),
+                        &dest_file_name,
+                        vec![url.clone()],
+                        &master_hash,
+                        self.file_service
+                            .get_storage_version(monitoring_file.full_file_path())?,
+                    )
+                    .await;
+
+                /* 복구에 실패한 경우 다음 이벤트에서 다시 전달되도록 수신 확인 정보를 제거한다. */
+                match &repair_res {
+                    Ok(_) => self.file_service.update_acked_hash(
+                        monitoring_file.full_file_path(),
+                        &url,
+                        storage_hash,
+                    )?,
+                    Err(_) => self
+                        .file_service
+                        .remove_acked_hash(monitoring_file.full_file_path(), &url)?,
+                }
+
+                /* 복구 작업은 각각 하나의 작업 이벤트로 기록한다. */
+                let (task_status, task_detail) = match &repair_res {
+                    Ok(_) => ("success", "anti-entropy repair".to_string()),
+                    Err(e) => ("failed", format!("anti-entropy repair: {:?}", e)),
+                };
+
+                let task_event: ElasticMsg = ElasticMsg::new(
+                    &from_host,
+                    &url,
+                    &dest_file_name,
+                    task_status,
+                    &task_detail,
+                )?;
+
+                write_task_event_log(&task_event);
+            }
+        }
+
+        Ok(())
+    }
+}
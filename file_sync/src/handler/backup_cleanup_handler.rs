@@ -0,0 +1,57 @@
+use crate::common::*;
+
+use crate::traits::service::backup_service::*;
+
+#[derive(Debug, new)]
+pub struct BackupCleanupHandler<F>
+where
+    F: BackupService + Sync + Send + 'static,
+{
+    file_service: Arc<F>,
+}
+
+impl<F> BackupCleanupHandler<F>
+where
+    F: BackupService + Sync + Send + 'static,
+{
+    #[doc = "백업 디렉토리를 주기적으로 정리해주는 함수 - 백그라운드 태스크로 실행된다."]
+    /// 파일을 수신할 때마다 정리하지 않고, 설정된 간격마다 보관기간(backup_days)과 파일별 보관 정책을 적용한다.
+    ///
+    /// # Arguments
+    /// * `backup_dirs` - 정리할 백업 디렉토리 목록
+    /// * `interval`    - 정리 간격
+    pub async fn run(&self, backup_dirs: Vec<PathBuf>, interval: Duration) {
+        info!(
+            "Start backup cleanup loop. interval: {:?} // {:?}",
+            interval, backup_dirs
+        );
+
+        loop {
+            for backup_dir in &backup_dirs {
+                if let Err(e) = self.cleanup_backup_dir(backup_dir) {
+                    error!("[Error][BackupCleanupHandler->run()] {:?}", e);
+                }
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    #[doc = "백업 디렉토리 하나를 정리해주는 함수"]
+    /// # Arguments
+    /// * `backup_dir` - 백업 디렉토리 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn cleanup_backup_dir(&self, backup_dir: &Path) -> Result<(), anyhow::Error> {
+        /* 아직 백업된 파일이 없는 경우 */
+        if !backup_dir.is_dir() {
+            return Ok(());
+        }
+
+        /* 이전 형식으로 남아있는 백업도 보관기간과 보관 정책이 적용되도록 먼저 저장소로 옮긴다. */
+        self.file_service.import_legacy_backups(backup_dir)?;
+        self.file_service.backup_file_delete(backup_dir)?;
+        self.file_service.apply_backup_retention(backup_dir)
+    }
+}
@@ -0,0 +1,224 @@
+use crate::common::*;
+
+use crate::traits::service::{file_service::*, receive_service::*, request_service::*};
+
+use crate::configs::configs::*;
+
+use crate::model::elastic_msg::*;
+use crate::model::file_event_info::*;
+
+use crate::repository::receive_repository::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::io_utils::*;
+use crate::utils_modules::logger_utils::*;
+use crate::utils_modules::pattern_utils::*;
+
+#[derive(Debug, new)]
+pub struct LocalChangeHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: FileService + ReceiveService + Sync + Send + 'static,
+{
+    req_service: Arc<R>,
+    file_service: Arc<F>,
+}
+
+impl<R, F> LocalChangeHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: FileService + ReceiveService + Sync + Send + 'static,
+{
+    #[doc = "slave 의 감시 디렉토리를 감시하여 master 를 거치지 않고 직접 수정된 파일을 처리해주는 함수"]
+    /// 마지막으로 수신한 파일과 내용이 다른 경우 local_change_policy 에 따라 처리한다.
+    /// * report  - 변경사항을 보고만 한다. (기본값)
+    /// * revert  - 마지막으로 수신한 사본으로 되돌린다.
+    /// * forward - master 에 검토용으로 전달한다.
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn run(&self) -> Result<(), anyhow::Error> {
+        let watch_path: PathBuf;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            watch_path = PathBuf::from(server_config.server.watch_path());
+        }
+
+        if !watch_path.is_dir() {
+            return Err(anyhow!(
+                "[Error][LocalChangeHandler->run()] The watch path does not exist: {:?}",
+                watch_path
+            ));
+        }
+
+        /* hotwatch 는 정규화된 절대경로로 이벤트를 넘겨주므로 감시 경로도 정규화해준다. */
+        let watch_path: PathBuf =
+            PathBuf::from(watch_path.canonicalize()?.to_string_lossy().replace(r"\\?\", ""));
+
+        let mut hotwatch: Hotwatch = Hotwatch::new()?;
+        let (tx, mut rx) = unbounded_channel::<Result<FileEventInfo, String>>();
+
+        info!("Start watching local changes on the slave: {:?}", watch_path);
+
+        let self_file_service: Arc<F> = self.file_service.clone();
+
+        hotwatch.watch(&watch_path, move |event: Event| match &event.kind {
+            WatchEventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                self_file_service.file_event_process(&event, &tx, "Rename");
+            }
+            WatchEventKind::Modify(_) => {
+                self_file_service.file_event_process(&event, &tx, "Modify");
+            }
+            WatchEventKind::Create(_) => {
+                self_file_service.file_event_process(&event, &tx, "Create");
+            }
+            WatchEventKind::Remove(_) => {
+                self_file_service.file_event_process(&event, &tx, "Remove");
+            }
+            _ => {}
+        })?;
+
+        while let Some(file_event) = rx.recv().await {
+            let file_event: FileEventInfo = match file_event {
+                Ok(file_event) => file_event,
+                Err(e) => {
+                    error!("[Error][LocalChangeHandler->run()] {:?}", e);
+                    continue;
+                }
+            };
+
+            /* 이름변경의 경우 이전 경로의 파일도 사라진 것이므로 같이 확인해준다. */
+            let changed_paths: Vec<&String> = std::iter::once(file_event.file_path())
+                .chain(file_event.from_file_path().as_ref())
+                .collect();
+
+            for changed_path in changed_paths {
+                if let Err(e) = self
+                    .local_change_process(&watch_path, Path::new(changed_path))
+                    .await
+                {
+                    error!("[Error][LocalChangeHandler->run()] {:?}", e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc = "변경된 파일이 마지막으로 수신한 파일과 다른 경우 정책에 맞게 처리해주는 함수"]
+    /// # Arguments
+    /// * `watch_path`  - slave 의 감시 디렉토리 (정규화된 경로)
+    /// * `file_path`   - 변경이 감지된 파일 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn local_change_process(&self, watch_path: &Path, file_path: &Path) -> Result<(), anyhow::Error> {
+        if is_staging_file(file_path) || file_path.is_dir() {
+            return Ok(());
+        }
+
+        let file_name: String = match get_relative_path_str(watch_path, file_path) {
+            Some(file_name) => file_name,
+            None => return Ok(()),
+        };
+
+        /* master 로부터 받은 적이 없는 파일은 동기화 대상이 아니므로 무시한다. */
+        let receive_info: ReceiveFileInfo = match self.file_service.get_receive_file_info(&file_name)? {
+            Some(receive_info) => receive_info,
+            None => return Ok(()),
+        };
+
+        /* 파일이 삭제된 경우에는 빈 해시값으로 비교한다. */
+        let local_hash: String = if file_path.exists() {
+            hash_to_hex(&conpute_hash(file_path)?)
+        } else {
+            String::new()
+        };
+
+        if local_hash.eq_ignore_ascii_case(receive_info.hash()) {
+            return Ok(());
+        }
+
+        let local_change_policy: String;
+        let review_master_url: Option<String>;
+        let slave_backup_path: String;
+        let slave_host: String;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            local_change_policy = server_config.server.get_local_change_policy();
+            review_master_url = server_config.server.review_master_url().clone();
+            slave_backup_path = server_config
+                .server
+                .slave_backup_path()
+                .clone()
+                .unwrap_or_default();
+            slave_host = server_config.server.host().clone();
+        }
+
+        warn!(
+            "The file '{}' was modified locally on the slave. received: {} // local: {}",
+            file_name,
+            receive_info.hash(),
+            if local_hash.is_empty() { "deleted" } else { &local_hash }
+        );
+
+        let (task_status, task_detail, to_host): (&str, String, String) =
+            match (local_change_policy.as_str(), review_master_url) {
+                ("revert", _) => {
+                    match self
+                        .file_service
+                        .restore_last_good_copy(file_path, &slave_backup_path, &file_name)
+                    {
+                        Ok(true) => (
+                            "reverted",
+                            "local modification reverted to the last received copy".to_string(),
+                            slave_host.clone(),
+                        ),
+                        Ok(false) => (
+                            "failed",
+                            "the last received copy does not exist".to_string(),
+                            slave_host.clone(),
+                        ),
+                        Err(e) => ("failed", format!("{:?}", e), slave_host.clone()),
+                    }
+                }
+                ("forward", Some(master_url)) if !local_hash.is_empty() => {
+                    match self
+                        .req_service
+                        .send_review_to_master(
+                            &master_url,
+                            &file_path.to_string_lossy(),
+                            &file_name,
+                            receive_info.version().unwrap_or_default(),
+                        )
+                        .await
+                    {
+                        Ok(_) => (
+                            "forwarded",
+                            "local modification forwarded to the master for review".to_string(),
+                            master_url,
+                        ),
+                        Err(e) => ("failed", format!("{:?}", e), master_url),
+                    }
+                }
+                _ => (
+                    "drift",
+                    "local modification detected".to_string(),
+                    slave_host.clone(),
+                ),
+            };
+
+        if task_status == "failed" {
+            error!(
+                "[Error][local_change_process()] Failed to handle the local modification of '{}': {}",
+                file_name, task_detail
+            );
+        }
+
+        let task_event: ElasticMsg =
+            ElasticMsg::new(&slave_host, &to_host, &file_name, task_status, &task_detail)?;
+        write_task_event_log(&task_event);
+
+        Ok(())
+    }
+}
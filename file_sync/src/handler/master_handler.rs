@@ -3,134 +3,254 @@ use crate::common::*;
 // use crate::service::file_service_impl::*;
 // use crate::service::request_service_impl::*;
 
-use crate::traits::service::{file_service::*, request_service::*};
+use crate::traits::service::{
+    backup_service::*, file_service::*, hash_storage_service::*, history_service::*, outbox_service::*,
+    request_service::*,
+};
+
+use crate::handler::anti_entropy_handler::*;
+use crate::handler::backup_cleanup_handler::*;
+use crate::handler::outbox_handler::*;
+use crate::handler::rollback_handler::*;
+
+use crate::middleware::middle_ware::*;
+
+use crate::router::master_router::*;
+
+use crate::utils_modules::tls_utils::*;
 
 use crate::configs::configs::*;
+use crate::configs::routing_rule_config::*;
+use crate::configs::watch_root_config::*;
 
+use crate::model::file_event_info::*;
 use crate::model::monitoring_path_info::*;
+use crate::model::pending_file_event::*;
+
+use crate::repository::history_repository::*;
+
+use crate::utils_modules::hash_utils::*;
+
+use crate::utils_modules::io_utils::*;
 
 #[derive(Debug)]
 pub struct MasterHandler<R, F>
 where
     R: RequestService + Sync + Send + 'static,
-    F: FileService + Sync + Send + 'static,
+    F: FileService
+        + HashStorageService
+        + HistoryService
+        + OutboxService
+        + BackupService
+        + Sync
+        + Send
+        + 'static,
 {
     req_service: Arc<R>,
     file_service: Arc<F>,
+    outbox_handler: Arc<OutboxHandler<R, F>>,
 }
 
 impl<R, F> MasterHandler<R, F>
 where
     R: RequestService + Sync + Send + 'static,
-    F: FileService + Sync + Send + 'static,
+    F: FileService
+        + HashStorageService
+        + HistoryService
+        + OutboxService
+        + BackupService
+        + Sync
+        + Send
+        + 'static,
 {
     pub fn new(req_service: Arc<R>, file_service: Arc<F>) -> Self {
+        let outbox_handler: Arc<OutboxHandler<R, F>> =
+            Arc::new(OutboxHandler::new(req_service.clone(), file_service.clone()));
+
         Self {
             req_service,
             file_service,
+            outbox_handler,
         }
     }
 
     #[doc = "프로그램 role 이 master 인경우의 작업"]
     pub async fn run(&self) -> Result<(), anyhow::Error> {
-        /* 감시파일 경로 */
-        let watch_dir_path: String;
+        /* 감시 디렉토리 목록 */
+        let watch_roots: Vec<WatchRootConfig>;
+        let anti_entropy_interval: Option<Duration>;
+        let pull_mode: bool;
+        let quiet_period: Duration;
+        let history_enabled: bool;
+        let review_path: Option<String>;
+        let backup_cleanup_interval: Duration;
         {
             let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
-            watch_dir_path = server_config.server.get_watch_dir_path();
+            watch_roots = server_config.server.get_watch_roots();
+            quiet_period = server_config.server.get_debounce_quiet_period();
+            history_enabled = server_config.server.is_history_enabled();
+            review_path = server_config.server.review_path().clone();
+            backup_cleanup_interval = server_config.server.get_backup_cleanup_interval();
+            anti_entropy_interval = server_config.server.get_anti_entropy_interval();
+            pull_mode = server_config.server.is_pull_mode();
+        }
+
+        self.log_routing_rules()?;
+
+        /*
+            pull 모드이거나 slave 의 수정파일 검토를 받는 경우 slave 들이 접근할 수 있는 서버를 띄워준다.
+            - 배포 이력을 보관하는 경우 관리자가 이력 조회 및 롤백을 요청할 수 있도록 서버를 띄워준다.
+        */
+        if pull_mode || review_path.is_some() || history_enabled {
+            self.start_master_server()?;
+        }
+
+        /* 검토용으로 받은 파일의 백업을 주기적으로 정리해주는 백그라운드 태스크 */
+        if let Some(review_path) = review_path {
+            let backup_cleanup_handler: BackupCleanupHandler<F> =
+                BackupCleanupHandler::new(self.file_service.clone());
+
+            task::spawn(async move {
+                backup_cleanup_handler
+                    .run(vec![Path::new(&review_path).join("backup")], backup_cleanup_interval)
+                    .await;
+            });
         }
 
         let mut hotwatch: Hotwatch = Hotwatch::new()?;
 
-        /* 해당 파일을 계속 감시해준다. */
-        let (tx, rx) = channel::<Result<String, String>>();
+        /* 해당 파일을 계속 감시해준다. -> 모든 감시 디렉토리가 하나의 이벤트 루프를 공유한다. */
+        let (tx, mut rx) = unbounded_channel::<Result<FileEventInfo, String>>();
 
-        let self_file_service: Arc<F> = self.file_service.clone(); /* self.file_service 의 참조 복사본 생성 */
+        for watch_root in watch_roots {
+            let self_file_service: Arc<F> = self.file_service.clone(); /* self.file_service 의 참조 복사본 생성 */
+            let tx: UnboundedSender<Result<FileEventInfo, String>> = tx.clone();
 
-        /* tx 부분 - 파일변경 감시해주는 부분 */
-        hotwatch.watch(watch_dir_path, move |event: Event| match &event.kind {
-            WatchEventKind::Modify(_) => {
-                self_file_service.file_event_process(&event, &tx, "Modify");
-            }
-            WatchEventKind::Create(_) => {
-                self_file_service.file_event_process(&event, &tx, "Create");
-            }
-            WatchEventKind::Remove(_) => {
+            info!(
+                "Start watching '{}' : {} // {:?}",
+                watch_root.name(),
+                watch_root.watch_path(),
+                watch_root.specific_files()
+            );
 
-                self_file_service.file_event_process(&event, &tx, "Remove");
-            }
-            _ => {
-                warn!(
-                    "[Warn][master_handler -> main()] Undetectable event: kind = {:?}, paths = {:?}",
-                    &event.kind, &event.paths
-                )
-            }
-        })?;
+            /* tx 부분 - 파일변경 감시해주는 부분 */
+            hotwatch.watch(watch_root.watch_path(), move |event: Event| match &event.kind {
+                WatchEventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                    self_file_service.file_event_process(&event, &tx, "Rename");
+                }
+                WatchEventKind::Modify(_) => {
+                    self_file_service.file_event_process(&event, &tx, "Modify");
+                }
+                WatchEventKind::Create(_) => {
+                    self_file_service.file_event_process(&event, &tx, "Create");
+                }
+                WatchEventKind::Remove(_) => {
+                    self_file_service.file_event_process(&event, &tx, "Remove");
+                }
+                _ => {
+                    warn!(
+                        "[Warn][master_handler -> main()] Undetectable event: kind = {:?}, paths = {:?}",
+                        &event.kind, &event.paths
+                    )
+                }
+            })?;
+        }
+
+        /* 이벤트 루프가 감시 종료를 감지할 수 있도록 원본 송신자는 제거한다. */
+        drop(tx);
+
+        /* 전달에 실패한 작업을 재시도해주는 백그라운드 태스크 -> 이전 실행에서 남은 작업도 이어서 처리된다. */
+        if !pull_mode {
+            let outbox_handler: Arc<OutboxHandler<R, F>> = Arc::clone(&self.outbox_handler);
+
+            task::spawn(async move {
+                outbox_handler.run().await;
+            });
+        }
 
         /*
-            rx 부분 - receive 를 계속 감시한다.
+            프로그램이 중지되어 있던 동안의 변경사항을 먼저 동기화해준다.
+            - 감시는 이미 시작되었으므로 동기화 중에 발생한 이벤트는 채널에 쌓여있다가 이후에 처리된다.
         */
-        for received in rx {
-            match received {
-                Ok(file_path) => {
-                    /* 일단 해당 파일이 모니터링 대상인지 확인을 먼저 함 */
-                    let monitor_file_list: Vec<MonitoringPathInfo>;
-                    {
-                        monitor_file_list = get_monitoring_file_detail_path()?;
-                    }
+        if let Err(e) = self.reconcile_on_startup().await {
+            error!("[Error][run()] Startup reconciliation failed: {:?}", e);
+        }
 
-                    let mut monitor_yn: bool = false;
-                    let mut short_file_path: String = String::new();
+        /* slave 들의 상태를 주기적으로 점검하여 복구해주는 백그라운드 태스크 -> pull 모드에서는 slave 가 직접 점검한다. */
+        if let (Some(interval), false) = (anti_entropy_interval, pull_mode) {
+            let anti_entropy_handler: AntiEntropyHandler<R, F> =
+                AntiEntropyHandler::new(self.req_service.clone(), self.file_service.clone());
 
-                    for inner_file in monitor_file_list {
-                        let inner_file_path: &Path = Path::new(inner_file.full_file_path());
-                        let file_path: &Path = Path::new(&file_path);
+            task::spawn(async move {
+                anti_entropy_handler.run(interval).await;
+            });
+        }
 
-                        /* 현재 이벤트가 발생한 파일이 내가 모니터링 대상으로 지정한 파일인지 체크해줌 */
-                        if inner_file_path == file_path {
-                            monitor_yn = true;
-                            short_file_path = inner_file.file_path().to_string();
-                            break;
-                        }
+        /*
+            rx 부분 - receive 를 계속 감시한다.
+            - 같은 파일에 연속으로 발생한 이벤트는 하나로 합쳐서, 파일이 더 이상 변하지 않을 때 한번만 처리한다.
+        */
+        let mut pending_events: HashMap<String, PendingFileEvent> = HashMap::new();
+
+        loop {
+            /* 가장 먼저 처리 시각이 되는 파일이 있다면 그 시각까지만 기다린다. */
+            let next_deadline: Option<Instant> =
+                pending_events.values().map(|pending| *pending.deadline()).min();
+
+            let received: Option<Result<FileEventInfo, String>> = match next_deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, rx.recv()).await {
+                    Ok(received) => received,
+                    Err(_) => {
+                        self.flush_pending_events(&mut pending_events, quiet_period, false)
+                            .await;
+                        continue;
                     }
+                },
+                None => rx.recv().await,
+            };
 
-                    if monitor_yn {
-                        /* 모니터링 대상 파일이 맞는 경우 */
-                        let file_name_path: &Path = Path::new(&file_path);
-
-                        /* 이벤트가 발생한 파일의 내용이 이전과 다른지 판단하기 위함. */
-                        let modify_yn: bool =
-                            match self.file_service.comparison_file(file_name_path) {
-                                Ok(watch_res) => watch_res,
-                                Err(e) => {
-                                    error!("[Error][run() -> watch_res]{:?}", e);
-                                    continue;
-                                }
-                            };
-
-                        if modify_yn {
-                            match self
-                                .req_service
-                                .send_info_to_slave(&file_path, &short_file_path)
+            /* 감시가 종료된 경우 대기중인 이벤트를 모두 처리하고 종료한다. */
+            let Some(received) = received else {
+                self.flush_pending_events(&mut pending_events, quiet_period, true)
+                    .await;
+                break;
+            };
+
+            match received {
+                Ok(file_event) => {
+                    match file_event.from_file_path() {
+                        Some(from_file_path) => {
+                            /* 이름변경은 바로 처리한다. -> 두 경로에 대기중인 이벤트는 이름변경 처리에 포함된다. */
+                            pending_events.remove(from_file_path);
+                            pending_events.remove(file_event.file_path());
+
+                            if let Err(e) = self
+                                .rename_event_process(from_file_path, file_event.file_path())
                                 .await
                             {
-                                Ok(_) => {
-                                    info!(
-                                        "Successfully sent files to slave servers. : {}",
-                                        &short_file_path
-                                    );
-                                }
-                                Err(e) => {
-                                    error!("[Error][run() -> modify_yn] {:?}", e);
-                                    continue;
-                                }
+                                error!(
+                                    "[Error][run()] Failed to process '{}' event of '{}': {:?}",
+                                    file_event.event_type(),
+                                    file_event.file_path(),
+                                    e
+                                );
                             }
-                        } else {
-                            info!("This file has not been modified.: {}", &file_path);
                         }
-                    } else {
-                        /* 모니터링 대상 파일이 아닌 경우 */
-                        info!("The file '{}' is not a monitoring target file.", &file_path);
+                        None if quiet_period.is_zero() => {
+                            self.pending_event_process(file_event.file_path(), file_event.event_type())
+                                .await;
+                        }
+                        None => {
+                            /* 이벤트가 발생할 때마다 처리 시각을 뒤로 미뤄준다. */
+                            pending_events.insert(
+                                file_event.file_path().clone(),
+                                PendingFileEvent::new(
+                                    file_event.event_type().clone(),
+                                    Instant::now() + quiet_period,
+                                    get_file_stat(Path::new(file_event.file_path())),
+                                ),
+                            );
+                        }
                     }
                 }
                 Err(e) => {
@@ -146,4 +266,454 @@ where
 
         Ok(())
     }
+
+    #[doc = "처리 시각이 된 대기 이벤트 중 파일이 안정화된 것만 처리해주는 함수"]
+    /// 대기 시간 동안 파일의 크기나 수정시각이 바뀌었다면 아직 기록 중인 것으로 보고 다시 기다린다.
+    ///
+    /// # Arguments
+    /// * `pending_events`  - 파일별 대기 이벤트
+    /// * `quiet_period`    - 파일이 변하지 않아야 하는 시간
+    /// * `flush_all`       - 처리 시각과 관계없이 모두 처리할지 여부 (감시 종료 시)
+    async fn flush_pending_events(
+        &self,
+        pending_events: &mut HashMap<String, PendingFileEvent>,
+        quiet_period: Duration,
+        flush_all: bool,
+    ) {
+        let now: Instant = Instant::now();
+
+        let due_file_paths: Vec<String> = pending_events
+            .iter()
+            .filter(|(_, pending)| flush_all || *pending.deadline() <= now)
+            .map(|(file_path, _)| file_path.clone())
+            .collect();
+
+        for file_path in due_file_paths {
+            let pending: PendingFileEvent = match pending_events.remove(&file_path) {
+                Some(pending) => pending,
+                None => continue,
+            };
+
+            let file_stat: Option<(u64, SystemTime)> = get_file_stat(Path::new(&file_path));
+
+            if !flush_all && file_stat != *pending.file_stat() {
+                pending_events.insert(
+                    file_path,
+                    PendingFileEvent::new(pending.event_type().clone(), now + quiet_period, file_stat),
+                );
+                continue;
+            }
+
+            self.pending_event_process(&file_path, pending.event_type())
+                .await;
+        }
+    }
+
+    #[doc = "파일 생성/수정/삭제 이벤트를 처리하고 실패한 경우 에러를 기록해주는 함수"]
+    /// # Arguments
+    /// * `file_path`   - 이벤트가 발생한 파일 경로
+    /// * `event_type`  - 마지막으로 발생한 이벤트 종류
+    async fn pending_event_process(&self, file_path: &str, event_type: &str) {
+        if let Err(e) = self.file_change_process(file_path).await {
+            error!(
+                "[Error][run()] Failed to process '{}' event of '{}': {:?}",
+                event_type, file_path, e
+            );
+        }
+    }
+
+    #[doc = "slave 그룹, 파일 라우팅 규칙, 경로 매핑을 로그로 남겨주는 함수 - 잘못 설정된 그룹이나 경로는 경고한다."]
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn log_routing_rules(&self) -> Result<(), anyhow::Error> {
+        let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+        let slave_address: Vec<String> = server_config.server.slave_address().clone().unwrap_or_default();
+
+        for path_mapping in server_config.server.path_mappings().as_deref().unwrap_or_default() {
+            info!(
+                "Path mapping : '{}' -> '{}' (slaves: {:?}, groups: {:?})",
+                path_mapping.file(),
+                path_mapping.dest(),
+                path_mapping.slaves().as_deref().unwrap_or_default(),
+                path_mapping.groups().as_deref().unwrap_or_default()
+            );
+
+            if let Err(e) = get_sync_target_path(Path::new(""), path_mapping.dest().trim_matches('/')) {
+                warn!("[Warn][log_routing_rules()] Invalid path mapping destination: {:?}", e);
+            }
+        }
+
+        let routing_rules: &[RoutingRuleConfig] =
+            server_config.server.routing_rules().as_deref().unwrap_or_default();
+
+        if routing_rules.is_empty() {
+            info!("Routing: every file is sent to all slaves {:?}", slave_address);
+            return Ok(());
+        }
+
+        if let Some(slave_groups) = server_config.server.slave_groups() {
+            for (group_name, group_slaves) in slave_groups {
+                info!("Slave group '{}' : {:?}", group_name, group_slaves);
+
+                for slave in group_slaves.iter().filter(|slave| !slave_address.contains(slave)) {
+                    warn!(
+                        "[Warn][log_routing_rules()] The slave '{}' of group '{}' is not listed in 'slave_address'.",
+                        slave, group_name
+                    );
+                }
+            }
+        }
+
+        for (index, routing_rule) in routing_rules.iter().enumerate() {
+            info!(
+                "Routing rule #{} : {:?} -> {:?}",
+                index + 1,
+                routing_rule.files(),
+                routing_rule.groups()
+            );
+
+            for group_name in routing_rule.groups() {
+                let exists_yn: bool = server_config
+                    .server
+                    .slave_groups()
+                    .as_ref()
+                    .is_some_and(|slave_groups| slave_groups.contains_key(group_name));
+
+                if !exists_yn {
+                    warn!(
+                        "[Warn][log_routing_rules()] The routing rule #{} refers to an unknown slave group '{}'.",
+                        index + 1,
+                        group_name
+                    );
+                }
+            }
+        }
+
+        info!("Routing: files without a matching rule are sent to all slaves {:?}", slave_address);
+
+        Ok(())
+    }
+
+    #[doc = "pull 모드 여부를 확인해주는 함수"]
+    fn is_pull_mode(&self) -> Result<bool, anyhow::Error> {
+        let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+        Ok(server_config.server.is_pull_mode())
+    }
+
+    #[doc = "slave 가 manifest 와 파일을 조회하거나 수정파일 검토를 요청하고, 관리자가 롤백을 요청할 수 있도록 master 서버를 백그라운드로 실행해주는 함수"]
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn start_master_server(&self) -> Result<(), anyhow::Error> {
+        let master_host: String;
+        let allowed_ip_list: Vec<String>;
+        let secure_mode: bool;
+        let history_enabled: bool;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            master_host = server_config.server.host().to_string();
+            /* 관리자는 배포 이력 조회/롤백만 요청할 수 있다. -> 핸들러에서 한번 더 확인한다. */
+            allowed_ip_list = server_config
+                .server
+                .get_slave_ip_list()
+                .into_iter()
+                .chain(server_config.server.get_admin_ip_list())
+                .collect();
+            secure_mode = server_config.server.is_secure_mode();
+            history_enabled = server_config.server.is_history_enabled();
+        }
+
+        let file_service: Arc<F> = self.file_service.clone();
+
+        let rollback_handler: Option<Arc<RollbackHandler<R, F>>> = if history_enabled {
+            Some(Arc::new(RollbackHandler::new(
+                self.req_service.clone(),
+                self.file_service.clone(),
+            )))
+        } else {
+            None
+        };
+
+        let http_server = HttpServer::new(move || {
+            let app = App::new()
+                .wrap(CheckIp::new(allowed_ip_list.clone()))
+                .configure(MasterRouter::configure_routes)
+                .app_data(web::Data::new(file_service.clone()));
+
+            match &rollback_handler {
+                Some(rollback_handler) => app.app_data(web::Data::new(rollback_handler.clone())),
+                None => app,
+            }
+        });
+
+        /* TLS 를 적용한 경우 */
+        let server: actix_web::dev::Server = if secure_mode {
+            let tls_config: rustls::ServerConfig = create_server_tls_config().map_err(|e| {
+                anyhow!("[ERROR][MasterHandler->start_master_server] Failed to create TLS config: {}", e)
+            })?;
+
+            info!("Starting secure master server with mTLS on: {}", master_host);
+            http_server.bind_rustls_0_23(&master_host, tls_config)?.run()
+        } else {
+            info!("Starting regular HTTP master server on: {}", master_host);
+            http_server.bind(&master_host)?.run()
+        };
+
+        task::spawn(async move {
+            if let Err(e) = server.await {
+                error!("[Error][start_master_server()] {:?}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    #[doc = "프로그램 시작 시 모니터링 대상 파일들을 해시 저장소 및 slave 들의 상태와 비교하여 동기화해주는 함수"]
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn reconcile_on_startup(&self) -> Result<(), anyhow::Error> {
+        info!("Start reconciling monitoring files with hash storage and slave servers.");
+
+        let monitor_file_list: Vec<MonitoringPathInfo> = get_monitoring_file_list()?;
+
+        /* 1. 프로그램이 중지된 동안 수정/생성된 파일 */
+        for monitoring_file in &monitor_file_list {
+            if let Err(e) = self
+                .file_change_process(monitoring_file.full_file_path())
+                .await
+            {
+                error!("[Error][reconcile_on_startup()] {:?}", e);
+            }
+        }
+
+        /* 2. 프로그램이 중지된 동안 삭제된 파일 */
+        for stored_file_path in self.file_service.get_storage_file_list()? {
+            if Path::new(&stored_file_path).exists() {
+                continue;
+            }
+
+            if let Err(e) = self.file_change_process(&stored_file_path).await {
+                error!("[Error][reconcile_on_startup()] {:?}", e);
+            }
+        }
+
+        /* 3. slave 에 존재하지 않거나 이전 내용을 가지고 있는 파일 -> pull 모드에서는 slave 가 직접 가져간다. */
+        if !self.is_pull_mode()? {
+            AntiEntropyHandler::new(self.req_service.clone(), self.file_service.clone())
+                .repair_slaves()
+                .await?;
+        }
+
+        info!("Startup reconciliation completed.");
+        Ok(())
+    }
+
+    #[doc = "파일 생성/수정/삭제 이벤트를 처리해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 이벤트가 발생한 파일 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn file_change_process(&self, file_path: &str) -> Result<(), anyhow::Error> {
+        /* 일단 해당 파일이 모니터링 대상(패턴)인지 확인을 먼저 함 */
+        let monitoring_file: MonitoringPathInfo = match get_monitoring_file_info(file_path)? {
+            Some(monitoring_file) => monitoring_file,
+            None => {
+                /* 모니터링 대상 파일이 아닌 경우 */
+                info!("The file '{}' is not a monitoring target file.", file_path);
+                return Ok(());
+            }
+        };
+
+        let file_path: &str = monitoring_file.full_file_path();
+        let short_file_path: &str = monitoring_file.file_path();
+        let file_name_path: &Path = Path::new(file_path);
+
+        /* 디렉토리 자체의 이벤트는 동기화 대상이 아니다. */
+        if file_name_path.is_dir() {
+            return Ok(());
+        }
+
+        if !file_name_path.exists() {
+            /* 모니터링 대상 파일이 삭제된 경우 -> slave 에도 삭제를 전파해준다. */
+            if self.file_service.comparison_deleted_file(file_name_path)? && !self.is_pull_mode()? {
+                self.outbox_handler
+                    .deliver_delete(file_path, short_file_path)
+                    .await?;
+
+                info!(
+                    "Successfully sent file deletion to slave servers. : [{}] {}",
+                    monitoring_file.root_name(),
+                    short_file_path
+                );
+            }
+
+            return Ok(());
+        }
+
+        /* 롤백으로 되돌린 파일은 롤백 처리에서 이전 버전을 수신했던 slave 에만 배포하므로 다시 배포하지 않는다. */
+        if let Some(rollback_hash) = get_rollback_write_hash(file_path)? {
+            let event_hash: String = conpute_hash(file_name_path)
+                .map(|hash| hash_to_hex(&hash))
+                .unwrap_or_default();
+
+            if event_hash == rollback_hash {
+                info!("The file '{}' was restored by a rollback. Skip the event.", file_path);
+                return Ok(());
+            }
+        }
+
+        /* 라우팅 규칙에 따라 해당 파일을 전달받는 slave 들 */
+        let slave_url: Vec<String> = get_target_slave_url_list(short_file_path)?;
+
+        /* 이벤트가 발생한 파일의 내용이 이전과 다른지 판단하기 위함. */
+        if !self.file_service.comparison_file(file_name_path, &slave_url)? {
+            info!("This file has not been modified.: {}", file_path);
+            return Ok(());
+        }
+
+        /* 배포하는 버전을 이력에 남겨 롤백할 수 있도록 한다. */
+        let file_version: u64 = self.file_service.get_storage_version(file_path)?;
+        self.file_service
+            .record_history_version(file_path, short_file_path, file_version, None)?;
+
+        if self.is_pull_mode()? {
+            /* pull 모드에서는 slave 들이 다음 조회 시 변경된 파일을 가져간다. */
+            info!(
+                "The file will be pulled by slave servers. : [{}] {}",
+                monitoring_file.root_name(),
+                short_file_path
+            );
+        } else {
+            self.outbox_handler
+                .deliver_file(file_path, short_file_path)
+                .await?;
+
+            info!(
+                "Successfully sent files to slave servers. : [{}] {}",
+                monitoring_file.root_name(),
+                short_file_path
+            );
+        }
+
+        Ok(())
+    }
+
+    #[doc = "파일 이름변경(이동) 이벤트를 처리해주는 함수"]
+    /// # Arguments
+    /// * `from_file_path`  - 변경 전 파일 경로
+    /// * `to_file_path`    - 변경 후 파일 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn rename_event_process(
+        &self,
+        from_file_path: &str,
+        to_file_path: &str,
+    ) -> Result<(), anyhow::Error> {
+        let from_monitoring_file: Option<MonitoringPathInfo> =
+            get_monitoring_file_info(from_file_path)?;
+        let to_monitoring_file: Option<MonitoringPathInfo> =
+            get_monitoring_file_info(to_file_path)?;
+
+        let (from_short_file_path, to_short_file_path) =
+            match (from_monitoring_file, to_monitoring_file) {
+                (Some(from_file), Some(to_file)) => {
+                    (from_file.file_path().to_string(), to_file.file_path().to_string())
+                }
+                /* 모니터링 대상이 아닌 이름으로 변경된 경우 -> 삭제로 간주 */
+                (Some(_), None) => return self.file_change_process(from_file_path).await,
+                /* 모니터링 대상이 아니었던 파일이 대상 이름으로 변경된 경우 -> 생성으로 간주 */
+                (None, Some(_)) => return self.file_change_process(to_file_path).await,
+                (None, None) => {
+                    info!(
+                        "The file '{}' -> '{}' is not a monitoring target file.",
+                        from_file_path, to_file_path
+                    );
+                    return Ok(());
+                }
+            };
+
+        let rename_yn: bool = self
+            .file_service
+            .comparison_renamed_file(Path::new(from_file_path), Path::new(to_file_path))?;
+
+        if !rename_yn || self.is_pull_mode()? {
+            /* 내용이 바뀐 경우 -> 새 파일 전송 후 이전 파일 삭제 (pull 모드에서는 해시 저장소만 갱신된다.) */
+            self.file_change_process(to_file_path).await?;
+            return self.file_change_process(from_file_path).await;
+        }
+
+        /*
+            라우팅 규칙이나 경로 매핑에 따라 이름변경 전후의 전달 대상과 저장 경로가 다를 수 있다.
+            - 양쪽 모두에 속하고 경로 매핑이 없는 slave 에만 이름변경을 보내고, 나머지는 파일 전송 및 삭제로 맞춰준다.
+        */
+        let from_slave_url: Vec<String> = get_target_slave_url_list(&from_short_file_path)?;
+        let to_slave_url: Vec<String> = get_target_slave_url_list(&to_short_file_path)?;
+
+        let mut rename_slave_url: Vec<String> = Vec::new();
+
+        for url in to_slave_url.iter().filter(|url| from_slave_url.contains(url)) {
+            let mapped_yn: bool = get_slave_dest_file_name(&from_short_file_path, url)? != from_short_file_path
+                || get_slave_dest_file_name(&to_short_file_path, url)? != to_short_file_path;
+
+            if mapped_yn {
+                /* 이름변경으로 옮겨진 수신 확인 정보를 제거하여 새 경로로 파일 전체를 다시 보내도록 한다. */
+                self.file_service.remove_acked_hash(to_file_path, url)?;
+            } else {
+                rename_slave_url.push(url.clone());
+            }
+        }
+
+        let rename_only_yn: bool =
+            from_slave_url == to_slave_url && rename_slave_url.len() == to_slave_url.len();
+
+        match self
+            .req_service
+            .send_rename_to_slave(
+                to_file_path,
+                &from_short_file_path,
+                &to_short_file_path,
+                rename_slave_url,
+            )
+            .await
+        {
+            Ok(_) if rename_only_yn => {
+                info!(
+                    "Successfully sent file rename to slave servers. : {} -> {}",
+                    &from_short_file_path, &to_short_file_path
+                );
+            }
+            Ok(_) => {
+                info!(
+                    "The file was renamed across slave groups or mapped paths. : {} -> {}",
+                    &from_short_file_path, &to_short_file_path
+                );
+
+                self.outbox_handler
+                    .deliver_file(to_file_path, &to_short_file_path)
+                    .await?;
+                self.outbox_handler
+                    .deliver_delete(from_file_path, &from_short_file_path)
+                    .await?;
+            }
+            Err(e) => {
+                /*
+                    이름변경이 실패한 slave 가 존재하는 경우 파일 전체를 다시 보내준다.
+                    - 해시 저장소에는 이미 이름변경이 반영되어 있으므로 수신 확인 정보를 초기화한 뒤 모든 slave 에 전송한다.
+                */
+                error!("[Error][rename_event_process()] {:?}", e);
+
+                self.file_service.clear_acked_hashes(to_file_path)?;
+
+                self.outbox_handler
+                    .deliver_file(to_file_path, &to_short_file_path)
+                    .await?;
+                self.outbox_handler
+                    .deliver_delete(from_file_path, &from_short_file_path)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -1,2 +1,11 @@
+pub mod analyzer_reload_handler;
+pub mod anti_entropy_handler;
+pub mod backup_cleanup_handler;
+pub mod local_change_handler;
 pub mod master_handler;
+pub mod outbox_handler;
+pub mod pull_handler;
+pub mod relay_handler;
+pub mod rollback_handler;
 pub mod slave_handler;
+pub mod sync_hook_handler;
@@ -0,0 +1,458 @@
+use crate::common::*;
+
+use crate::traits::service::{hash_storage_service::*, outbox_service::*, request_service::*};
+
+use crate::configs::configs::*;
+
+use crate::model::elastic_msg::*;
+
+use crate::repository::outbox_repository::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::logger_utils::*;
+use crate::utils_modules::time_utils::*;
+
+/* outbox 를 점검하는 간격(초) */
+const OUTBOX_POLL_INTERVAL_SECS: u64 = 1;
+/* 첫 재시도 간격(초) - 실패할 때마다 두 배씩 늘어난다. */
+const OUTBOX_RETRY_BASE_SECS: u64 = 2;
+/* 재시도 간격의 최대값(초) */
+const OUTBOX_RETRY_MAX_SECS: u64 = 300;
+
+#[derive(Debug)]
+pub struct OutboxHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: HashStorageService + OutboxService + Sync + Send + 'static,
+{
+    req_service: Arc<R>,
+    file_service: Arc<F>,
+}
+
+impl<R, F> OutboxHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: HashStorageService + OutboxService + Sync + Send + 'static,
+{
+    pub fn new(req_service: Arc<R>, file_service: Arc<F>) -> Self {
+        Self {
+            req_service,
+            file_service,
+        }
+    }
+
+    #[doc = "outbox 에 쌓인 작업을 재시도 시각마다 다시 전달해주는 함수 - 백그라운드 태스크로 실행된다."]
+    /// outbox 는 파일로 저장되므로 프로그램이 재시작되어도 이어서 재시도된다.
+    pub async fn run(&self) {
+        info!("Start outbox retry loop.");
+
+        loop {
+            if let Err(e) = self.retry_due_deliveries().await {
+                error!("[Error][OutboxHandler->run()] {:?}", e);
+            }
+
+            tokio::time::sleep(Duration::from_secs(OUTBOX_POLL_INTERVAL_SECS)).await;
+        }
+    }
+
+    #[doc = "수정된 파일을 아직 수신하지 못한 slave 에 전달하고, 실패한 slave 는 outbox 에 기록해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 수정된 파일경로
+    /// * `file_name` - 수정된 파일이름
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 하나라도 실패한 경우 에러 (실패한 작업은 재시도된다.)
+    pub async fn deliver_file(&self, file_path: &str, file_name: &str) -> Result<(), anyhow::Error> {
+        let slave_url: Vec<String> = self
+            .file_service
+            .get_unacked_slaves(file_path, &get_target_slave_url_list(file_name)?)?;
+
+        self.deliver_file_to_slaves(file_path, file_name, slave_url).await
+    }
+
+    #[doc = "파일을 지정한 slave 들에게 전달하고, 실패한 slave 는 outbox 에 기록해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 전달할 파일경로
+    /// * `file_name` - 전달할 파일이름
+    /// * `slave_url` - 전달 대상 slave 목록
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 하나라도 실패한 경우 에러 (실패한 작업은 재시도된다.)
+    pub async fn deliver_file_to_slaves(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        slave_url: Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        /* 변경 감지 시 기록된 해시값을 보내고, slave 가 같은 해시값을 확인해주면 그 값을 수신 확인 정보로 남긴다. */
+        let storage_hash: Vec<u8> = self.file_service.get_storage_hash(file_path)?;
+        let content_hash: String = hash_to_hex(&storage_hash);
+        let file_version: u64 = self.file_service.get_storage_version(file_path)?;
+        let dest_file_names: Vec<String> = get_dest_file_names(file_name, &slave_url)?;
+
+        let results: Vec<Result<(), anyhow::Error>> =
+            join_all(slave_url.iter().zip(&dest_file_names).map(|(url, dest_file_name)| {
+                self.req_service.send_info_to_target_slave(
+                    file_path,
+                    dest_file_name,
+                    vec![url.clone()],
+                    &content_hash,
+                    file_version,
+                )
+            }))
+            .await;
+
+        self.handle_delivery_results("upload", file_path, storage_hash, slave_url, dest_file_names, results)
+    }
+
+    #[doc = "파일 삭제를 해당 파일을 전달받는 slave 에 전달하고, 실패한 slave 는 outbox 에 기록해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 삭제된 파일경로
+    /// * `file_name` - 삭제된 파일이름
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 하나라도 실패한 경우 에러 (실패한 작업은 재시도된다.)
+    pub async fn deliver_delete(&self, file_path: &str, file_name: &str) -> Result<(), anyhow::Error> {
+        let slave_url: Vec<String> = get_target_slave_url_list(file_name)?;
+        let dest_file_names: Vec<String> = get_dest_file_names(file_name, &slave_url)?;
+
+        let results: Vec<Result<(), anyhow::Error>> =
+            join_all(slave_url.iter().zip(&dest_file_names).map(|(url, dest_file_name)| {
+                self.req_service
+                    .send_delete_to_target_slave(file_path, dest_file_name, vec![url.clone()])
+            }))
+            .await;
+
+        self.handle_delivery_results("delete", file_path, Vec::new(), slave_url, dest_file_names, results)
+    }
+
+    #[doc = "slave 별 전달 결과를 outbox 에 반영해주는 함수"]
+    /// # Arguments
+    /// * `operation`       - 작업 이름 (upload, delete)
+    /// * `file_path`       - 대상 파일경로
+    /// * `storage_hash`    - 전달한 파일의 해시값 (upload 인 경우)
+    /// * `slave_url`       - 전달 대상 slave 목록
+    /// * `dest_file_names` - slave 별 저장 경로 (slave_url 과 같은 순서)
+    /// * `results`         - slave 별 전달 결과 (slave_url 과 같은 순서)
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn handle_delivery_results(
+        &self,
+        operation: &str,
+        file_path: &str,
+        storage_hash: Vec<u8>,
+        slave_url: Vec<String>,
+        dest_file_names: Vec<String>,
+        results: Vec<Result<(), anyhow::Error>>,
+    ) -> Result<(), anyhow::Error> {
+        let content_hash: String = hash_to_hex(&storage_hash);
+
+        let now_secs: u64 = get_current_unix_secs();
+        let mut failed_slaves: Vec<String> = Vec::new();
+
+        for ((url, file_name), result) in slave_url.into_iter().zip(dest_file_names).zip(results) {
+            match result {
+                /* 성공한 경우 이전에 실패하여 대기중인 작업은 더 이상 필요없다. */
+                Ok(_) => {
+                    self.record_delivery_ack(operation, file_path, &url, storage_hash.clone())?;
+                    self.file_service.remove_outbox_delivery(&url, &file_name)?;
+                }
+                Err(e) => {
+                    error!(
+                        "[Error][handle_delivery_results()] Failed to {} '{}' to '{}': {:?}",
+                        operation, file_name, url, e
+                    );
+
+                    self.file_service.enqueue_outbox_delivery(OutboxDelivery {
+                        operation: operation.to_string(),
+                        file_path: file_path.to_string(),
+                        file_name: file_name.clone(),
+                        slave_url: url.clone(),
+                        content_hash: content_hash.clone(),
+                        attempts: 1,
+                        first_failed_at: now_secs,
+                        next_retry_at: now_secs + get_retry_backoff_secs(1),
+                        last_error: format!("{:?}", e),
+                    })?;
+
+                    failed_slaves.push(url);
+                }
+            }
+        }
+
+        if failed_slaves.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "[Error][handle_delivery_results()] Failed to {} '{}' to {:?}. It will be retried.",
+                operation,
+                file_path,
+                failed_slaves
+            ))
+        }
+    }
+
+    #[doc = "전달에 성공한 작업을 해시 저장소의 slave 별 수신 확인 정보에 반영해주는 함수"]
+    /// # Arguments
+    /// * `operation`    - 작업 이름 (upload, delete)
+    /// * `file_path`    - 대상 파일경로
+    /// * `slave_url`    - 전달에 성공한 slave
+    /// * `storage_hash` - 전달한 파일의 해시값 (upload 인 경우)
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn record_delivery_ack(
+        &self,
+        operation: &str,
+        file_path: &str,
+        slave_url: &str,
+        storage_hash: Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        if operation == "delete" {
+            self.file_service.remove_acked_hash(file_path, slave_url)
+        } else {
+            self.file_service.update_acked_hash(file_path, slave_url, storage_hash)
+        }
+    }
+
+    #[doc = "재시도 시각이 된 작업들을 다시 전달해주는 함수"]
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn retry_due_deliveries(&self) -> Result<(), anyhow::Error> {
+        let retry_deadline: Duration;
+        let from_host: String;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            retry_deadline = server_config.server.get_outbox_retry_deadline();
+            from_host = server_config.server.host().to_string();
+        }
+
+        let due_deliveries: Vec<OutboxDelivery> = self
+            .file_service
+            .get_due_outbox_deliveries(get_current_unix_secs())?;
+
+        for delivery in due_deliveries {
+            if let Err(e) = self
+                .retry_delivery(&delivery, retry_deadline, &from_host)
+                .await
+            {
+                error!("[Error][retry_due_deliveries()] {:?}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc = "작업 하나를 다시 전달해주는 함수 - 기한이 지나도록 실패하면 포기하고 작업 이벤트를 남긴다."]
+    /// # Arguments
+    /// * `delivery`        - 재시도할 작업
+    /// * `retry_deadline`  - 최초 실패 시각으로부터 재시도하는 최대 기간
+    /// * `from_host`       - master 호스트 주소
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn retry_delivery(
+        &self,
+        delivery: &OutboxDelivery,
+        retry_deadline: Duration,
+        from_host: &str,
+    ) -> Result<(), anyhow::Error> {
+        let (retry_res, storage_hash): (Result<(), anyhow::Error>, Vec<u8>) =
+            if delivery.operation() == "delete" {
+                let retry_res: Result<(), anyhow::Error> = self
+                    .req_service
+                    .send_delete_to_target_slave(
+                        delivery.file_path(),
+                        delivery.file_name(),
+                        vec![delivery.slave_url().clone()],
+                    )
+                    .await;
+
+                (retry_res, Vec::new())
+            } else {
+                /* 파일이 이미 삭제되었다면 삭제 작업이 따로 전달되므로 더 이상 보낼 필요가 없다. */
+                if !Path::new(delivery.file_path()).exists() {
+                    self.file_service.replace_outbox_delivery(delivery, None)?;
+                    return Ok(());
+                }
+
+                /* 재시도 시점의 최신 내용을 보내준다. */
+                let storage_hash: Vec<u8> = self.file_service.get_storage_hash(delivery.file_path())?;
+                let file_version: u64 = self.file_service.get_storage_version(delivery.file_path())?;
+
+                let retry_res: Result<(), anyhow::Error> = self
+                    .req_service
+                    .send_info_to_target_slave(
+                        delivery.file_path(),
+                        delivery.file_name(),
+                        vec![delivery.slave_url().clone()],
+                        &hash_to_hex(&storage_hash),
+                        file_version,
+                    )
+                    .await;
+
+                (retry_res, storage_hash)
+            };
+
+        let attempts: u32 = delivery.attempts() + 1;
+
+        let (next_delivery, task_status, task_detail): (Option<OutboxDelivery>, &str, String) =
+            match retry_res {
+                Ok(_) => {
+                    self.record_delivery_ack(
+                        delivery.operation(),
+                        delivery.file_path(),
+                        delivery.slave_url(),
+                        storage_hash,
+                    )?;
+
+                    info!(
+                        "The pending {} of '{}' was delivered to '{}' after {} attempts.",
+                        delivery.operation(),
+                        delivery.file_name(),
+                        delivery.slave_url(),
+                        attempts
+                    );
+
+                    (None, "success", format!("outbox retry {} (attempts: {})", delivery.operation(), attempts))
+                }
+                Err(e) => {
+                    let now_secs: u64 = get_current_unix_secs();
+
+                    if is_retry_deadline_exceeded(*delivery.first_failed_at(), now_secs, retry_deadline) {
+                        error!(
+                            "[Error][retry_delivery()] Gave up delivering {} of '{}' to '{}' after {} attempts: {:?}",
+                            delivery.operation(),
+                            delivery.file_name(),
+                            delivery.slave_url(),
+                            attempts,
+                            e
+                        );
+
+                        (
+                            None,
+                            "give-up",
+                            format!(
+                                "outbox retry deadline exceeded {} (attempts: {}): {:?}",
+                                delivery.operation(),
+                                attempts,
+                                e
+                            ),
+                        )
+                    } else {
+                        let next_delivery: OutboxDelivery = OutboxDelivery {
+                            content_hash: hash_to_hex(&storage_hash),
+                            attempts,
+                            next_retry_at: now_secs + get_retry_backoff_secs(attempts),
+                            last_error: format!("{:?}", e),
+                            ..delivery.clone()
+                        };
+
+                        /* 재시도 대기중인 작업은 작업 이벤트로 남기지 않는다. */
+                        self.file_service
+                            .replace_outbox_delivery(delivery, Some(next_delivery))?;
+                        return Ok(());
+                    }
+                }
+            };
+
+        /* 재시도하는 동안 새로운 작업으로 교체되었다면 결과를 남기지 않는다. */
+        if self
+            .file_service
+            .replace_outbox_delivery(delivery, next_delivery)?
+        {
+            let task_event: ElasticMsg = ElasticMsg::new(
+                from_host,
+                delivery.slave_url(),
+                delivery.file_name(),
+                task_status,
+                &task_detail,
+            )?;
+
+            write_task_event_log(&task_event);
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "재시도 간격을 계산해주는 함수 - 지수 백오프에 jitter 를 적용한다."]
+/// # Arguments
+/// * `attempts` - 지금까지 시도한 횟수
+///
+/// # Returns
+/// * u64 - 다음 재시도까지의 간격(초)
+fn get_retry_backoff_secs(attempts: u32) -> u64 {
+    let exponent: u32 = attempts.saturating_sub(1).min(16);
+    let backoff_secs: u64 = OUTBOX_RETRY_BASE_SECS
+        .saturating_mul(1 << exponent)
+        .min(OUTBOX_RETRY_MAX_SECS);
+
+    /* 여러 작업이 한꺼번에 재시도되지 않도록 간격의 절반 ~ 전체 사이에서 무작위로 정한다. */
+    rand::thread_rng().gen_range(backoff_secs / 2..=backoff_secs)
+}
+
+#[doc = "재시도 기한이 지났는지 확인해주는 함수"]
+/// # Arguments
+/// * `first_failed_at` - 처음 전달에 실패한 시각 (unix 초)
+/// * `now_secs`        - 현재 시각 (unix 초)
+/// * `retry_deadline`  - 최초 실패 시각으로부터 재시도하는 최대 기간
+///
+/// # Returns
+/// * bool - 기한이 지났다면 true
+fn is_retry_deadline_exceeded(first_failed_at: u64, now_secs: u64, retry_deadline: Duration) -> bool {
+    now_secs.saturating_sub(first_failed_at) >= retry_deadline.as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_within_jitter() {
+        for attempts in 1..=5 {
+            let max_secs: u64 = OUTBOX_RETRY_BASE_SECS << (attempts - 1);
+            let backoff_secs: u64 = get_retry_backoff_secs(attempts);
+
+            assert!(
+                (max_secs / 2..=max_secs).contains(&backoff_secs),
+                "attempts {}: {} not in {}..={}",
+                attempts,
+                backoff_secs,
+                max_secs / 2,
+                max_secs
+            );
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        for attempts in [9, 17, 100, u32::MAX] {
+            let backoff_secs: u64 = get_retry_backoff_secs(attempts);
+
+            assert!((OUTBOX_RETRY_MAX_SECS / 2..=OUTBOX_RETRY_MAX_SECS).contains(&backoff_secs));
+        }
+    }
+
+    #[test]
+    fn backoff_first_attempt_is_base() {
+        /* 0 번째 시도도 첫 시도와 같은 간격을 사용한다. */
+        assert!((1..=OUTBOX_RETRY_BASE_SECS).contains(&get_retry_backoff_secs(0)));
+    }
+
+    #[test]
+    fn deadline_is_measured_from_first_failure() {
+        let retry_deadline: Duration = Duration::from_secs(600);
+
+        assert!(!is_retry_deadline_exceeded(1_000, 1_599, retry_deadline));
+        assert!(is_retry_deadline_exceeded(1_000, 1_600, retry_deadline));
+        assert!(is_retry_deadline_exceeded(1_000, 5_000, retry_deadline));
+    }
+
+    #[test]
+    fn deadline_tolerates_clock_going_backwards() {
+        assert!(!is_retry_deadline_exceeded(1_000, 900, Duration::from_secs(600)));
+        assert!(is_retry_deadline_exceeded(1_000, 900, Duration::ZERO));
+    }
+}
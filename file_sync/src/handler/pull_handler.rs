@@ -0,0 +1,251 @@
+use crate::common::*;
+
+use crate::traits::service::{backup_service::*, receive_service::*, request_service::*};
+
+use crate::handler::analyzer_reload_handler::*;
+use crate::handler::sync_hook_handler::*;
+
+use crate::configs::configs::*;
+
+use crate::model::hook_result::*;
+use crate::model::manifest_info::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::io_utils::*;
+
+#[derive(Debug)]
+pub struct PullHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: ReceiveService + BackupService + Sync + Send + 'static,
+{
+    req_service: Arc<R>,
+    file_service: Arc<F>,
+    analyzer_reload_handler: Option<Arc<AnalyzerReloadHandler<R>>>, /* slave 로컬 Elasticsearch 가 설정된 경우 */
+    sync_hook_handler: SyncHookHandler<F>,
+}
+
+impl<R, F> PullHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: ReceiveService + BackupService + Sync + Send + 'static,
+{
+    pub fn new(
+        req_service: Arc<R>,
+        file_service: Arc<F>,
+        analyzer_reload_handler: Option<Arc<AnalyzerReloadHandler<R>>>,
+    ) -> Self {
+        let sync_hook_handler: SyncHookHandler<F> = SyncHookHandler::new(Arc::clone(&file_service));
+
+        Self {
+            req_service,
+            file_service,
+            analyzer_reload_handler,
+            sync_hook_handler,
+        }
+    }
+
+    #[doc = "pull 모드 slave 의 작업: 설정된 간격마다 master 의 manifest 를 조회하여 변경된 파일을 내려받는다."]
+    /// 첫 조회는 시작과 동시에 수행되므로 처음 기동된 slave 는 모든 파일을 내려받게 된다.
+    pub async fn run(&self) -> Result<(), anyhow::Error> {
+        let master_url: String;
+        let pull_interval: Duration;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            master_url = server_config
+                .server
+                .pull_master_url()
+                .clone()
+                .ok_or_else(|| {
+                    anyhow!("[Error][PullHandler->run()] The information 'pull_master_url' does not exist.")
+                })?;
+            pull_interval = server_config.server.get_pull_interval();
+        }
+
+        info!(
+            "Start pulling files from the master server: {} // interval: {:?}",
+            master_url, pull_interval
+        );
+
+        loop {
+            if let Err(e) = self.pull_from_master(&master_url).await {
+                error!("[Error][PullHandler->run()] {:?}", e);
+            }
+
+            tokio::time::sleep(pull_interval).await;
+        }
+    }
+
+    #[doc = "master 의 manifest 와 slave 의 파일을 비교하여 다른 파일은 내려받고, master 에서 사라진 파일은 삭제해주는 함수"]
+    /// # Arguments
+    /// * `master_url` - master 서버 주소
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn pull_from_master(&self, master_url: &str) -> Result<(), anyhow::Error> {
+        let watch_path_string: String;
+        let slave_backup_path: String;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            watch_path_string = server_config.server.watch_path().clone();
+            slave_backup_path = server_config
+                .server
+                .slave_backup_path()
+                .clone()
+                .unwrap_or_default();
+        }
+
+        let watch_path: &Path = Path::new(watch_path_string.as_str());
+        let manifest: ManifestInfo = self.req_service.get_master_manifest(master_url).await?;
+
+        /* 1. master 와 내용이 다르거나 slave 에 존재하지 않는 파일 */
+        for manifest_file in manifest.files() {
+            let target_file_path: PathBuf = match get_sync_target_path(watch_path, manifest_file.file_path()) {
+                Ok(target_file_path) => target_file_path,
+                Err(e) => {
+                    error!("[Error][pull_from_master()] {:?}", e);
+                    continue;
+                }
+            };
+
+            let local_hash: String = conpute_hash(&target_file_path)
+                .map(|hash| hash_to_hex(&hash))
+                .unwrap_or_default();
+
+            if &local_hash == manifest_file.hash() {
+                continue;
+            }
+
+            if let Err(e) = self
+                .pull_file(master_url, manifest_file, &target_file_path)
+                .await
+            {
+                error!(
+                    "[Error][pull_from_master()] Failed to pull '{}' : {:?}",
+                    manifest_file.file_path(),
+                    e
+                );
+            }
+        }
+
+        /* 2. master 로부터 받았지만 더 이상 master 에 존재하지 않는 파일 */
+        for received_file_name in self.file_service.get_receive_file_list()? {
+            if manifest.get_file_hash(&received_file_name).is_some() {
+                continue;
+            }
+
+            let deleted_file_path: PathBuf = match get_sync_target_path(watch_path, &received_file_name) {
+                Ok(deleted_file_path) => deleted_file_path,
+                Err(e) => {
+                    error!("[Error][pull_from_master()] {:?}", e);
+                    continue;
+                }
+            };
+
+            /* 하나의 파일을 삭제하지 못하더라도 나머지 파일들은 계속 처리한다. */
+            if let Err(e) = self.file_service.delete_file_with_backup(
+                deleted_file_path,
+                &slave_backup_path,
+                &received_file_name,
+            ) {
+                error!(
+                    "[Error][pull_from_master()] Failed to delete '{}' : {:?}",
+                    received_file_name,
+                    e
+                );
+                continue;
+            }
+
+            info!("The file '{}' was deleted on the master server.", received_file_name);
+        }
+
+        Ok(())
+    }
+
+    #[doc = "master 로부터 파일 하나를 내려받아 기존 파일을 백업 후 덮어써주는 함수"]
+    /// # Arguments
+    /// * `master_url`          - master 서버 주소
+    /// * `manifest_file`       - 내려받을 파일의 manifest 정보 (파일 이름, 해시값, 버전, 메타정보)
+    /// * `target_file_path`    - 덮어쓰기 대상이 되는 파일 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn pull_file(
+        &self,
+        master_url: &str,
+        manifest_file: &ManifestFileInfo,
+        target_file_path: &Path,
+    ) -> Result<(), anyhow::Error> {
+        let file_name: &str = manifest_file.file_path();
+        let expected_hash: &str = manifest_file.hash();
+        let file_version: Option<u64> = *manifest_file.version();
+
+        let file_data: Vec<u8> = self.req_service.get_master_file(master_url, file_name).await?;
+
+        /* 내려받은 내용이 manifest 의 해시값과 다르면 반영하지 않는다. */
+        let received_hash: String = hash_to_hex(&compute_hash_from_bytes(&file_data));
+
+        if received_hash != expected_hash {
+            return Err(anyhow!(
+                "[Error][pull_file()] Content digest mismatch for '{}'. expected: {} // received: {}",
+                file_name,
+                expected_hash,
+                received_hash
+            ));
+        }
+
+        /* 임시 파일에 먼저 기록한 뒤 원자적으로 교체한다. */
+        let staged_file_path: PathBuf = self
+            .file_service
+            .write_staged_file(target_file_path, &file_data)?;
+
+        /* upload 와 마찬가지로 파일에 설정된 동기화 훅을 거쳐 반영한다. */
+        match self
+            .sync_hook_handler
+            .commit_with_sync_hooks(
+                &staged_file_path,
+                target_file_path,
+                file_name,
+                master_url,
+                file_version,
+                manifest_file.file_meta().as_ref(),
+            )
+            .await?
+        {
+            SyncWriteResult::Committed(_) => (),
+            SyncWriteResult::Stale => {
+                warn!(
+                    "[Warn][pull_file()] Skipped stale version {:?} of '{}'.",
+                    file_version, file_name
+                );
+                return Ok(());
+            }
+            SyncWriteResult::Rejected(hook_result) => {
+                return Err(anyhow!(
+                    "[Error][pull_file()] The pre-write hook rejected '{}' : {}",
+                    file_name,
+                    hook_result.get_summary()
+                ));
+            }
+            SyncWriteResult::Reverted(hook_result, restore_detail) => {
+                return Err(anyhow!(
+                    "[Error][pull_file()] The post-write hook failed for '{}' : {} -> {}",
+                    file_name,
+                    hook_result.get_summary(),
+                    restore_detail
+                ));
+            }
+        }
+
+        info!("The file '{:?}' has been pulled from the master server.", target_file_path);
+
+        /* pull 모드에서는 보고할 master 요청이 없으므로 리로드 결과는 작업 이벤트로만 남는다. */
+        if let Some(analyzer_reload_handler) = &self.analyzer_reload_handler {
+            if let Err(e) = analyzer_reload_handler.reload_file_analyzers(file_name).await {
+                error!("[Error][pull_file()] {:?}", e);
+            }
+        }
+
+        Ok(())
+    }
+}
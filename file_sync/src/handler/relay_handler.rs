@@ -0,0 +1,213 @@
+use crate::common::*;
+
+use crate::traits::service::{receive_service::*, request_service::*};
+
+use crate::configs::configs::*;
+
+use crate::model::elastic_msg::*;
+use crate::model::relay_result::*;
+
+use crate::repository::receive_repository::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::logger_utils::*;
+
+#[derive(Debug)]
+pub struct RelayHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: ReceiveService + Sync + Send + 'static,
+{
+    req_service: Arc<R>,
+    file_service: Arc<F>,
+}
+
+impl<R, F> RelayHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: ReceiveService + Sync + Send + 'static,
+{
+    pub fn new(req_service: Arc<R>, file_service: Arc<F>) -> Self {
+        Self {
+            req_service,
+            file_service,
+        }
+    }
+
+    #[doc = "relay 에 반영된 파일을 하위 slave 들에게 다시 전달해주는 함수"]
+    /// # Arguments
+    /// * `file_path`    - relay 에 반영된 파일경로
+    /// * `file_name`    - 파일이름 (감시 디렉토리 기준 상대경로)
+    /// * `content_hash` - master 가 보내고 relay 가 수신하면서 확인한 해시값
+    /// * `file_version` - master 가 보낸 파일의 버전 -> 그대로 하위 slave 에 전달한다.
+    ///
+    /// # Returns
+    /// * Result<RelayResult, anyhow::Error>
+    pub async fn relay_file(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        content_hash: &str,
+        file_version: u64,
+    ) -> Result<RelayResult, anyhow::Error> {
+        let slave_url: Vec<String> = get_target_slave_url_list(file_name)?;
+        let dest_file_names: Vec<String> = get_dest_file_names(file_name, &slave_url)?;
+
+        let results: Vec<Result<(), anyhow::Error>> =
+            join_all(slave_url.iter().zip(&dest_file_names).map(|(url, dest_file_name)| {
+                self.req_service.send_info_to_target_slave(
+                    file_path,
+                    dest_file_name,
+                    vec![url.clone()],
+                    content_hash,
+                    file_version,
+                )
+            }))
+            .await;
+
+        self.handle_relay_results("upload", file_name, slave_url, results)
+    }
+
+    #[doc = "relay 에서 삭제된 파일을 하위 slave 들에게 다시 전달해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - relay 에서 삭제된 파일경로
+    /// * `file_name` - 파일이름 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<RelayResult, anyhow::Error>
+    pub async fn relay_delete(&self, file_path: &str, file_name: &str) -> Result<RelayResult, anyhow::Error> {
+        let slave_url: Vec<String> = get_target_slave_url_list(file_name)?;
+        let dest_file_names: Vec<String> = get_dest_file_names(file_name, &slave_url)?;
+
+        let results: Vec<Result<(), anyhow::Error>> =
+            join_all(slave_url.iter().zip(&dest_file_names).map(|(url, dest_file_name)| {
+                self.req_service
+                    .send_delete_to_target_slave(file_path, dest_file_name, vec![url.clone()])
+            }))
+            .await;
+
+        self.handle_relay_results("delete", file_name, slave_url, results)
+    }
+
+    #[doc = "relay 에서 이름이 변경된 파일을 하위 slave 들에게 다시 전달해주는 함수"]
+    /// 이름변경에 실패한 slave 에는 변경된 파일 전체를 보내고 이전 파일은 삭제해준다.
+    ///
+    /// # Arguments
+    /// * `file_path`       - relay 에서 이름이 변경된 파일경로
+    /// * `from_file_name`  - 이전 파일이름
+    /// * `to_file_name`    - 새로운 파일이름
+    ///
+    /// # Returns
+    /// * Result<RelayResult, anyhow::Error>
+    pub async fn relay_rename(
+        &self,
+        file_path: &str,
+        from_file_name: &str,
+        to_file_name: &str,
+    ) -> Result<RelayResult, anyhow::Error> {
+        let slave_url: Vec<String> = get_target_slave_url_list(to_file_name)?;
+
+        /* 파일 전체를 다시 보내는 경우에는 relay 가 마지막으로 수신한 버전과 해시값을 그대로 사용한다. */
+        let receive_info: Option<ReceiveFileInfo> = self.file_service.get_receive_file_info(to_file_name)?;
+        let file_version: u64 = receive_info
+            .as_ref()
+            .and_then(|receive_info| *receive_info.version())
+            .unwrap_or_default();
+        let content_hash: String = match receive_info {
+            Some(receive_info) => receive_info.hash().to_string(),
+            None => hash_to_hex(&conpute_hash(Path::new(file_path))?),
+        };
+        let content_hash: &str = content_hash.as_str();
+
+        let from_file_names: Vec<String> = get_dest_file_names(from_file_name, &slave_url)?;
+        let to_file_names: Vec<String> = get_dest_file_names(to_file_name, &slave_url)?;
+
+        let results: Vec<Result<(), anyhow::Error>> = join_all(
+            slave_url
+                .iter()
+                .zip(from_file_names.iter().zip(&to_file_names))
+                .map(|(url, (from_file_name, to_file_name))| async move {
+                    let query: String = format!(
+                        "rename?from_filename={}&to_filename={}",
+                        from_file_name, to_file_name
+                    );
+
+                    let rename_res: Result<(), anyhow::Error> = self
+                        .req_service
+                        .send_operation_to_slave(file_path, "rename", &query, vec![url.clone()])
+                        .await;
+
+                    if rename_res.is_ok() {
+                        return rename_res;
+                    }
+
+                    self.req_service
+                        .send_info_to_target_slave(file_path, to_file_name, vec![url.clone()], content_hash, file_version)
+                        .await?;
+                    self.req_service
+                        .send_delete_to_target_slave(file_path, from_file_name, vec![url.clone()])
+                        .await
+                }),
+        )
+        .await;
+
+        self.handle_relay_results("rename", to_file_name, slave_url, results)
+    }
+
+    #[doc = "하위 slave 별 전달 결과를 모아서 상위 서버에 보고할 결과로 만들어주는 함수"]
+    /// # Arguments
+    /// * `operation` - 작업 이름 (upload, delete, rename)
+    /// * `file_name` - 대상 파일이름
+    /// * `slave_url` - 전달 대상 slave 목록
+    /// * `results`   - slave 별 전달 결과 (slave_url 과 같은 순서)
+    ///
+    /// # Returns
+    /// * Result<RelayResult, anyhow::Error>
+    fn handle_relay_results(
+        &self,
+        operation: &str,
+        file_name: &str,
+        slave_url: Vec<String>,
+        results: Vec<Result<(), anyhow::Error>>,
+    ) -> Result<RelayResult, anyhow::Error> {
+        let relay_host: String = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            server_config.server.host().to_string()
+        };
+
+        let total: usize = slave_url.len();
+        let mut failed_slaves: Vec<String> = Vec::new();
+
+        for (url, result) in slave_url.into_iter().zip(results) {
+            let (task_status, task_detail): (&str, String) = match result {
+                Ok(_) => ("success", format!("relay {}", operation)),
+                Err(e) => {
+                    error!(
+                        "[Error][handle_relay_results()] Failed to relay {} '{}' to '{}': {:?}",
+                        operation, file_name, url, e
+                    );
+                    ("failed", format!("relay {}: {:?}", operation, e))
+                }
+            };
+
+            if task_status == "failed" {
+                failed_slaves.push(url.clone());
+            }
+
+            let task_event: ElasticMsg =
+                ElasticMsg::new(&relay_host, &url, file_name, task_status, &task_detail)?;
+            write_task_event_log(&task_event);
+        }
+
+        let relay_result: RelayResult = RelayResult::new(relay_host, total, failed_slaves);
+
+        info!(
+            "Relayed {} '{}' to downstream slaves: {}",
+            operation,
+            file_name,
+            relay_result.get_summary()
+        );
+
+        Ok(relay_result)
+    }
+}
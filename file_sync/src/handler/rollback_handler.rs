@@ -0,0 +1,260 @@
+use crate::common::*;
+
+use crate::traits::service::{
+    file_service::*, hash_storage_service::*, history_service::*, outbox_service::*, request_service::*,
+};
+
+use crate::handler::outbox_handler::*;
+
+use crate::configs::configs::*;
+
+use crate::model::elastic_msg::*;
+use crate::model::monitoring_path_info::*;
+use crate::model::rollback_result::*;
+
+use crate::repository::history_repository::*;
+
+use crate::utils_modules::logger_utils::*;
+
+#[doc = "롤백으로 발생한 감시 이벤트를 건너뛰는 시간 - 이벤트 대기 시간에 더해진다."]
+const ROLLBACK_EVENT_GRACE_SECS: u64 = 10;
+
+#[derive(Debug)]
+pub struct RollbackHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: FileService + HashStorageService + HistoryService + OutboxService + Sync + Send + 'static,
+{
+    file_service: Arc<F>,
+    outbox_handler: OutboxHandler<R, F>,
+}
+
+impl<R, F> RollbackHandler<R, F>
+where
+    R: RequestService + Sync + Send + 'static,
+    F: FileService + HashStorageService + HistoryService + OutboxService + Sync + Send + 'static,
+{
+    pub fn new(req_service: Arc<R>, file_service: Arc<F>) -> Self {
+        let outbox_handler: OutboxHandler<R, F> = OutboxHandler::new(req_service, file_service.clone());
+
+        Self {
+            file_service,
+            outbox_handler,
+        }
+    }
+
+    #[doc = "모니터링 대상 파일의 배포 이력을 조회해주는 함수"]
+    /// # Arguments
+    /// * `file_name` - 감시 디렉토리 기준 상대경로
+    ///
+    /// # Returns
+    /// * Result<Vec<HistoryVersion>, anyhow::Error> - 오래된 버전부터
+    pub fn get_history(&self, file_name: &str) -> Result<Vec<HistoryVersion>, anyhow::Error> {
+        let monitoring_file: MonitoringPathInfo = find_monitoring_file(file_name)?;
+        self.file_service
+            .get_history_versions(monitoring_file.full_file_path())
+    }
+
+    #[doc = "배포된 파일을 이전 버전으로 되돌려 해당 파일을 수신했던 모든 slave 에 다시 배포해주는 함수"]
+    /// master 의 파일도 되돌린 버전으로 교체되며, 되돌린 내용은 새로운 버전으로 배포된다.
+    /// (slave 는 이미 가진 버전보다 낮은 버전은 거절하기 때문)
+    ///
+    /// # Arguments
+    /// * `file_name`       - 감시 디렉토리 기준 상대경로
+    /// * `target_version`  - 되돌릴 버전 (None 인 경우 마지막 배포 직전 버전)
+    ///
+    /// # Returns
+    /// * Result<RollbackResult, anyhow::Error>
+    pub async fn rollback_file(
+        &self,
+        file_name: &str,
+        target_version: Option<u64>,
+    ) -> Result<RollbackResult, anyhow::Error> {
+        let monitoring_file: MonitoringPathInfo = find_monitoring_file(file_name)?;
+        let file_path: &str = monitoring_file.full_file_path();
+
+        let history_versions: Vec<HistoryVersion> = self.file_service.get_history_versions(file_path)?;
+
+        let current_version: &HistoryVersion = history_versions.last().ok_or_else(|| {
+            anyhow!("[Error][rollback_file()] There is no deployment history of '{}'.", file_name)
+        })?;
+
+        let rollback_version: &HistoryVersion = match target_version {
+            Some(target_version) => history_versions
+                .iter()
+                .find(|history_version| *history_version.version() == target_version),
+            None => history_versions.iter().rev().nth(1),
+        }
+        .ok_or_else(|| {
+            anyhow!(
+                "[Error][rollback_file()] The version {:?} of '{}' is not in the deployment history.",
+                target_version,
+                file_name
+            )
+        })?;
+
+        if rollback_version.version() == current_version.version() {
+            return Err(anyhow!(
+                "[Error][rollback_file()] The version {} of '{}' is already deployed.",
+                rollback_version.version(),
+                file_name
+            ));
+        }
+
+        /* 문제가 된 버전을 수신했던 slave 들에게만 다시 배포한다. */
+        let target_slaves: Vec<String> = current_version.received_slaves().clone();
+
+        if target_slaves.is_empty() {
+            return Err(anyhow!(
+                "[Error][rollback_file()] No slave has received version {} of '{}'.",
+                current_version.version(),
+                file_name
+            ));
+        }
+
+        let (master_host, pull_mode, quiet_period): (String, bool, Duration) = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            (
+                server_config.server.host().to_string(),
+                server_config.server.is_pull_mode(),
+                server_config.server.get_debounce_quiet_period(),
+            )
+        };
+
+        /*
+            되돌린 파일에도 감시 이벤트가 발생한다.
+            - 이벤트로 처리되면 라우팅 대상 slave 전체에 배포되므로, 롤백에서 처리한 것으로 기록해둔다.
+        */
+        mark_rollback_write(
+            file_path,
+            rollback_version.hash(),
+            quiet_period + Duration::from_secs(ROLLBACK_EVENT_GRACE_SECS),
+        )?;
+
+        let restore_res: Result<bool, anyhow::Error> = self
+            .file_service
+            .restore_history_version(Path::new(file_path), *rollback_version.version());
+
+        if !matches!(restore_res, Ok(true)) {
+            unmark_rollback_write(file_path)?;
+        }
+
+        if !restore_res? {
+            return Err(anyhow!(
+                "[Error][rollback_file()] The content of version {} of '{}' does not exist.",
+                rollback_version.version(),
+                file_name
+            ));
+        }
+
+        /* 되돌린 내용을 해시 저장소에 반영하여 새 버전을 받는다. */
+        self.file_service
+            .comparison_file(Path::new(file_path), &target_slaves)?;
+        let pushed_version: u64 = self.file_service.get_storage_version(file_path)?;
+        self.file_service.record_history_version(
+            file_path,
+            file_name,
+            pushed_version,
+            Some(*rollback_version.version()),
+        )?;
+
+        /* pull 모드에서는 slave 들이 다음 조회 시 되돌린 파일을 가져간다. */
+        let failed_slaves: Vec<String> = if pull_mode {
+            Vec::new()
+        } else {
+            if let Err(e) = self
+                .outbox_handler
+                .deliver_file_to_slaves(file_path, file_name, target_slaves.clone())
+                .await
+            {
+                error!("[Error][rollback_file()] {:?}", e);
+            }
+
+            self.file_service
+                .get_unacked_slaves(file_path, &target_slaves)?
+        };
+
+        let rollback_result: RollbackResult = RollbackResult::new(
+            file_name.to_string(),
+            *current_version.version(),
+            *rollback_version.version(),
+            pushed_version,
+            target_slaves,
+            failed_slaves,
+        );
+
+        self.write_rollback_events(&master_host, &rollback_result)?;
+
+        info!(
+            "Rolled back '{}' from version {} to {} (pushed as version {}) : {}/{} slaves",
+            file_name,
+            rollback_result.from_version(),
+            rollback_result.to_version(),
+            rollback_result.pushed_version(),
+            rollback_result.target_slaves().len() - rollback_result.failed_slaves().len(),
+            rollback_result.target_slaves().len()
+        );
+
+        Ok(rollback_result)
+    }
+
+    #[doc = "롤백 결과를 slave 별 작업 이벤트로 남겨주는 함수"]
+    /// # Arguments
+    /// * `master_host`     - master 호스트 주소
+    /// * `rollback_result` - 롤백 결과
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn write_rollback_events(&self, master_host: &str, rollback_result: &RollbackResult) -> Result<(), anyhow::Error> {
+        let task_detail: String = format!(
+            "rollback version {} -> {} (pushed as version {})",
+            rollback_result.from_version(),
+            rollback_result.to_version(),
+            rollback_result.pushed_version()
+        );
+
+        for slave_url in rollback_result.target_slaves() {
+            let task_status: &str = if rollback_result.failed_slaves().contains(slave_url) {
+                "rollback-failed"
+            } else {
+                "rollback"
+            };
+
+            let task_event: ElasticMsg = ElasticMsg::new(
+                master_host,
+                slave_url,
+                rollback_result.file_name(),
+                task_status,
+                &task_detail,
+            )?;
+            write_task_event_log(&task_event);
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "감시 디렉토리 기준 상대경로로 모니터링 대상 파일을 찾아주는 함수"]
+/// # Arguments
+/// * `file_name` - 감시 디렉토리 기준 상대경로
+///
+/// # Returns
+/// * Result<MonitoringPathInfo, anyhow::Error> - 없거나 여러 감시 디렉토리에 같은 이름이 있는 경우 에러
+fn find_monitoring_file(file_name: &str) -> Result<MonitoringPathInfo, anyhow::Error> {
+    let mut monitoring_files: Vec<MonitoringPathInfo> = get_monitoring_file_list()?
+        .into_iter()
+        .filter(|monitoring_file| monitoring_file.file_path() == file_name)
+        .collect();
+
+    match monitoring_files.len() {
+        0 => Err(anyhow!(
+            "[Error][find_monitoring_file()] '{}' is not a monitoring target file.",
+            file_name
+        )),
+        1 => Ok(monitoring_files.remove(0)),
+        _ => Err(anyhow!(
+            "[Error][find_monitoring_file()] '{}' exists in more than one watch root.",
+            file_name
+        )),
+    }
+}
@@ -1,11 +1,19 @@
 use crate::common::*;
 
-use crate::traits::service::{file_service::*, request_service::*};
+use crate::traits::service::{
+    backup_service::*, file_service::*, receive_service::*, request_service::*,
+};
 
 use crate::middleware::middle_ware::*;
 
 use crate::router::app_router::*;
 
+use crate::handler::analyzer_reload_handler::*;
+use crate::handler::backup_cleanup_handler::*;
+use crate::handler::local_change_handler::*;
+use crate::handler::pull_handler::*;
+use crate::handler::relay_handler::*;
+
 use crate::configs::configs::*;
 
 use crate::utils_modules::tls_utils::*;
@@ -14,7 +22,7 @@ use crate::utils_modules::tls_utils::*;
 pub struct SlaveHandler<R, F>
 where
     R: RequestService + Sync + Send + 'static,
-    F: FileService + Sync + Send + 'static,
+    F: FileService + ReceiveService + BackupService + Sync + Send + 'static,
 {
     req_service: Arc<R>,
     file_service: Arc<F>,
@@ -23,7 +31,7 @@ where
 impl<R, F> SlaveHandler<R, F>
 where
     R: RequestService + Sync + Send + 'static,
-    F: FileService + Sync + Send + 'static,
+    F: FileService + ReceiveService + BackupService + Sync + Send + 'static,
 {
     pub fn new(req_service: Arc<R>, file_service: Arc<F>) -> Self {
         Self {
@@ -34,9 +42,64 @@ where
     
     #[doc = "프로그램 role 이 slave 인경우의 작업: 보안 모드에 따라 HTTP 또는 mTLS HTTPS 서버를 실행한다."]
     pub async fn run(&self) -> Result<(), anyhow::Error> {
+        let pull_mode: bool;
+        let slave_backup_path: Option<String>;
+        let backup_cleanup_interval: Duration;
+        let slave_elastic_url: Option<String>;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            pull_mode = server_config.server.is_pull_mode();
+            slave_backup_path = server_config.server.slave_backup_path().clone();
+            backup_cleanup_interval = server_config.server.get_backup_cleanup_interval();
+            slave_elastic_url = server_config.server.slave_elastic_url().clone();
+        }
+
+        /*
+            slave 로컬 Elasticsearch 가 설정된 경우 파일을 반영한 뒤 검색 분석기를 리로드한다.
+            - analyzer reload handler 가 등록되지 않은 slave 는 파일을 반영만 한다.
+        */
+        let analyzer_reload_handler: Option<Arc<AnalyzerReloadHandler<R>>> =
+            slave_elastic_url.map(|slave_elastic_url| {
+                info!("Search analyzers are reloaded on: {}", slave_elastic_url);
+                Arc::new(AnalyzerReloadHandler::new(self.req_service.clone(), slave_elastic_url))
+            });
+
+        /* 오래된 백업을 주기적으로 정리해주는 백그라운드 태스크 */
+        if let Some(slave_backup_path) = slave_backup_path {
+            let backup_cleanup_handler: BackupCleanupHandler<F> =
+                BackupCleanupHandler::new(self.file_service.clone());
+
+            task::spawn(async move {
+                backup_cleanup_handler
+                    .run(vec![PathBuf::from(slave_backup_path)], backup_cleanup_interval)
+                    .await;
+            });
+        }
+
+        /* master 를 거치지 않고 slave 에서 직접 수정된 파일을 감지해주는 백그라운드 태스크 */
+        let local_change_handler: LocalChangeHandler<R, F> =
+            LocalChangeHandler::new(self.req_service.clone(), self.file_service.clone());
+
+        task::spawn(async move {
+            if let Err(e) = local_change_handler.run().await {
+                error!("[Error][SlaveHandler->run()] {:?}", e);
+            }
+        });
+
+        /* pull 모드인 경우 서버를 띄우지 않고 master 로부터 직접 파일을 가져온다. */
+        if pull_mode {
+            let pull_handler: PullHandler<R, F> = PullHandler::new(
+                self.req_service.clone(),
+                self.file_service.clone(),
+                analyzer_reload_handler,
+            );
+            return pull_handler.run().await;
+        }
+
         let slave_host: String;
         let master_address: Vec<String>;
         let secure_mode: bool;
+        let relay_role: bool;
         {
             let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
             slave_host = server_config.server.host().to_string();
@@ -49,39 +112,53 @@ where
                 })?
                 .clone();
             secure_mode = server_config.server.is_secure_mode();
+            relay_role = server_config.server.is_relay_role();
         }
 
         let file_service: Arc<F> = self.file_service.clone();
 
+        /*
+            relay 인 경우 반영한 파일을 자신의 slave_address 로 다시 전달한다.
+            - relay handler 가 등록되지 않은 일반 slave 는 파일을 반영만 한다.
+        */
+        let relay_handler: Option<Arc<RelayHandler<R, F>>> = if relay_role {
+            info!("This server relays received files to its own slaves.");
+            Some(Arc::new(RelayHandler::new(
+                self.req_service.clone(),
+                self.file_service.clone(),
+            )))
+        } else {
+            None
+        };
+
+        let http_server = HttpServer::new(move || {
+            let app = App::new()
+                .wrap(CheckIp::new(master_address.clone()))
+                .configure(AppRouter::configure_routes)
+                .app_data(web::Data::new(file_service.clone()));
+
+            let app = match &relay_handler {
+                Some(relay_handler) => app.app_data(web::Data::new(relay_handler.clone())),
+                None => app,
+            };
+
+            match &analyzer_reload_handler {
+                Some(analyzer_reload_handler) => app.app_data(web::Data::new(analyzer_reload_handler.clone())),
+                None => app,
+            }
+        });
+
         /* TLS 를 적용한 경우 */
         if secure_mode {
             let tls_config: rustls::ServerConfig = create_server_tls_config()
                 .map_err(|e| anyhow!("[ERROR][SlaveHandler->run] Failed to create TLS config: {}", e))?;
 
             info!("Starting secure slave server with mTLS on: {}", slave_host);
-
-            HttpServer::new(move || {
-                App::new()
-                    .wrap(CheckIp::new(master_address.clone()))
-                    .configure(AppRouter::configure_routes)
-                    .app_data(web::Data::new(file_service.clone()))
-            })
-            .bind_rustls_0_23(&slave_host, tls_config)?
-            .run()
-            .await?;
+            http_server.bind_rustls_0_23(&slave_host, tls_config)?.run().await?;
         } else {
             /* TLS 를 적용하지 않은 경우 */
             info!("Starting regular HTTP slave server on: {}", slave_host);
-
-            HttpServer::new(move || {
-                App::new()
-                    .wrap(CheckIp::new(master_address.clone()))
-                    .configure(AppRouter::configure_routes)
-                    .app_data(web::Data::new(file_service.clone()))
-            })
-            .bind(slave_host)?
-            .run()
-            .await?;
+            http_server.bind(&slave_host)?.run().await?;
         }
 
         Ok(())
@@ -0,0 +1,330 @@
+use crate::common::*;
+
+use crate::traits::service::{backup_service::*, receive_service::*};
+
+use crate::configs::configs::*;
+use crate::configs::sync_hook_config::*;
+
+use crate::model::elastic_msg::*;
+use crate::model::file_meta_info::*;
+use crate::model::hook_result::*;
+
+use crate::utils_modules::hook_utils::*;
+use crate::utils_modules::io_utils::*;
+use crate::utils_modules::logger_utils::*;
+
+#[derive(Debug)]
+pub struct SyncHookHandler<F>
+where
+    F: ReceiveService + BackupService + Sync + Send + 'static,
+{
+    file_service: Arc<F>,
+}
+
+impl<F> SyncHookHandler<F>
+where
+    F: ReceiveService + BackupService + Sync + Send + 'static,
+{
+    pub fn new(file_service: Arc<F>) -> Self {
+        Self { file_service }
+    }
+
+    #[doc = "파일에 설정된 동기화 훅을 실행하면서 수신이 완료된 임시 파일을 반영해주는 함수"]
+    /// slave 가 파일을 쓰는 모든 경로(upload, pull, 백업 복원, 훅이 설정된 이름으로의 이름변경)는 이 함수를 통해 반영된다.
+    /// (삭제는 파일을 쓰지 않으므로 훅을 실행하지 않는다.)
+    /// 1. pre-write 훅이 실패하면 임시 파일을 제거하고 반영하지 않는다.
+    /// 2. 기존 파일을 백업 후 교체하고 수신 정보를 기록한다.
+    /// 3. post-write 훅이 실패하면 교체 전 상태로 되돌린다.
+    ///
+    /// # Arguments
+    /// * `staged_file_path`    - 수신이 완료된 임시 파일 경로
+    /// * `target_file_path`    - 교체 대상이 되는 파일 경로
+    /// * `file_name`           - 반영할 파일의 이름 (감시 디렉토리 기준 상대경로)
+    /// * `from_host`           - 파일을 보낸 곳
+    /// * `version`             - 반영할 파일의 버전 (알 수 없는 경우 None)
+    /// * `file_meta`           - 교체된 파일에 적용할 메타정보 (없는 경우 None)
+    ///
+    /// # Returns
+    /// * Result<SyncWriteResult, anyhow::Error>
+    pub async fn commit_with_sync_hooks(
+        &self,
+        staged_file_path: &Path,
+        target_file_path: &Path,
+        file_name: &str,
+        from_host: &str,
+        version: Option<u64>,
+        file_meta: Option<&FileMetaInfo>,
+    ) -> Result<SyncWriteResult, anyhow::Error> {
+        let slave_backup_path: String;
+        let slave_host: String;
+        let sync_hook: Option<SyncHookConfig>;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+                Ok(server_config) => server_config,
+                Err(e) => {
+                    let _ = fs::remove_file(staged_file_path);
+                    return Err(e);
+                }
+            };
+
+            slave_backup_path = server_config
+                .server
+                .slave_backup_path()
+                .clone()
+                .unwrap_or_default();
+            slave_host = server_config.server.host().clone();
+            sync_hook = match server_config.server.get_sync_hook(file_name) {
+                Ok(sync_hook) => sync_hook,
+                Err(e) => {
+                    let _ = fs::remove_file(staged_file_path);
+                    return Err(e);
+                }
+            };
+        }
+
+        /* 훅 명령에는 대상 파일 정보가 환경변수로 전달된다. */
+        let target_file_path_str: String = target_file_path.to_string_lossy().to_string();
+        let staged_file_path_str: String = staged_file_path.to_string_lossy().to_string();
+        let version_str: String = version.map(|version| version.to_string()).unwrap_or_default();
+        let hook_envs: Vec<(&str, &str)> = vec![
+            ("FILE_SYNC_FILE_NAME", file_name),
+            ("FILE_SYNC_TARGET_PATH", target_file_path_str.as_str()),
+            ("FILE_SYNC_FROM_HOST", from_host),
+            ("FILE_SYNC_VERSION", version_str.as_str()),
+        ];
+        let mut hook_results: Vec<HookResult> = Vec::new();
+
+        /* pre-write 훅이 실패하면 수신한 파일을 반영하지 않는다. -> 기존 파일은 그대로 유지된다. */
+        if let Some(sync_hook) = sync_hook.as_ref() {
+            if let Some(pre_write) = sync_hook.pre_write() {
+                let mut pre_write_envs: Vec<(&str, &str)> = hook_envs.clone();
+                pre_write_envs.push(("FILE_SYNC_STAGED_PATH", staged_file_path_str.as_str()));
+
+                let hook_result: HookResult =
+                    run_sync_hook("pre-write", pre_write, &pre_write_envs, sync_hook.get_timeout()).await;
+
+                if !hook_result.is_success() {
+                    warn!(
+                        "[Warn][commit_with_sync_hooks()] The pre-write hook rejected '{}' : {}",
+                        file_name,
+                        hook_result.get_summary()
+                    );
+                    let _ = fs::remove_file(staged_file_path);
+                    write_hook_event(from_host, &slave_host, file_name, "hook-rejected", &hook_result.get_summary());
+
+                    return Ok(SyncWriteResult::Rejected(hook_result));
+                }
+
+                hook_results.push(hook_result);
+            }
+        }
+
+        /* post-write 훅이 실패했을 때 되돌릴 기존 파일이 있는지 확인 */
+        let had_previous_file: bool = target_file_path.exists();
+
+        /* 기존 파일 백업 후 임시 파일로 교체하고 수신한 파일의 정보를 기록해준다. -> manifest 조회시 사용 */
+        if !self.file_service.commit_received_file(
+            staged_file_path,
+            target_file_path,
+            &slave_backup_path,
+            file_name,
+            from_host,
+            version,
+        )? {
+            return Ok(SyncWriteResult::Stale);
+        }
+
+        /* 내용은 이미 반영되었으므로 메타정보 적용에 실패하더라도 에러만 남긴다. */
+        if let Some(file_meta) = file_meta {
+            if let Err(e) = self
+                .file_service
+                .apply_received_file_meta(target_file_path, file_meta)
+            {
+                error!(
+                    "[Error][commit_with_sync_hooks()] Failed to apply file metadata of '{}' : {:?}",
+                    file_name, e
+                );
+            }
+        }
+
+        /* post-write 훅이 실패하면 기존 파일로 되돌린다. */
+        if let Some(sync_hook) = sync_hook.as_ref() {
+            if let Some(post_write) = sync_hook.post_write() {
+                let hook_result: HookResult =
+                    run_sync_hook("post-write", post_write, &hook_envs, sync_hook.get_timeout()).await;
+
+                if !hook_result.is_success() {
+                    error!(
+                        "[Error][commit_with_sync_hooks()] The post-write hook failed for '{}' : {}",
+                        file_name,
+                        hook_result.get_summary()
+                    );
+
+                    let restore_detail: String = match self.restore_after_failed_hook(
+                        target_file_path,
+                        &slave_backup_path,
+                        file_name,
+                        had_previous_file,
+                    ) {
+                        Ok(restore_detail) => restore_detail,
+                        Err(e) => {
+                            error!("[Error][commit_with_sync_hooks()] {:?}", e);
+                            format!("failed to restore the previous file: {:?}", e)
+                        }
+                    };
+
+                    write_hook_event(
+                        from_host,
+                        &slave_host,
+                        file_name,
+                        "hook-failed",
+                        &format!("{} -> {}", hook_result.get_summary(), restore_detail),
+                    );
+
+                    return Ok(SyncWriteResult::Reverted(hook_result, restore_detail));
+                }
+
+                hook_results.push(hook_result);
+            }
+        }
+
+        Ok(SyncWriteResult::Committed(hook_results))
+    }
+
+    #[doc = "master 에서 이름이 변경된 파일을 새 이름에 설정된 동기화 훅을 거쳐 반영해주는 함수"]
+    /// 새 이름에 동기화 훅이 설정되지 않은 경우에는 파일 이름만 변경한다.
+    /// 훅이 설정된 경우에는 이전 파일의 내용을 새 이름으로 쓰는 것으로 보고 훅을 실행한 뒤, 반영된 경우에만 이전 파일을 제거한다.
+    ///
+    /// # Arguments
+    /// * `from_file_path`  - 이전 파일 경로
+    /// * `to_file_path`    - 새로운 파일 경로
+    /// * `from_file_name`  - 이전 파일이름 (감시 디렉토리 기준 상대경로)
+    /// * `to_file_name`    - 새로운 파일이름 (감시 디렉토리 기준 상대경로)
+    /// * `from_host`       - 이름변경을 보낸 곳
+    ///
+    /// # Returns
+    /// * Result<SyncWriteResult, anyhow::Error>
+    pub async fn rename_with_sync_hooks(
+        &self,
+        from_file_path: &Path,
+        to_file_path: &Path,
+        from_file_name: &str,
+        to_file_name: &str,
+        from_host: &str,
+    ) -> Result<SyncWriteResult, anyhow::Error> {
+        let slave_backup_path: String;
+        let preserve_owner: bool;
+        let has_sync_hook: bool;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            slave_backup_path = server_config
+                .server
+                .slave_backup_path()
+                .clone()
+                .unwrap_or_default();
+            preserve_owner = server_config.server.is_preserve_owner();
+            has_sync_hook = server_config.server.get_sync_hook(to_file_name)?.is_some();
+        }
+
+        if !has_sync_hook {
+            self.file_service.rename_file_with_backup(
+                from_file_path.to_path_buf(),
+                to_file_path.to_path_buf(),
+                &slave_backup_path,
+                from_file_name,
+                to_file_name,
+            )?;
+
+            return Ok(SyncWriteResult::Committed(Vec::new()));
+        }
+
+        /* 이전 파일의 내용, 버전, 메타정보를 그대로 새 이름으로 반영한다. */
+        let version: Option<u64> = self
+            .file_service
+            .get_receive_file_info(from_file_name)?
+            .and_then(|receive_info| *receive_info.version());
+        let file_meta: FileMetaInfo = get_file_meta(from_file_path, preserve_owner)?;
+        let file_data: Vec<u8> = fs::read(from_file_path)?;
+        let staged_file_path: PathBuf = self.file_service.write_staged_file(to_file_path, &file_data)?;
+
+        let write_result: SyncWriteResult = self
+            .commit_with_sync_hooks(
+                &staged_file_path,
+                to_file_path,
+                to_file_name,
+                from_host,
+                version,
+                Some(&file_meta),
+            )
+            .await?;
+
+        if let SyncWriteResult::Committed(_) = &write_result {
+            self.file_service.delete_file_with_backup(
+                from_file_path.to_path_buf(),
+                &slave_backup_path,
+                from_file_name,
+            )?;
+        }
+
+        Ok(write_result)
+    }
+
+    #[doc = "post-write 훅이 실패한 파일을 교체 전 상태로 되돌려주는 함수"]
+    /// 기존 파일이 있었다면 교체하면서 남긴 백업으로 복원하고, 새로 생긴 파일이라면 제거한다.
+    ///
+    /// # Arguments
+    /// * `target_file_path`    - 되돌릴 파일 경로
+    /// * `backup_dir_path`     - 백업 디렉토리 경로
+    /// * `file_name`           - 되돌릴 파일의 이름 (감시 디렉토리 기준 상대경로)
+    /// * `had_previous_file`   - 교체 전에 파일이 존재했는지 여부
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - 되돌린 결과 메시지
+    fn restore_after_failed_hook(
+        &self,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+        had_previous_file: bool,
+    ) -> Result<String, anyhow::Error> {
+        if !had_previous_file {
+            self.file_service.remove_received_file(target_file_path, file_name)?;
+            return Ok("removed the new file".to_string());
+        }
+
+        /* 교체하면서 남긴 백업이 가장 최근 백업이다. -> 거절된 내용은 백업하지 않고 버전도 함께 되돌린다. */
+        let backup_id: String = self
+            .file_service
+            .get_backup_file_list(backup_dir_path, file_name)?
+            .first()
+            .map(|backup| backup.backup_id().to_string())
+            .ok_or_else(|| anyhow!("[Error][restore_after_failed_hook()] There is no backup of '{}'.", file_name))?;
+
+        if !self
+            .file_service
+            .revert_received_file(target_file_path, backup_dir_path, file_name, &backup_id)?
+        {
+            return Err(anyhow!(
+                "[Error][restore_after_failed_hook()] The backup '{}' of '{}' does not exist.",
+                backup_id,
+                file_name
+            ));
+        }
+
+        Ok(format!("restored from backup {}", backup_id))
+    }
+}
+
+#[doc = "동기화 훅으로 파일 반영이 거절되거나 되돌려진 경우 작업 이벤트로 남겨주는 함수"]
+/// # Arguments
+/// * `from_host`   - 파일을 보낸 곳
+/// * `slave_host`  - 훅을 실행한 slave 호스트
+/// * `file_name`   - 대상 파일의 이름
+/// * `task_status` - hook-rejected, hook-failed
+/// * `task_detail` - 훅 실행 결과
+fn write_hook_event(from_host: &str, slave_host: &str, file_name: &str, task_status: &str, task_detail: &str) {
+    match ElasticMsg::new(from_host, slave_host, file_name, task_status, task_detail) {
+        Ok(task_event) => write_task_event_log(&task_event),
+        Err(e) => error!("[Error][write_hook_event()] {:?}", e),
+    }
+}
@@ -0,0 +1,282 @@
+use crate::common::*;
+
+use crate::model::elastic_msg::*;
+
+use crate::utils_modules::logger_utils::*;
+
+#[doc = "slave 가 검색 분석기를 리로드한 결과를 담는 헤더 - 성공한 인덱스 수/전체 인덱스 수"]
+pub const ANALYZER_RELOAD_HEADER: &str = "X-Analyzer-Reload";
+
+#[doc = "검색 분석기 리로드 작업 이벤트의 상태값"]
+pub const ANALYZER_RELOAD_STATUS: &str = "analyzer-reload";
+pub const ANALYZER_RELOAD_FAILED_STATUS: &str = "analyzer-reload-failed";
+
+#[doc = "slave 가 응답한 검색 분석기 리로드 결과를 master 에 남겨주는 함수 - 실패한 인덱스가 있다면 작업 이벤트로도 기록한다."]
+/// # Arguments
+/// * `reload_summary`  - ANALYZER_RELOAD_HEADER 값 (리로드하지 않은 경우 None)
+/// * `file_path`       - 전달한 파일
+/// * `from_host`       - 파일을 보낸 master
+/// * `to_host`         - 파일을 전달받은 slave
+pub fn report_analyzer_reload(reload_summary: Option<&str>, file_path: &str, from_host: &str, to_host: &str) {
+    let reload_summary: &str = match reload_summary {
+        Some(reload_summary) => reload_summary,
+        None => return,
+    };
+
+    if !is_analyzer_reload_failed(reload_summary) {
+        info!(
+            "The slave '{}' reloaded search analyzers for '{}' : {}",
+            to_host, file_path, reload_summary
+        );
+        return;
+    }
+
+    warn!(
+        "[Warn] The slave '{}' failed to reload search analyzers for '{}' : {}",
+        to_host, file_path, reload_summary
+    );
+
+    /* 다른 동기화 실패와 마찬가지로 작업 이벤트로 남겨 조회/알림에 사용할 수 있도록 한다. */
+    match ElasticMsg::new(
+        from_host,
+        to_host,
+        file_path,
+        ANALYZER_RELOAD_FAILED_STATUS,
+        &format!("analyzer reload {}", reload_summary),
+    ) {
+        Ok(task_event) => write_task_event_log(&task_event),
+        Err(e) => error!("[Error][report_analyzer_reload()] {:?}", e),
+    }
+}
+
+#[doc = "검색 분석기 리로드 요약(성공한 인덱스 수/전체 인덱스 수)에 실패한 인덱스가 있는지 확인해주는 함수"]
+/// 형식이 잘못된 요약은 실패로 본다.
+pub fn is_analyzer_reload_failed(reload_summary: &str) -> bool {
+    reload_summary
+        .split_once('/')
+        .is_none_or(|(succeeded, total)| succeeded != total)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct AnalyzerReloadResult {
+    pub index: String,
+    pub status_code: Option<u16>,           /* Elasticsearch 응답 코드 (요청 자체가 실패한 경우 None) */
+    pub reloaded_analyzers: Vec<String>,
+    pub failed_shards: u64,
+    pub detail: String,
+}
+
+impl AnalyzerReloadResult {
+    #[doc = "`_reload_search_analyzers` 응답으로 인덱스의 리로드 결과를 만들어주는 함수"]
+    /// # Arguments
+    /// * `index`           - 리로드한 인덱스
+    /// * `status_code`     - Elasticsearch 응답 코드
+    /// * `response_body`   - Elasticsearch 응답 본문
+    ///
+    /// # Returns
+    /// * AnalyzerReloadResult
+    pub fn from_response(index: &str, status_code: u16, response_body: &serde_json::Value) -> Self {
+        /* 응답 예: {"_shards":{"total":2,"successful":2,"failed":0},"reload_details":[{"index":"..","reloaded_analyzers":[".."],"reloaded_node_ids":[".."]}]} */
+        let total_shards: u64 = response_body["_shards"]["total"].as_u64().unwrap_or_default();
+        let failed_shards: u64 = response_body["_shards"]["failed"].as_u64().unwrap_or_default();
+
+        let mut reloaded_analyzers: Vec<String> = Vec::new();
+        for reload_detail in response_body["reload_details"].as_array().cloned().unwrap_or_default() {
+            for analyzer in reload_detail["reloaded_analyzers"].as_array().cloned().unwrap_or_default() {
+                if let Some(analyzer) = analyzer.as_str() {
+                    if !reloaded_analyzers.iter().any(|reloaded| reloaded == analyzer) {
+                        reloaded_analyzers.push(analyzer.to_string());
+                    }
+                }
+            }
+        }
+
+        let detail: String = if (200..300).contains(&status_code) {
+            format!(
+                "status {}, shards {}/{}, analyzers {:?}",
+                status_code,
+                total_shards - failed_shards.min(total_shards),
+                total_shards,
+                reloaded_analyzers
+            )
+        } else {
+            let reason: String = response_body["error"]["reason"]
+                .as_str()
+                .map(|reason| reason.to_string())
+                .unwrap_or_else(|| response_body.to_string());
+            format!("status {}: {}", status_code, reason)
+        };
+
+        Self::new(
+            index.to_string(),
+            Some(status_code),
+            reloaded_analyzers,
+            failed_shards,
+            detail,
+        )
+    }
+
+    #[doc = "리로드 요청 자체가 실패한 경우의 결과를 만들어주는 함수 (연결 실패, 시간 초과 등)"]
+    pub fn from_request_error(index: &str, error: &anyhow::Error) -> Self {
+        Self::new(
+            index.to_string(),
+            None,
+            Vec::new(),
+            0,
+            format!("request failed: {:?}", error),
+        )
+    }
+
+    #[doc = "리로드 요청이 성공했고 실패한 샤드가 없는지 여부"]
+    pub fn is_success(&self) -> bool {
+        self.status_code.is_some_and(|status_code| (200..300).contains(&status_code)) && self.failed_shards == 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct AnalyzerReloadReport {
+    pub file_name: String,
+    pub results: Vec<AnalyzerReloadResult>,
+}
+
+impl AnalyzerReloadReport {
+    #[doc = "모든 인덱스의 리로드가 성공했는지 여부"]
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(AnalyzerReloadResult::is_success)
+    }
+
+    #[doc = "작업 이벤트에 기록할 상태값"]
+    pub fn get_task_status(&self) -> &'static str {
+        if self.is_success() {
+            ANALYZER_RELOAD_STATUS
+        } else {
+            ANALYZER_RELOAD_FAILED_STATUS
+        }
+    }
+
+    #[doc = "리로드에 실패한 인덱스 목록"]
+    pub fn get_failed_indices(&self) -> Vec<String> {
+        self.results
+            .iter()
+            .filter(|result| !result.is_success())
+            .map(|result| result.index.clone())
+            .collect()
+    }
+
+    #[doc = "상위 서버에 응답할 헤더 값 - 예: 2/3"]
+    pub fn get_summary(&self) -> String {
+        let succeeded: usize = self.results.iter().filter(|result| result.is_success()).count();
+        format!("{}/{}", succeeded, self.results.len())
+    }
+
+    #[doc = "응답 본문에 담을 인덱스별 리로드 결과"]
+    pub fn get_report(&self) -> String {
+        let mut report: String = format!("[analyzer reload {}]", self.get_summary());
+
+        for result in &self.results {
+            report.push_str(&format!(
+                "\n{} : {} ({})",
+                result.index,
+                if result.is_success() { "reloaded" } else { "failed" },
+                result.detail
+            ));
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_ok_result() -> AnalyzerReloadResult {
+        AnalyzerReloadResult::from_response(
+            "dict_ok",
+            200,
+            &serde_json::json!({
+                "_shards": { "total": 2, "successful": 2, "failed": 0 },
+                "reload_details": [
+                    { "index": "dict_ok", "reloaded_analyzers": ["synonym_search"], "reloaded_node_ids": ["node_1"] },
+                    { "index": "dict_ok", "reloaded_analyzers": ["synonym_search"], "reloaded_node_ids": ["node_2"] }
+                ]
+            }),
+        )
+    }
+
+    #[test]
+    fn from_response_success() {
+        let result: AnalyzerReloadResult = get_ok_result();
+
+        assert!(result.is_success());
+        assert_eq!(*result.failed_shards(), 0);
+        assert_eq!(result.reloaded_analyzers(), &vec!["synonym_search".to_string()]);
+        assert_eq!(result.detail(), "status 200, shards 2/2, analyzers [\"synonym_search\"]");
+    }
+
+    #[test]
+    fn from_response_failed_shards() {
+        let result: AnalyzerReloadResult = AnalyzerReloadResult::from_response(
+            "dict_partial",
+            200,
+            &serde_json::json!({
+                "_shards": { "total": 2, "successful": 1, "failed": 1 },
+                "reload_details": [
+                    { "index": "dict_partial", "reloaded_analyzers": ["synonym_search"], "reloaded_node_ids": ["node_1"] }
+                ]
+            }),
+        );
+
+        assert!(!result.is_success());
+        assert_eq!(*result.failed_shards(), 1);
+        assert_eq!(result.detail(), "status 200, shards 1/2, analyzers [\"synonym_search\"]");
+    }
+
+    #[test]
+    fn from_response_error_body() {
+        let result: AnalyzerReloadResult = AnalyzerReloadResult::from_response(
+            "dict_missing",
+            404,
+            &serde_json::json!({
+                "error": { "type": "index_not_found_exception", "reason": "no such index [dict_missing]" },
+                "status": 404
+            }),
+        );
+
+        assert!(!result.is_success());
+        assert!(result.reloaded_analyzers().is_empty());
+        assert_eq!(result.detail(), "status 404: no such index [dict_missing]");
+    }
+
+    #[test]
+    fn report_summary_and_task_status() {
+        let success_report: AnalyzerReloadReport =
+            AnalyzerReloadReport::new("dict/synonyms.txt".to_string(), vec![get_ok_result()]);
+
+        assert_eq!(success_report.get_summary(), "1/1");
+        assert_eq!(success_report.get_task_status(), ANALYZER_RELOAD_STATUS);
+
+        let failed_report: AnalyzerReloadReport = AnalyzerReloadReport::new(
+            "dict/synonyms.txt".to_string(),
+            vec![
+                get_ok_result(),
+                AnalyzerReloadResult::from_request_error("dict_other", &anyhow!("connection refused")),
+            ],
+        );
+
+        assert_eq!(failed_report.get_summary(), "1/2");
+        assert_eq!(failed_report.get_failed_indices(), vec!["dict_other".to_string()]);
+        assert_eq!(failed_report.get_task_status(), ANALYZER_RELOAD_FAILED_STATUS);
+        assert!(failed_report.get_report().contains("\ndict_other : failed (request failed:"));
+    }
+
+    #[test]
+    fn reload_summary_failure_detection() {
+        assert!(!is_analyzer_reload_failed("2/2"));
+        assert!(is_analyzer_reload_failed("1/2"));
+        assert!(is_analyzer_reload_failed("0/1"));
+        assert!(is_analyzer_reload_failed("invalid"));
+    }
+}
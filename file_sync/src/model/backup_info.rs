@@ -0,0 +1,21 @@
+use crate::common::*;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct BackupFileInfo {
+    pub backup_id: String,  /* 백업 시각 (예: 2025_01_15_093000.123456) */
+    pub version: Option<u64>,   /* 백업된 내용의 master 버전 (알 수 없는 경우 None) */
+    pub source: String,         /* 백업된 내용을 보낸 곳 */
+    pub hash: String,           /* 원래 내용의 해시값 (압축된 백업도 동일) */
+    pub size: u64,              /* 원래 내용의 크기 */
+    pub stored_size: u64,       /* 저장소에 저장된 크기 (압축된 경우 압축된 크기, 다른 백업과 공유될 수 있음) */
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct BackupListInfo {
+    pub host: String,
+    pub file_name: String,
+    pub backups: Vec<BackupFileInfo>,
+}
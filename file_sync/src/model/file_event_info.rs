@@ -0,0 +1,9 @@
+use crate::common::*;
+
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct FileEventInfo {
+    pub event_type: String,
+    pub file_path: String,
+    pub from_file_path: Option<String>,
+}
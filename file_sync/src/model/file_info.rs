@@ -4,3 +4,21 @@ use crate::common::*;
 pub struct FileInfo {
     pub filename: String,
 }
+
+#[derive(Deserialize)]
+pub struct RenameInfo {
+    pub from_filename: String,
+    pub to_filename: String,
+}
+
+#[derive(Deserialize)]
+pub struct BackupFileQuery {
+    pub filename: String,
+    pub backup_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct RollbackQuery {
+    pub filename: String,
+    pub version: Option<u64>, /* 지정하지 않은 경우 직전 버전으로 되돌린다. */
+}
@@ -0,0 +1,61 @@
+use crate::common::*;
+
+#[doc = "파일 권한을 담는 헤더 - 8진수 문자열 (예: 644)"]
+pub const FILE_MODE_HEADER: &str = "X-File-Mode";
+#[doc = "파일 소유자 uid 를 담는 헤더"]
+pub const FILE_UID_HEADER: &str = "X-File-Uid";
+#[doc = "파일 소유 그룹 gid 를 담는 헤더"]
+pub const FILE_GID_HEADER: &str = "X-File-Gid";
+#[doc = "파일 수정시각을 담는 헤더 - unix time(초)"]
+pub const FILE_MTIME_HEADER: &str = "X-File-Mtime";
+
+#[doc = "master 파일의 권한, 소유자, 수정시각 정보 - slave 는 파일을 교체한 뒤 해당 정보를 적용한다."]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct FileMetaInfo {
+    pub mode: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub mtime: Option<u64>,
+}
+
+impl FileMetaInfo {
+    #[doc = "파일 전송 요청에 함께 보낼 헤더 목록을 반환해주는 함수 - 값이 없는 항목은 보내지 않는다."]
+    pub fn get_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers: Vec<(&'static str, String)> = Vec::new();
+
+        if let Some(mode) = self.mode {
+            headers.push((FILE_MODE_HEADER, format!("{:o}", mode)));
+        }
+        if let Some(uid) = self.uid {
+            headers.push((FILE_UID_HEADER, uid.to_string()));
+        }
+        if let Some(gid) = self.gid {
+            headers.push((FILE_GID_HEADER, gid.to_string()));
+        }
+        if let Some(mtime) = self.mtime {
+            headers.push((FILE_MTIME_HEADER, mtime.to_string()));
+        }
+
+        headers
+    }
+
+    #[doc = "요청 헤더에서 파일 메타정보를 읽어주는 함수 - 잘못된 값은 무시한다."]
+    /// # Arguments
+    /// * `headers` - Http 요청 헤더
+    ///
+    /// # Returns
+    /// * FileMetaInfo
+    pub fn from_headers(headers: &actix_web::http::header::HeaderMap) -> Self {
+        let get_header = |header_name: &str| -> Option<&str> {
+            headers.get(header_name).and_then(|value| value.to_str().ok())
+        };
+
+        FileMetaInfo {
+            mode: get_header(FILE_MODE_HEADER).and_then(|value| u32::from_str_radix(value, 8).ok()),
+            uid: get_header(FILE_UID_HEADER).and_then(|value| value.parse::<u32>().ok()),
+            gid: get_header(FILE_GID_HEADER).and_then(|value| value.parse::<u32>().ok()),
+            mtime: get_header(FILE_MTIME_HEADER).and_then(|value| value.parse::<u64>().ok()),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::common::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct HookResult {
+    pub stage: String,              /* pre-write, post-write */
+    pub command: String,
+    pub exit_code: Option<i32>,     /* 시간 초과나 실행 실패로 종료 코드가 없는 경우 None */
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub elapsed_ms: u64,
+}
+
+impl HookResult {
+    #[doc = "훅 명령이 제한 시간 안에 종료 코드 0 으로 끝났는지 여부"]
+    pub fn is_success(&self) -> bool {
+        !self.timed_out && self.exit_code == Some(0)
+    }
+
+    #[doc = "훅 실행 결과 요약 - 예: post-write hook exited with 1 (120ms)"]
+    pub fn get_summary(&self) -> String {
+        match (self.timed_out, self.exit_code) {
+            (true, _) => format!("{} hook timed out ({}ms)", self.stage, self.elapsed_ms),
+            (false, Some(exit_code)) => format!(
+                "{} hook exited with {} ({}ms)",
+                self.stage, exit_code, self.elapsed_ms
+            ),
+            (false, None) => format!("{} hook failed to run ({}ms)", self.stage, self.elapsed_ms),
+        }
+    }
+
+    #[doc = "응답 본문에 담을 훅 실행 결과 - 요약과 함께 캡처한 출력을 담는다."]
+    pub fn get_report(&self) -> String {
+        let mut report: String = format!("[{}] {}", self.get_summary(), self.command);
+
+        if !self.stdout.is_empty() {
+            report.push_str(&format!("\n--- stdout ---\n{}", self.stdout.trim_end()));
+        }
+
+        if !self.stderr.is_empty() {
+            report.push_str(&format!("\n--- stderr ---\n{}", self.stderr.trim_end()));
+        }
+
+        report
+    }
+}
+
+#[doc = "동기화 훅을 거쳐 파일을 반영한 결과"]
+#[derive(Debug, Clone)]
+pub enum SyncWriteResult {
+    Committed(Vec<HookResult>),     /* 반영 완료 - 실행된 훅 결과 */
+    Stale,                          /* 더 최신 버전이 이미 반영되어 있어 반영하지 않음 */
+    Rejected(HookResult),           /* pre-write 훅이 거절하여 반영하지 않음 */
+    Reverted(HookResult, String),   /* post-write 훅이 실패하여 되돌림 - 되돌린 결과 메시지 */
+}
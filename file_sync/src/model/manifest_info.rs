@@ -0,0 +1,51 @@
+use crate::common::*;
+
+use crate::model::file_meta_info::*;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct ManifestFileInfo {
+    pub file_path: String,
+    pub hash: String,
+    pub size: u64,
+    pub mtime: String,
+    pub version: Option<u64>,
+    pub received_at: Option<String>,
+    #[serde(default)]
+    pub file_meta: Option<FileMetaInfo>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct ManifestInfo {
+    pub host: String,
+    pub files: Vec<ManifestFileInfo>,
+}
+
+impl ManifestInfo {
+    #[doc = "manifest 에서 특정 파일의 해시값을 찾아주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 감시 디렉토리 기준 상대경로
+    ///
+    /// # Returns
+    /// * Option<&str> - 해당 파일이 존재하지 않는 경우 None
+    pub fn get_file_hash(&self, file_path: &str) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|file| file.file_path() == file_path)
+            .map(|file| file.hash().as_str())
+    }
+
+    #[doc = "manifest 에서 특정 파일의 버전을 찾아주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 감시 디렉토리 기준 상대경로
+    ///
+    /// # Returns
+    /// * Option<u64> - 해당 파일이 존재하지 않거나 버전 정보가 없는 경우 None
+    pub fn get_file_version(&self, file_path: &str) -> Option<u64> {
+        self.files
+            .iter()
+            .find(|file| file.file_path() == file_path)
+            .and_then(|file| file.version)
+    }
+}
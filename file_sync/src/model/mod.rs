@@ -1,3 +1,13 @@
+pub mod analyzer_reload_result;
+pub mod backup_info;
 pub mod elastic_msg;
+pub mod file_event_info;
 pub mod file_info;
+pub mod file_meta_info;
+pub mod hook_result;
+pub mod manifest_info;
 pub mod monitoring_path_info;
+pub mod pending_file_event;
+pub mod relay_result;
+pub mod rollback_result;
+pub mod transfer_info;
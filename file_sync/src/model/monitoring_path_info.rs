@@ -3,6 +3,7 @@ use crate::common::*;
 #[derive(Deserialize, Debug, Getters, new)]
 #[getset(get = "pub")]
 pub struct MonitoringPathInfo {
+    pub root_name: String,
     pub file_path: String,
     pub full_file_path: String,
 }
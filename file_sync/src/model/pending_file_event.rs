@@ -0,0 +1,9 @@
+use crate::common::*;
+
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct PendingFileEvent {
+    pub event_type: String,
+    pub deadline: Instant,                     /* 이 시각까지 이벤트가 없으면 처리를 시도한다. */
+    pub file_stat: Option<(u64, SystemTime)>,  /* 마지막으로 확인한 파일의 크기와 수정시각 */
+}
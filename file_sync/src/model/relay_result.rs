@@ -0,0 +1,24 @@
+use crate::common::*;
+
+#[doc = "relay 가 하위 slave 들에게 전달한 결과를 담는 헤더 - 성공한 slave 수/전체 slave 수"]
+pub const RELAY_RESULT_HEADER: &str = "X-Relay-Result";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct RelayResult {
+    pub relay_host: String,
+    pub total: usize,
+    pub failed_slaves: Vec<String>,
+}
+
+impl RelayResult {
+    #[doc = "모든 하위 slave 에 전달이 성공했는지 여부"]
+    pub fn is_success(&self) -> bool {
+        self.failed_slaves.is_empty()
+    }
+
+    #[doc = "상위 서버에 응답할 헤더 값 - 예: 2/3"]
+    pub fn get_summary(&self) -> String {
+        format!("{}/{}", self.total - self.failed_slaves.len(), self.total)
+    }
+}
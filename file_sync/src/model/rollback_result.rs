@@ -0,0 +1,29 @@
+use crate::common::*;
+
+use crate::repository::history_repository::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct RollbackResult {
+    pub file_name: String,
+    pub from_version: u64,          /* 롤백 전에 배포되어 있던 버전 */
+    pub to_version: u64,            /* 되돌린 버전 */
+    pub pushed_version: u64,        /* 롤백 내용이 새로 배포된 버전 */
+    pub target_slaves: Vec<String>, /* from_version 을 수신했던 slave 목록 */
+    pub failed_slaves: Vec<String>,
+}
+
+impl RollbackResult {
+    #[doc = "모든 대상 slave 에 롤백이 반영되었는지 여부"]
+    pub fn is_success(&self) -> bool {
+        self.failed_slaves.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Getters, new)]
+#[getset(get = "pub")]
+pub struct HistoryListInfo {
+    pub host: String,
+    pub file_name: String,
+    pub versions: Vec<HistoryVersion>,
+}
@@ -0,0 +1,29 @@
+use crate::common::*;
+
+use crate::model::file_meta_info::*;
+
+use crate::utils_modules::hash_utils::*;
+
+#[doc = "파일의 변경 버전을 담는 헤더 - slave 는 이미 가지고 있는 버전보다 오래된 파일을 거절한다."]
+pub const FILE_VERSION_HEADER: &str = "X-File-Version";
+
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct TransferInfo {
+    pub content_hash: String,
+    pub file_version: u64,
+    pub file_meta: FileMetaInfo,
+}
+
+impl TransferInfo {
+    #[doc = "파일 전송 요청에 함께 보낼 헤더 목록을 반환해주는 함수"]
+    pub fn get_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers: Vec<(&'static str, String)> = vec![
+            (CONTENT_SHA256_HEADER, self.content_hash.clone()),
+            (FILE_VERSION_HEADER, self.file_version.to_string()),
+        ];
+
+        headers.extend(self.file_meta.get_headers());
+        headers
+    }
+}
@@ -4,6 +4,9 @@ use crate::utils_modules::io_utils::*;
 
 use crate::configs::configs::*;
 
+#[doc = "현재 Hash Storage 파일의 형식 버전 - 0 은 slave 별 수신 확인 정보가 없던 이전 형식이다."]
+const HASH_STORAGE_FORMAT_VERSION: u32 = 1;
+
 #[doc = "Hash Storage를 전역적으로 사용하기 위함."]
 static HASH_STORAGE_CLIENT: once_lazy<Arc<Mutex<HashStorage>>> =
     once_lazy::new(initialize_hash_storage_clients);
@@ -11,6 +14,7 @@ static HASH_STORAGE_CLIENT: once_lazy<Arc<Mutex<HashStorage>>> =
 #[doc = "Hash Storage 를 초기화해주는 함수"]
 pub fn initialize_hash_storage_clients() -> Arc<Mutex<HashStorage>> {
     let hash_file_path: Option<String>;
+    let slave_url: Vec<String>;
     {
         let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
             Ok(server_config) => server_config,
@@ -21,6 +25,7 @@ pub fn initialize_hash_storage_clients() -> Arc<Mutex<HashStorage>> {
         };
 
         hash_file_path = server_config.server.hash_storage_path().clone();
+        slave_url = server_config.server.slave_address().clone().unwrap_or_default();
     }
 
     let hash_file: String = match hash_file_path {
@@ -33,7 +38,9 @@ pub fn initialize_hash_storage_clients() -> Arc<Mutex<HashStorage>> {
 
     //let hash_map_dir: String = format!("{}hash_storage", hash_file);
 
-    let hash_storage: HashStorage = match HashStorage::load(&hash_file) {
+    let hash_storage: HashStorage = match HashStorage::load(&hash_file)
+        .and_then(|mut hash_storage| hash_storage.migrate(&slave_url).map(|_| hash_storage))
+    {
         Ok(hash_storage) => hash_storage,
         Err(e) => {
             error!(
@@ -55,7 +62,15 @@ pub fn get_hash_storage() -> Arc<Mutex<HashStorage>> {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HashStorage {
-    pub hashes: HashMap<String, Vec<u8>>,
+    #[serde(default)]
+    pub format_version: u32,
+    pub hashes: HashMap<String, Vec<u8>>, /* master 에서 마지막으로 감지한 해시값 */
+    #[serde(default)]
+    pub acked_hashes: HashMap<String, HashMap<String, Vec<u8>>>, /* 파일별 -> slave 별로 수신이 확인된 해시값 */
+    #[serde(default)]
+    pub versions: HashMap<String, u64>, /* 파일별 변경 버전 - 변경이 감지될 때마다 증가한다. */
+    #[serde(default)]
+    pub tombstones: HashMap<String, String>,
     pub dir_path: String,
 }
 
@@ -70,13 +85,34 @@ impl HashStorage {
             .to_str()
             .ok_or_else(|| anyhow!("[Error][load()]The path cannot be converted into a string."))?;
 
-        let mut hash_storage: HashStorage = match serde_json::from_str(&contents) {
-            Ok(hashes) => hashes,
-            Err(e) => {
-                warn!("[WARN][load()] No data exists in file 'hash map': {:?}", e);
-                HashStorage {
-                    hashes: HashMap::new(),
-                    dir_path: dir_path_str.to_string(),
+        let empty_storage: HashStorage = HashStorage {
+            format_version: HASH_STORAGE_FORMAT_VERSION,
+            hashes: HashMap::new(),
+            acked_hashes: HashMap::new(),
+            versions: HashMap::new(),
+            tombstones: HashMap::new(),
+            dir_path: dir_path_str.to_string(),
+        };
+
+        /*
+            처음 만들어진 빈 파일이 아닌데 읽을 수 없다면 파일별 버전 정보를 잃지 않도록 격리해두고 빈 저장소로 시작한다.
+            - 버전은 이후 slave 의 manifest 를 점검할 때 slave 가 가진 버전 이상으로 다시 맞춰진다.
+        */
+        let mut hash_storage: HashStorage = if contents.trim().is_empty() {
+            empty_storage
+        } else {
+            match serde_json::from_str(&contents) {
+                Ok(hashes) => hashes,
+                Err(e) => {
+                    let quarantine_path: PathBuf = quarantine_corrupt_file(&dir_path)?;
+
+                    error!(
+                        "[Error][load()] The hash storage file is corrupted and was moved to {:?}. File versions will be rebased from the slave manifests: {:?}",
+                        quarantine_path, e
+                    );
+
+                    empty_storage.save()?;
+                    empty_storage
                 }
             }
         };
@@ -87,25 +123,171 @@ impl HashStorage {
         */
         if dir_path_str != hash_storage.dir_path {
             hash_storage.dir_path = dir_path_str.to_string();
-            let contents = serde_json::to_string(&hash_storage)?;
-            fs::write(dir_path_str, contents)?;
+            hash_storage.save()?;
         }
 
         Ok(hash_storage)
     }
 
+    #[doc = "이전 형식의 해쉬파일을 현재 형식으로 변환해주는 함수"]
+    /// 이전 형식은 해쉬값을 저장한 시점에 모든 slave 에 전달된 것으로 간주했으므로,
+    /// 저장되어 있던 해쉬값을 현재 설정된 모든 slave 가 수신한 것으로 기록해준다.
+    ///
+    /// # Arguments
+    /// * `slave_url` - 현재 설정된 slave 목록
+    pub fn migrate(&mut self, slave_url: &[String]) -> Result<(), anyhow::Error> {
+        if self.format_version >= HASH_STORAGE_FORMAT_VERSION {
+            return Ok(());
+        }
+
+        for (file_name, hash) in &self.hashes {
+            let acked_slaves: &mut HashMap<String, Vec<u8>> =
+                self.acked_hashes.entry(file_name.clone()).or_default();
+
+            for url in slave_url {
+                acked_slaves.insert(url.clone(), hash.clone());
+            }
+        }
+
+        info!(
+            "The hash storage has been migrated from version {} to {}.",
+            self.format_version, HASH_STORAGE_FORMAT_VERSION
+        );
+
+        self.format_version = HASH_STORAGE_FORMAT_VERSION;
+        self.save()
+    }
+
     #[doc = "해쉬파일에 Heap 메모리 상에 존재하는 해쉬값을 저장해주는 함수."]
     pub fn save(&self) -> Result<(), anyhow::Error> {
         let contents = serde_json::to_string(&self)?;
-        fs::write(self.dir_path.clone(), contents)?;
-        Ok(())
+
+        /* 저장 도중 종료되더라도 해쉬파일이 잘리지 않도록 원자적으로 교체한다. */
+        write_file_atomic(Path::new(&self.dir_path), contents.as_bytes())
     }
 
-    #[doc = "해쉬파일에서 해쉬값을 업데이트 해주는 함수"]
+    #[doc = "해쉬파일에서 해쉬값을 업데이트 해주는 함수 - 파일의 버전도 함께 증가한다."]
     pub fn update_hash(&mut self, file_name: String, hash: Vec<u8>) {
+        /* 삭제되었던 파일이 다시 생성된 경우 tombstone 을 제거해준다. */
+        self.tombstones.remove(&file_name);
+
+        let version: u64 = self.get_version(&file_name) + 1;
+        self.versions.insert(file_name.clone(), version);
         self.hashes.insert(file_name, hash);
     }
 
+    #[doc = "파일의 버전을 주어진 버전 이상으로 맞춰주는 함수 - 해쉬값은 변경되지 않는다."]
+    /// 해쉬파일이 초기화된 경우 slave 가 이미 가지고 있는 버전보다 낮은 버전을 보내지 않도록 할 때 사용한다.
+    ///
+    /// # Arguments
+    /// * `file_name`   - 파일 이름
+    /// * `min_version` - 맞춰줄 최소 버전
+    ///
+    /// # Returns
+    /// * bool - 버전이 변경된 경우 true
+    pub fn rebase_version(&mut self, file_name: &str, min_version: u64) -> bool {
+        if self.get_version(file_name) >= min_version {
+            return false;
+        }
+
+        self.versions.insert(file_name.to_string(), min_version);
+        true
+    }
+
+    #[doc = "파일이 삭제되었음을 기록(tombstone)해주는 함수 - 저장된 해쉬값은 제거된다."]
+    /// # Arguments
+    /// * `file_name`   - 삭제된 파일 이름
+    /// * `deleted_at`  - 삭제가 감지된 시각
+    pub fn update_tombstone(&mut self, file_name: String, deleted_at: String) {
+        self.hashes.remove(&file_name);
+        self.tombstones.insert(file_name, deleted_at);
+    }
+
+    #[doc = "이름이 변경된 파일의 해쉬값을 새로운 이름으로 옮겨주는 함수 - 이전 이름은 tombstone 으로 기록된다."]
+    /// slave 별 수신 확인 정보도 함께 옮겨진다. (이름변경 전파에 실패한 경우에는 호출하는 쪽에서 초기화해야 한다.)
+    ///
+    /// # Arguments
+    /// * `from_file_name`  - 변경 전 파일 이름
+    /// * `to_file_name`    - 변경 후 파일 이름
+    /// * `renamed_at`      - 이름변경이 감지된 시각
+    pub fn rename_hash(&mut self, from_file_name: &str, to_file_name: String, renamed_at: String) {
+        let hash: Vec<u8> = self.get_hash(from_file_name);
+        let acked_slaves: HashMap<String, Vec<u8>> =
+            self.acked_hashes.remove(from_file_name).unwrap_or_default();
+
+        /* 새 이름의 버전은 두 이름의 이전 버전보다 커야 slave 에서 거절되지 않는다. */
+        let version: u64 = self
+            .get_version(from_file_name)
+            .max(self.get_version(&to_file_name))
+            + 1;
+
+        self.update_tombstone(from_file_name.to_string(), renamed_at);
+        self.acked_hashes.insert(to_file_name.clone(), acked_slaves);
+        self.update_hash(to_file_name.clone(), hash);
+        self.versions.insert(to_file_name, version);
+    }
+
+    #[doc = "slave 가 해당 해쉬값의 파일을 수신했음을 기록해주는 함수"]
+    pub fn update_acked_hash(&mut self, file_name: String, slave_url: String, hash: Vec<u8>) {
+        self.acked_hashes
+            .entry(file_name)
+            .or_default()
+            .insert(slave_url, hash);
+    }
+
+    #[doc = "slave 의 수신 확인 정보를 제거해주는 함수 - slave 에서 파일이 삭제된 경우"]
+    pub fn remove_acked_hash(&mut self, file_name: &str, slave_url: &str) {
+        if let Some(acked_slaves) = self.acked_hashes.get_mut(file_name) {
+            acked_slaves.remove(slave_url);
+
+            if acked_slaves.is_empty() {
+                self.acked_hashes.remove(file_name);
+            }
+        }
+    }
+
+    #[doc = "파일의 모든 slave 수신 확인 정보를 제거해주는 함수"]
+    pub fn clear_acked_hashes(&mut self, file_name: &str) {
+        self.acked_hashes.remove(file_name);
+    }
+
+    #[doc = "마지막으로 감지한 해쉬값을 아직 수신하지 못한 slave 목록을 조회."]
+    /// # Arguments
+    /// * `file_name` - 조회할 파일 이름
+    /// * `slave_url` - 확인할 slave 목록
+    pub fn get_unacked_slaves(&self, file_name: &str, slave_url: &[String]) -> Vec<String> {
+        let hash: Vec<u8> = self.get_hash(file_name);
+        let acked_slaves: Option<&HashMap<String, Vec<u8>>> = self.acked_hashes.get(file_name);
+
+        slave_url
+            .iter()
+            .filter(|url| {
+                acked_slaves
+                    .and_then(|acked_slaves| acked_slaves.get(url.as_str()))
+                    != Some(&hash)
+            })
+            .cloned()
+            .collect()
+    }
+
+    #[doc = "파일을 가지고 있는 것으로 기록된 slave 가 있는지 확인."]
+    pub fn has_acked_slaves(&self, file_name: &str) -> bool {
+        self.acked_hashes
+            .get(file_name)
+            .map(|acked_slaves| !acked_slaves.is_empty())
+            .unwrap_or(false)
+    }
+
+    #[doc = "해당 파일의 삭제가 이미 기록되어 있는지 확인해주는 함수."]
+    pub fn is_tombstone(&self, file_name: &str) -> bool {
+        self.tombstones.contains_key(file_name)
+    }
+
+    #[doc = "해시 저장소에서 주어진 파일 이름의 버전을 조회. - 기록된 적이 없는 경우 0"]
+    pub fn get_version(&self, file_name: &str) -> u64 {
+        self.versions.get(file_name).copied().unwrap_or_default()
+    }
+
     #[doc = "해시 저장소에서 주어진 파일 이름의 해시 값을 조회."]
     pub fn get_hash(&self, file_name: &str) -> Vec<u8> {
         let hash_val: Vec<u8> = self.hashes.get(file_name).unwrap_or(&Vec::new()).clone();
@@ -113,3 +295,105 @@ impl HashStorage {
         hash_val
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* 테스트마다 비어있는 해쉬파일 디렉토리를 만들어준다. */
+    fn get_test_dir(test_name: &str) -> String {
+        let test_dir: PathBuf = std::env::temp_dir()
+            .join(format!("file_sync_hash_{}_{}", std::process::id(), test_name));
+
+        if test_dir.exists() {
+            fs::remove_dir_all(&test_dir).expect("failed to clear the test directory");
+        }
+
+        test_dir.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn migrate_marks_stored_hashes_as_acked() {
+        let test_dir: String = get_test_dir("migrate");
+        let slave_url: Vec<String> = vec!["10.0.0.1:9001".to_string(), "10.0.0.2:9001".to_string()];
+
+        let mut hash_storage: HashStorage = HashStorage::load(&test_dir).unwrap();
+        hash_storage.format_version = 0;
+        hash_storage.hashes.insert("synonyms.txt".to_string(), vec![1, 2, 3]);
+        hash_storage.migrate(&slave_url).unwrap();
+
+        assert_eq!(hash_storage.format_version, HASH_STORAGE_FORMAT_VERSION);
+        assert!(hash_storage.get_unacked_slaves("synonyms.txt", &slave_url).is_empty());
+
+        /* 변환된 형식이 해쉬파일에도 저장되어야 한다. */
+        let reloaded_storage: HashStorage = HashStorage::load(&test_dir).unwrap();
+        assert_eq!(reloaded_storage.format_version, HASH_STORAGE_FORMAT_VERSION);
+        assert!(reloaded_storage.has_acked_slaves("synonyms.txt"));
+    }
+
+    #[test]
+    fn migrate_skips_current_format() {
+        let test_dir: String = get_test_dir("migrate_current");
+        let slave_url: Vec<String> = vec!["10.0.0.1:9001".to_string()];
+
+        let mut hash_storage: HashStorage = HashStorage::load(&test_dir).unwrap();
+        hash_storage.update_hash("synonyms.txt".to_string(), vec![1, 2, 3]);
+        hash_storage.migrate(&slave_url).unwrap();
+
+        assert_eq!(hash_storage.get_unacked_slaves("synonyms.txt", &slave_url), slave_url);
+    }
+
+    #[test]
+    fn rename_hash_bumps_version_above_both_names() {
+        let test_dir: String = get_test_dir("rename");
+
+        let mut hash_storage: HashStorage = HashStorage::load(&test_dir).unwrap();
+        hash_storage.update_hash("synonyms.txt".to_string(), vec![1, 2, 3]);
+        hash_storage.update_acked_hash("synonyms.txt".to_string(), "10.0.0.1:9001".to_string(), vec![1, 2, 3]);
+        hash_storage.versions.insert("synonyms_new.txt".to_string(), 7);
+
+        hash_storage.rename_hash("synonyms.txt", "synonyms_new.txt".to_string(), "2024-01-01T00:00:00Z".to_string());
+
+        assert_eq!(hash_storage.get_version("synonyms_new.txt"), 8);
+        assert_eq!(hash_storage.get_hash("synonyms_new.txt"), vec![1, 2, 3]);
+        assert!(hash_storage.has_acked_slaves("synonyms_new.txt"));
+        assert!(hash_storage.is_tombstone("synonyms.txt"));
+        assert!(hash_storage.get_hash("synonyms.txt").is_empty());
+    }
+
+    #[test]
+    fn rebase_version_only_raises() {
+        let test_dir: String = get_test_dir("rebase");
+
+        let mut hash_storage: HashStorage = HashStorage::load(&test_dir).unwrap();
+        hash_storage.update_hash("synonyms.txt".to_string(), vec![1, 2, 3]);
+
+        assert!(hash_storage.rebase_version("synonyms.txt", 5));
+        assert_eq!(hash_storage.get_version("synonyms.txt"), 5);
+        assert!(!hash_storage.rebase_version("synonyms.txt", 3));
+        assert_eq!(hash_storage.get_version("synonyms.txt"), 5);
+    }
+
+    #[test]
+    fn load_quarantines_corrupt_file() {
+        let test_dir: String = get_test_dir("corrupt");
+        let hash_file_path: PathBuf = create_dir_and_file(&test_dir, "hash_value.json").unwrap();
+        fs::write(&hash_file_path, "{\"hashes\": {\"synonyms.txt\"").unwrap();
+
+        let hash_storage: HashStorage = HashStorage::load(&test_dir).unwrap();
+
+        assert!(hash_storage.hashes.is_empty());
+        assert_eq!(hash_storage.format_version, HASH_STORAGE_FORMAT_VERSION);
+
+        /* 손상된 파일은 격리되고, 빈 저장소가 새로 저장된다. */
+        let quarantined_files: Vec<String> = fs::read_dir(&test_dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|file_name| file_name.starts_with("hash_value.json.corrupt_"))
+            .collect();
+
+        assert_eq!(quarantined_files.len(), 1);
+        assert!(HashStorage::load(&test_dir).is_ok());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::common::*;
+
+use crate::utils_modules::io_utils::*;
+
+use crate::configs::configs::*;
+
+#[doc = "History Storage를 전역적으로 사용하기 위함."]
+static HISTORY_STORAGE_CLIENT: once_lazy<Arc<Mutex<HistoryStorage>>> =
+    once_lazy::new(initialize_history_storage_clients);
+
+#[doc = "History Storage 를 초기화해주는 함수 - master 가 slave 에 배포한 파일의 버전 이력을 관리한다."]
+pub fn initialize_history_storage_clients() -> Arc<Mutex<HistoryStorage>> {
+    let history_path: String;
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
+            Err(e) => {
+                error!("[Error][initialize_history_storage_clients()] {:?}", e);
+                panic!("{:?}", e)
+            }
+        };
+
+        history_path = server_config
+            .server
+            .history_path()
+            .clone()
+            .unwrap_or_else(|| "history".to_string());
+    }
+
+    let history_storage: HistoryStorage = match HistoryStorage::load(&history_path) {
+        Ok(history_storage) => history_storage,
+        Err(e) => {
+            error!(
+                "[Error][initialize_history_storage_clients()] Cannot Create HistoryStorage: {:?}",
+                e
+            );
+            panic!("{:?}", e)
+        }
+    };
+
+    Arc::new(Mutex::new(history_storage))
+}
+
+#[doc = "History Storage 를 불러와주는 함수"]
+pub fn get_history_storage() -> Arc<Mutex<HistoryStorage>> {
+    let history_storage: &once_lazy<Arc<Mutex<HistoryStorage>>> = &HISTORY_STORAGE_CLIENT;
+    Arc::clone(history_storage)
+}
+
+#[doc = "롤백으로 되돌린 master 파일의 해시값과 만료시각 - 롤백으로 발생한 감시 이벤트를 다시 배포하지 않기 위함."]
+static ROLLBACK_WRITE_MARKS: once_lazy<Mutex<HashMap<String, (String, Instant)>>> =
+    once_lazy::new(|| Mutex::new(HashMap::new()));
+
+#[doc = "롤백으로 되돌릴 master 파일을 기록해주는 함수 - 만료 전까지 같은 내용의 감시 이벤트는 이미 처리된 것으로 본다."]
+/// # Arguments
+/// * `file_path`   - 되돌릴 master 파일의 절대경로
+/// * `hash`        - 되돌린 내용의 해시값 (16진수)
+/// * `valid_for`   - 기록을 유지하는 시간
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn mark_rollback_write(file_path: &str, hash: &str, valid_for: Duration) -> Result<(), anyhow::Error> {
+    let mut rollback_marks: MutexGuard<'_, HashMap<String, (String, Instant)>> = ROLLBACK_WRITE_MARKS
+        .lock()
+        .map_err(|e| anyhow!("[Error][mark_rollback_write()] {:?}", e))?;
+
+    rollback_marks.insert(file_path.to_string(), (hash.to_string(), Instant::now() + valid_for));
+    Ok(())
+}
+
+#[doc = "롤백으로 되돌릴 master 파일의 기록을 제거해주는 함수 - 파일을 되돌리지 못한 경우"]
+/// # Arguments
+/// * `file_path` - 되돌리려던 master 파일의 절대경로
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn unmark_rollback_write(file_path: &str) -> Result<(), anyhow::Error> {
+    let mut rollback_marks: MutexGuard<'_, HashMap<String, (String, Instant)>> = ROLLBACK_WRITE_MARKS
+        .lock()
+        .map_err(|e| anyhow!("[Error][unmark_rollback_write()] {:?}", e))?;
+
+    rollback_marks.remove(file_path);
+    Ok(())
+}
+
+#[doc = "롤백으로 되돌린 master 파일의 해시값을 조회해주는 함수 - 만료된 기록은 제거된다."]
+/// # Arguments
+/// * `file_path` - master 파일의 절대경로
+///
+/// # Returns
+/// * Result<Option<String>, anyhow::Error> - 롤백 중이거나 최근에 롤백된 파일이 아닌 경우 None
+pub fn get_rollback_write_hash(file_path: &str) -> Result<Option<String>, anyhow::Error> {
+    let mut rollback_marks: MutexGuard<'_, HashMap<String, (String, Instant)>> = ROLLBACK_WRITE_MARKS
+        .lock()
+        .map_err(|e| anyhow!("[Error][get_rollback_write_hash()] {:?}", e))?;
+
+    let now: Instant = Instant::now();
+    rollback_marks.retain(|_, (_, expires_at)| *expires_at > now);
+
+    Ok(rollback_marks.get(file_path).map(|(hash, _)| hash.clone()))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct HistoryVersion {
+    pub version: u64,
+    pub hash: String,
+    pub file_name: String,              /* 감시 디렉토리 기준 상대경로 */
+    pub pushed_at: String,
+    pub received_slaves: Vec<String>,   /* 해당 버전의 수신이 확인된 slave 목록 */
+    pub rollback_of: Option<u64>,       /* 롤백으로 배포된 버전인 경우 되돌린 대상 버전 */
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HistoryStorage {
+    pub files: HashMap<String, Vec<HistoryVersion>>, /* master 의 파일 절대경로 -> 오래된 버전부터 */
+    pub dir_path: String,
+}
+
+impl HistoryStorage {
+    #[doc = "배포 이력 파일을 읽어서 로드해주는 함수"]
+    pub fn load(history_map_dir: &str) -> Result<Self, anyhow::Error> {
+        /* 디렉토리와 파일이 존재하는지 확인 */
+        let dir_path: PathBuf = create_dir_and_file(history_map_dir, "history_value.json")?;
+
+        let contents: String = fs::read_to_string(&dir_path)?;
+        let dir_path_str: &str = dir_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][load()]The path cannot be converted into a string."))?;
+
+        let mut history_storage: HistoryStorage = match serde_json::from_str(&contents) {
+            Ok(files) => files,
+            Err(e) => {
+                warn!("[WARN][load()] No data exists in file 'history map': {:?}", e);
+                HistoryStorage {
+                    files: HashMap::new(),
+                    dir_path: dir_path_str.to_string(),
+                }
+            }
+        };
+
+        if dir_path_str != history_storage.dir_path {
+            history_storage.dir_path = dir_path_str.to_string();
+            history_storage.save()?;
+        }
+
+        Ok(history_storage)
+    }
+
+    #[doc = "배포 이력 파일에 Heap 메모리 상에 존재하는 이력을 저장해주는 함수."]
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let contents = serde_json::to_string(&self)?;
+
+        /* 저장 도중 종료되더라도 배포 이력 파일이 잘리지 않도록 원자적으로 교체한다. */
+        write_file_atomic(Path::new(&self.dir_path), contents.as_bytes())
+    }
+
+    #[doc = "해당 파일의 배포 이력을 조회. - 오래된 버전부터"]
+    pub fn get_versions(&self, file_path: &str) -> Vec<HistoryVersion> {
+        self.files.get(file_path).cloned().unwrap_or_default()
+    }
+
+    #[doc = "해당 파일의 마지막 배포 버전을 조회."]
+    pub fn get_latest_version(&self, file_path: &str) -> Option<&HistoryVersion> {
+        self.files.get(file_path).and_then(|versions| versions.last())
+    }
+
+    #[doc = "새로 배포된 버전을 추가해주는 함수 - 보관 개수를 넘는 오래된 버전은 제거되어 반환된다."]
+    /// # Arguments
+    /// * `file_path`       - master 의 파일 절대경로
+    /// * `history_version` - 추가할 버전
+    /// * `max_versions`    - 파일별 최대 보관 개수
+    pub fn push_version(
+        &mut self,
+        file_path: String,
+        history_version: HistoryVersion,
+        max_versions: usize,
+    ) -> Vec<HistoryVersion> {
+        let versions: &mut Vec<HistoryVersion> = self.files.entry(file_path).or_default();
+        versions.push(history_version);
+
+        let overflow: usize = versions.len().saturating_sub(max_versions);
+        versions.drain(..overflow).collect()
+    }
+
+    #[doc = "slave 가 해당 해시값의 버전을 수신했음을 기록해주는 함수"]
+    /// # Returns
+    /// * bool - 기록이 변경된 경우 True
+    pub fn update_received_slave(&mut self, file_path: &str, slave_url: &str, hash: &str) -> bool {
+        let history_version: Option<&mut HistoryVersion> = self
+            .files
+            .get_mut(file_path)
+            .and_then(|versions| versions.iter_mut().rev().find(|version| version.hash == hash));
+
+        match history_version {
+            Some(history_version) if !history_version.received_slaves.iter().any(|url| url == slave_url) => {
+                history_version.received_slaves.push(slave_url.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[doc = "배포 이력 디렉토리 경로를 반환. - 배포된 버전의 파일 내용은 이 디렉토리의 버전 저장소에 보관된다."]
+    pub fn get_history_dir(&self) -> Result<PathBuf, anyhow::Error> {
+        Path::new(&self.dir_path)
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("[Error][get_history_dir()] Invalid path: {}", self.dir_path))
+    }
+}
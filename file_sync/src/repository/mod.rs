@@ -1,3 +1,7 @@
 //pub mod elastic_repository;
 pub mod hash_repository;
+pub mod history_repository;
+pub mod outbox_repository;
+pub mod receive_repository;
+pub mod version_store_repository;
 //pub mod request_repository;
@@ -0,0 +1,176 @@
+use crate::common::*;
+
+use crate::utils_modules::io_utils::*;
+
+use crate::configs::configs::*;
+
+#[doc = "Outbox Storage를 전역적으로 사용하기 위함."]
+static OUTBOX_STORAGE_CLIENT: once_lazy<Arc<Mutex<OutboxStorage>>> =
+    once_lazy::new(initialize_outbox_storage_clients);
+
+#[doc = "Outbox Storage 를 초기화해주는 함수 - master 가 slave 에 전달하지 못한 작업을 관리한다."]
+pub fn initialize_outbox_storage_clients() -> Arc<Mutex<OutboxStorage>> {
+    let outbox_file_path: String;
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
+            Err(e) => {
+                error!("[Error][initialize_outbox_storage_clients()] {:?}", e);
+                panic!("{:?}", e)
+            }
+        };
+
+        outbox_file_path = server_config
+            .server
+            .hash_storage_path()
+            .clone()
+            .unwrap_or_else(|| "hash_storage".to_string());
+    }
+
+    let outbox_storage: OutboxStorage = match OutboxStorage::load(&outbox_file_path) {
+        Ok(outbox_storage) => outbox_storage,
+        Err(e) => {
+            error!(
+                "[Error][initialize_outbox_storage_clients()] Cannot Create OutboxStorage: {:?}",
+                e
+            );
+            panic!("{:?}", e)
+        }
+    };
+
+    Arc::new(Mutex::new(outbox_storage))
+}
+
+#[doc = "Outbox Storage 를 불러와주는 함수"]
+pub fn get_outbox_storage() -> Arc<Mutex<OutboxStorage>> {
+    let outbox_storage: &once_lazy<Arc<Mutex<OutboxStorage>>> = &OUTBOX_STORAGE_CLIENT;
+    Arc::clone(outbox_storage)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Getters)]
+#[getset(get = "pub")]
+pub struct OutboxDelivery {
+    pub operation: String,     /* upload, delete */
+    pub file_path: String,     /* master 의 파일 절대경로 */
+    pub file_name: String,     /* slave 에 전달되는 파일 이름 */
+    pub slave_url: String,
+    pub content_hash: String,  /* 전달하려던 파일 내용의 해시값 (delete 인 경우 빈 값) */
+    pub attempts: u32,
+    pub first_failed_at: u64,  /* UNIX time (초) */
+    pub next_retry_at: u64,    /* UNIX time (초) */
+    pub last_error: String,
+}
+
+impl OutboxDelivery {
+    #[doc = "저장소의 키 - 같은 slave 의 같은 파일에 대해서는 가장 마지막 작업만 유지된다."]
+    pub fn get_key(&self) -> String {
+        get_outbox_key(&self.slave_url, &self.file_name)
+    }
+}
+
+#[doc = "slave 주소와 파일 이름으로 outbox 저장소의 키를 만들어주는 함수"]
+pub fn get_outbox_key(slave_url: &str, file_name: &str) -> String {
+    format!("{}|{}", slave_url, file_name)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OutboxStorage {
+    pub deliveries: HashMap<String, OutboxDelivery>,
+    pub dir_path: String,
+}
+
+impl OutboxStorage {
+    #[doc = "outbox 파일을 읽어서 로드해주는 함수"]
+    pub fn load(outbox_map_dir: &str) -> Result<Self, anyhow::Error> {
+        /* 디렉토리와 파일이 존재하는지 확인 */
+        let dir_path: PathBuf = create_dir_and_file(outbox_map_dir, "outbox_value.json")?;
+
+        let contents: String = fs::read_to_string(&dir_path)?;
+        let dir_path_str: &str = dir_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][load()]The path cannot be converted into a string."))?;
+
+        let empty_storage: OutboxStorage = OutboxStorage {
+            deliveries: HashMap::new(),
+            dir_path: dir_path_str.to_string(),
+        };
+
+        /*
+            처음 만들어진 빈 파일이 아닌데 읽을 수 없다면 전달 대기 작업을 잃지 않도록 격리해두고 빈 outbox 로 시작한다.
+            - 격리된 파일은 운영자가 확인 후 직접 복구할 수 있다.
+        */
+        let mut outbox_storage: OutboxStorage = if contents.trim().is_empty() {
+            empty_storage
+        } else {
+            match serde_json::from_str(&contents) {
+                Ok(deliveries) => deliveries,
+                Err(e) => {
+                    let quarantine_path: PathBuf = quarantine_corrupt_file(&dir_path)?;
+
+                    error!(
+                        "[Error][load()] The outbox file is corrupted and was moved to {:?}. Pending deliveries must be recovered manually: {:?}",
+                        quarantine_path, e
+                    );
+
+                    empty_storage.save()?;
+                    empty_storage
+                }
+            }
+        };
+
+        if dir_path_str != outbox_storage.dir_path {
+            outbox_storage.dir_path = dir_path_str.to_string();
+            outbox_storage.save()?;
+        }
+
+        Ok(outbox_storage)
+    }
+
+    #[doc = "outbox 파일에 Heap 메모리 상에 존재하는 전달 대기 작업을 저장해주는 함수."]
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let contents = serde_json::to_string(&self)?;
+
+        /* 저장 도중 종료되더라도 outbox 파일이 잘리지 않도록 원자적으로 교체한다. */
+        write_file_atomic(Path::new(&self.dir_path), contents.as_bytes())
+    }
+
+    #[doc = "전달 대기 작업을 추가(교체)해주는 함수"]
+    pub fn update_delivery(&mut self, delivery: OutboxDelivery) {
+        self.deliveries.insert(delivery.get_key(), delivery);
+    }
+
+    #[doc = "새로 실패한 작업을 기록해주는 함수 - 이미 대기중인 작업이 있다면 재시도 상태는 유지하고 작업 내용만 교체한다."]
+    /// 대기중인 동안 파일이 계속 변경되더라도 재시도 간격과 최초 실패 시각이 초기화되지 않아야 기한이 지나면 포기할 수 있다.
+    pub fn enqueue_delivery(&mut self, delivery: OutboxDelivery) {
+        let delivery: OutboxDelivery = match self.deliveries.get(&delivery.get_key()) {
+            Some(pending_delivery) => OutboxDelivery {
+                attempts: pending_delivery.attempts,
+                first_failed_at: pending_delivery.first_failed_at,
+                next_retry_at: pending_delivery.next_retry_at,
+                ..delivery
+            },
+            None => delivery,
+        };
+
+        self.update_delivery(delivery);
+    }
+
+    #[doc = "전달 대기 작업을 제거해주는 함수"]
+    pub fn remove_delivery(&mut self, key: &str) -> Option<OutboxDelivery> {
+        self.deliveries.remove(key)
+    }
+
+    #[doc = "주어진 키의 전달 대기 작업을 조회."]
+    pub fn get_delivery(&self, key: &str) -> Option<OutboxDelivery> {
+        self.deliveries.get(key).cloned()
+    }
+
+    #[doc = "재시도 시각이 된 전달 대기 작업 목록을 조회."]
+    pub fn get_due_deliveries(&self, now_secs: u64) -> Vec<OutboxDelivery> {
+        self.deliveries
+            .values()
+            .filter(|delivery| delivery.next_retry_at <= now_secs)
+            .cloned()
+            .collect()
+    }
+}
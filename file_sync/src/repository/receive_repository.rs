@@ -0,0 +1,218 @@
+use crate::common::*;
+
+use crate::utils_modules::io_utils::*;
+
+use crate::configs::configs::*;
+
+#[doc = "수신한 적이 없는 파일을 백업할 때 기록하는 보낸 곳"]
+pub const UNKNOWN_BACKUP_SOURCE: &str = "unknown";
+
+#[doc = "Receive Storage를 전역적으로 사용하기 위함."]
+static RECEIVE_STORAGE_CLIENT: once_lazy<Arc<Mutex<ReceiveStorage>>> =
+    once_lazy::new(initialize_receive_storage_clients);
+
+#[doc = "Receive Storage 를 초기화해주는 함수 - slave 가 master 로부터 수신한 파일 정보를 관리한다."]
+pub fn initialize_receive_storage_clients() -> Arc<Mutex<ReceiveStorage>> {
+    let receive_file_path: String;
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
+            Err(e) => {
+                error!("[Error][initialize_receive_storage_clients()] {:?}", e);
+                panic!("{:?}", e)
+            }
+        };
+
+        /* slave 는 hash_storage_path 가 지정되지 않은 경우가 있으므로 기본 경로를 사용한다. */
+        receive_file_path = server_config
+            .server
+            .hash_storage_path()
+            .clone()
+            .unwrap_or_else(|| "hash_storage".to_string());
+    }
+
+    let receive_storage: ReceiveStorage = match ReceiveStorage::load(&receive_file_path) {
+        Ok(receive_storage) => receive_storage,
+        Err(e) => {
+            error!(
+                "[Error][initialize_receive_storage_clients()] Cannot Create ReceiveStorage: {:?}",
+                e
+            );
+            panic!("{:?}", e)
+        }
+    };
+
+    Arc::new(Mutex::new(receive_storage))
+}
+
+#[doc = "Receive Storage 를 불러와주는 함수"]
+pub fn get_receive_storage() -> Arc<Mutex<ReceiveStorage>> {
+    let receive_storage: &once_lazy<Arc<Mutex<ReceiveStorage>>> = &RECEIVE_STORAGE_CLIENT;
+    Arc::clone(receive_storage)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct ReceiveFileInfo {
+    pub hash: String,
+    pub version: Option<u64>,
+    pub received_at: String,
+    pub from_host: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReceiveStorage {
+    pub files: HashMap<String, ReceiveFileInfo>,
+    pub dir_path: String,
+}
+
+impl ReceiveStorage {
+    #[doc = "수신 정보 파일을 읽어서 로드해주는 함수"]
+    pub fn load(receive_map_dir: &str) -> Result<Self, anyhow::Error> {
+        /* 디렉토리와 파일이 존재하는지 확인 */
+        let dir_path: PathBuf = create_dir_and_file(receive_map_dir, "receive_value.json")?;
+
+        let contents: String = fs::read_to_string(&dir_path)?;
+        let dir_path_str: &str = dir_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][load()]The path cannot be converted into a string."))?;
+
+        let mut receive_storage: ReceiveStorage = match serde_json::from_str(&contents) {
+            Ok(files) => files,
+            Err(e) => {
+                warn!("[WARN][load()] No data exists in file 'receive map': {:?}", e);
+                ReceiveStorage {
+                    files: HashMap::new(),
+                    dir_path: dir_path_str.to_string(),
+                }
+            }
+        };
+
+        if dir_path_str != receive_storage.dir_path {
+            receive_storage.dir_path = dir_path_str.to_string();
+            receive_storage.save()?;
+        }
+
+        Ok(receive_storage)
+    }
+
+    #[doc = "수신 정보 파일에 Heap 메모리 상에 존재하는 수신 정보를 저장해주는 함수."]
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let contents = serde_json::to_string(&self)?;
+
+        /* 저장 도중 종료되더라도 수신 정보 파일이 잘리지 않도록 원자적으로 교체한다. */
+        write_file_atomic(Path::new(&self.dir_path), contents.as_bytes())
+    }
+
+    #[doc = "수신한 파일의 정보를 업데이트 해주는 함수"]
+    pub fn update_file(&mut self, file_name: String, receive_info: ReceiveFileInfo) {
+        self.files.insert(file_name, receive_info);
+    }
+
+    #[doc = "수신 정보에서 파일을 제거해주는 함수"]
+    pub fn remove_file(&mut self, file_name: &str) -> Option<ReceiveFileInfo> {
+        self.files.remove(file_name)
+    }
+
+    #[doc = "파일의 수신 정보와 마지막으로 수신한 사본을 제거해주는 함수"]
+    pub fn remove_receive_info(&mut self, file_name: &str) -> Result<(), anyhow::Error> {
+        if self.remove_file(file_name).is_some() {
+            self.save()?;
+        }
+
+        let last_good_copy_path: PathBuf = self.get_last_good_copy_path(file_name)?;
+
+        if last_good_copy_path.exists() {
+            fs::remove_file(&last_good_copy_path)?;
+        }
+
+        Ok(())
+    }
+
+    #[doc = "파일의 수신 정보와 마지막으로 수신한 사본을 새로운 이름으로 옮겨주는 함수"]
+    pub fn rename_receive_info(&mut self, from_file_name: &str, to_file_name: &str) -> Result<(), anyhow::Error> {
+        if let Some(receive_info) = self.remove_file(from_file_name) {
+            self.update_file(to_file_name.to_string(), receive_info);
+            self.save()?;
+        }
+
+        let from_copy_path: PathBuf = self.get_last_good_copy_path(from_file_name)?;
+
+        if from_copy_path.exists() {
+            let to_copy_path: PathBuf = self.get_last_good_copy_path(to_file_name)?;
+            create_parent_dir(&to_copy_path)?;
+            fs::rename(&from_copy_path, &to_copy_path)?;
+        }
+
+        Ok(())
+    }
+
+    #[doc = "수신한 파일의 버전이 이미 가지고 있는 버전보다 오래된 것인지 확인."]
+    /// 버전 정보가 없는 경우(이전 master 로부터 수신한 경우 등)에는 오래된 것으로 보지 않는다.
+    pub fn is_stale_version(&self, file_name: &str, version: Option<u64>) -> bool {
+        let stored_version: Option<u64> = self
+            .files
+            .get(file_name)
+            .and_then(|info| info.version);
+
+        matches!((stored_version, version), (Some(stored_version), Some(version)) if version < stored_version)
+    }
+
+    #[doc = "마지막으로 수신한 파일의 사본 경로를 반환. - slave 에서 직접 수정된 파일을 되돌릴 때 사용한다."]
+    pub fn get_last_good_copy_path(&self, file_name: &str) -> Result<PathBuf, anyhow::Error> {
+        let receive_dir: &Path = Path::new(&self.dir_path)
+            .parent()
+            .ok_or_else(|| anyhow!("[Error][get_last_good_copy_path()] Invalid path: {}", self.dir_path))?;
+
+        get_sync_target_path(&receive_dir.join("last_good"), file_name)
+    }
+
+    #[doc = "주어진 파일 이름의 수신 정보를 조회."]
+    pub fn get_file(&self, file_name: &str) -> Option<ReceiveFileInfo> {
+        self.files.get(file_name).cloned()
+    }
+
+    #[doc = "백업에 기록할 현재 파일의 버전과 보낸 곳을 조회. - 수신한 적이 없는 파일은 (None, unknown)"]
+    pub fn get_backup_source(&self, file_name: &str) -> (Option<u64>, String) {
+        match self.files.get(file_name) {
+            Some(receive_info) => (receive_info.version, receive_info.from_host.clone()),
+            None => (None, UNKNOWN_BACKUP_SOURCE.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_storage(version: Option<u64>) -> ReceiveStorage {
+        let receive_info: ReceiveFileInfo = ReceiveFileInfo::new(
+            "abcdef".to_string(),
+            version,
+            "2024-01-01T00:00:00Z".to_string(),
+            "10.0.0.1:9000".to_string(),
+        );
+
+        ReceiveStorage {
+            files: HashMap::from([("synonyms.txt".to_string(), receive_info)]),
+            dir_path: String::new(),
+        }
+    }
+
+    #[test]
+    fn older_version_is_stale() {
+        let receive_storage: ReceiveStorage = get_test_storage(Some(5));
+
+        assert!(receive_storage.is_stale_version("synonyms.txt", Some(4)));
+        assert!(!receive_storage.is_stale_version("synonyms.txt", Some(5)));
+        assert!(!receive_storage.is_stale_version("synonyms.txt", Some(6)));
+    }
+
+    #[test]
+    fn missing_version_is_never_stale() {
+        /* 버전을 보내지 않는 이전 master 나, 버전 없이 수신한 파일은 항상 받아들인다. */
+        assert!(!get_test_storage(Some(5)).is_stale_version("synonyms.txt", None));
+        assert!(!get_test_storage(None).is_stale_version("synonyms.txt", Some(1)));
+        assert!(!get_test_storage(Some(5)).is_stale_version("stopwords.txt", Some(1)));
+    }
+}
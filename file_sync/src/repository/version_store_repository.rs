@@ -0,0 +1,237 @@
+use crate::common::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::io_utils::*;
+
+#[doc = "버전 저장소가 위치하는 하위 디렉토리 이름 - 백업/이력 디렉토리 아래에 만들어진다."]
+pub const VERSION_STORE_DIR: &str = "store";
+
+#[doc = "디렉토리별 Version Store를 전역적으로 사용하기 위함."]
+static VERSION_STORE_CLIENTS: once_lazy<Mutex<HashMap<PathBuf, Arc<Mutex<VersionStore>>>>> =
+    once_lazy::new(|| Mutex::new(HashMap::new()));
+
+#[doc = "Version Store 를 불러와주는 함수 - 디렉토리별로 처음 요청될 때 로드된다."]
+/// # Arguments
+/// * `base_dir` - 백업 또는 배포 이력 디렉토리 경로
+///
+/// # Returns
+/// * Result<Arc<Mutex<VersionStore>>, anyhow::Error>
+pub fn get_version_store(base_dir: &Path) -> Result<Arc<Mutex<VersionStore>>, anyhow::Error> {
+    let store_dir: PathBuf = base_dir.join(VERSION_STORE_DIR);
+
+    let mut version_stores: MutexGuard<'_, HashMap<PathBuf, Arc<Mutex<VersionStore>>>> =
+        match VERSION_STORE_CLIENTS.lock() {
+            Ok(version_stores) => version_stores,
+            Err(e) => return Err(anyhow!("[Error][get_version_store()] {:?}", e)),
+        };
+
+    if let Some(version_store) = version_stores.get(&store_dir) {
+        return Ok(Arc::clone(version_store));
+    }
+
+    let version_store: Arc<Mutex<VersionStore>> = Arc::new(Mutex::new(VersionStore::load(&store_dir)?));
+    version_stores.insert(store_dir, Arc::clone(&version_store));
+
+    Ok(version_store)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Getters, new)]
+#[getset(get = "pub")]
+pub struct VersionRecord {
+    pub record_id: String,          /* 파일 안에서의 식별자 - 백업은 백업 시각, 배포 이력은 버전 */
+    pub file_name: String,          /* 감시 디렉토리 기준 상대경로 */
+    pub version: Option<u64>,       /* master 가 부여한 버전 (알 수 없는 경우 None) */
+    pub created_at: String,
+    pub source: String,             /* 해당 내용을 만든 곳 (예: 보낸 호스트, push, rollback) */
+    pub hash: String,               /* 내용의 SHA-256 -> blob 의 키 */
+    pub size: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VersionStore {
+    pub records: Vec<VersionRecord>,
+    pub refcounts: HashMap<String, u64>, /* blob 별로 참조하는 record 수 -> 0 이 되면 blob 을 삭제한다. */
+    pub dir_path: String,
+}
+
+impl VersionStore {
+    #[doc = "버전 저장소의 색인 파일을 읽어서 로드해주는 함수"]
+    pub fn load(store_dir: &Path) -> Result<Self, anyhow::Error> {
+        /* 디렉토리와 파일이 존재하는지 확인 */
+        let dir_path: PathBuf = create_dir_and_file(store_dir, "version_index.json")?;
+
+        let contents: String = fs::read_to_string(&dir_path)?;
+        let dir_path_str: &str = dir_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][load()]The path cannot be converted into a string."))?;
+
+        let mut version_store: VersionStore = match serde_json::from_str(&contents) {
+            Ok(version_store) => version_store,
+            Err(e) => {
+                warn!("[WARN][load()] No data exists in file 'version index': {:?}", e);
+                VersionStore {
+                    records: Vec::new(),
+                    refcounts: HashMap::new(),
+                    dir_path: dir_path_str.to_string(),
+                }
+            }
+        };
+
+        if dir_path_str != version_store.dir_path {
+            version_store.dir_path = dir_path_str.to_string();
+            version_store.save()?;
+        }
+
+        Ok(version_store)
+    }
+
+    #[doc = "색인 파일에 Heap 메모리 상에 존재하는 색인을 저장해주는 함수."]
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let contents = serde_json::to_string(&self)?;
+
+        /* 저장 도중 종료되더라도 버전 저장소 기록 파일이 잘리지 않도록 원자적으로 교체한다. */
+        write_file_atomic(Path::new(&self.dir_path), contents.as_bytes())
+    }
+
+    #[doc = "내용을 blob 으로 저장해주는 함수 - 같은 내용의 blob 이 이미 있다면 다시 저장하지 않는다."]
+    /// 저장한 blob 은 add_record 로 기록해야 참조된다.
+    ///
+    /// # Arguments
+    /// * `file_data`   - 저장할 내용
+    /// * `compression` - 새로 저장하는 blob 의 압축 방식 (none, gzip, zstd)
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - blob 의 키 (내용의 SHA-256)
+    pub fn put_blob(&self, file_data: &[u8], compression: &str) -> Result<String, anyhow::Error> {
+        let hash: String = hash_to_hex(&compute_hash_from_bytes(file_data));
+
+        if self.get_blob_path(&hash)?.is_some() {
+            return Ok(hash);
+        }
+
+        let blob_path: PathBuf = PathBuf::from(format!(
+            "{}{}",
+            self.get_blob_base_path(&hash)?.to_string_lossy(),
+            get_backup_compression_ext(compression)?
+        ));
+
+        /* 저장 도중 실패하더라도 깨진 blob 이 남지 않도록 임시 파일에 먼저 기록한다. */
+        create_parent_dir(&blob_path)?;
+        let staged_blob_path: PathBuf = get_staging_file_path(&blob_path)?;
+
+        let write_res: Result<(), anyhow::Error> =
+            write_compressed_file(file_data, &staged_blob_path, compression)
+                .and_then(|_| Ok(fs::rename(&staged_blob_path, &blob_path)?));
+
+        if write_res.is_err() {
+            let _ = fs::remove_file(&staged_blob_path);
+        }
+
+        write_res.map(|_| hash)
+    }
+
+    #[doc = "blob 을 참조하는 기록을 색인에 추가해주는 함수 - 같은 파일에 같은 식별자의 기록이 이미 있다면 교체된다."]
+    pub fn add_record(&mut self, version_record: VersionRecord) -> Result<(), anyhow::Error> {
+        if self.get_blob_path(version_record.hash())?.is_none() {
+            return Err(anyhow!(
+                "[Error][add_record()] The blob '{}' does not exist.",
+                version_record.hash()
+            ));
+        }
+
+        *self.refcounts.entry(version_record.hash.clone()).or_default() += 1;
+        self.remove_record(&version_record.file_name, &version_record.record_id)?;
+        self.records.push(version_record);
+
+        Ok(())
+    }
+
+    #[doc = "색인에서 기록을 제거해주는 함수 - 더 이상 참조되지 않는 blob 은 삭제된다."]
+    pub fn remove_record(&mut self, file_name: &str, record_id: &str) -> Result<Option<VersionRecord>, anyhow::Error> {
+        let index: usize = match self
+            .records
+            .iter()
+            .position(|record| record.file_name == file_name && record.record_id == record_id)
+        {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        let version_record: VersionRecord = self.records.remove(index);
+        let refcount: u64 = self
+            .refcounts
+            .get(&version_record.hash)
+            .copied()
+            .unwrap_or_default()
+            .saturating_sub(1);
+
+        if refcount == 0 {
+            self.refcounts.remove(&version_record.hash);
+
+            if let Some(blob_path) = self.get_blob_path(&version_record.hash)? {
+                fs::remove_file(&blob_path)?;
+            }
+        } else {
+            self.refcounts.insert(version_record.hash.clone(), refcount);
+        }
+
+        Ok(Some(version_record))
+    }
+
+    #[doc = "해당 파일의 기록 목록을 조회."]
+    pub fn get_records(&self, file_name: &str) -> Vec<VersionRecord> {
+        self.records
+            .iter()
+            .filter(|record| record.file_name == file_name)
+            .cloned()
+            .collect()
+    }
+
+    #[doc = "해당 파일의 특정 기록을 조회."]
+    pub fn get_record(&self, file_name: &str, record_id: &str) -> Option<VersionRecord> {
+        self.records
+            .iter()
+            .find(|record| record.file_name == file_name && record.record_id == record_id)
+            .cloned()
+    }
+
+    #[doc = "blob 의 원래 내용을 읽어주는 함수"]
+    pub fn read_blob(&self, hash: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let blob_path: PathBuf = self
+            .get_blob_path(hash)?
+            .ok_or_else(|| anyhow!("[Error][read_blob()] The blob '{}' does not exist.", hash))?;
+
+        read_backup_file(&blob_path)
+    }
+
+    #[doc = "blob 이 디스크에서 차지하는 크기를 조회. - 존재하지 않는 경우 0"]
+    pub fn get_blob_size(&self, hash: &str) -> Result<u64, anyhow::Error> {
+        match self.get_blob_path(hash)? {
+            Some(blob_path) => Ok(fs::metadata(blob_path)?.len()),
+            None => Ok(0),
+        }
+    }
+
+    #[doc = "저장되어 있는 blob 의 경로를 조회. - 압축 방식에 따라 확장자가 다르다."]
+    fn get_blob_path(&self, hash: &str) -> Result<Option<PathBuf>, anyhow::Error> {
+        let blob_base_path: String = self.get_blob_base_path(hash)?.to_string_lossy().to_string();
+
+        Ok(["", ".gz", ".zst"]
+            .iter()
+            .map(|ext| PathBuf::from(format!("{}{}", blob_base_path, ext)))
+            .find(|blob_path| blob_path.is_file()))
+    }
+
+    #[doc = "blob 경로 (압축 확장자 제외) - `저장소/blobs/해시 앞 두자리/해시`"]
+    fn get_blob_base_path(&self, hash: &str) -> Result<PathBuf, anyhow::Error> {
+        if hash.len() < 2 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("[Error][get_blob_base_path()] Invalid hash: {}", hash));
+        }
+
+        let store_dir: &Path = Path::new(&self.dir_path)
+            .parent()
+            .ok_or_else(|| anyhow!("[Error][get_blob_base_path()] Invalid path: {}", self.dir_path))?;
+
+        Ok(store_dir.join("blobs").join(&hash[..2]).join(hash))
+    }
+}
@@ -1,12 +1,35 @@
 use crate::common::*;
 
+use crate::model::analyzer_reload_result::*;
+use crate::model::backup_info::*;
+use crate::model::elastic_msg::*;
 use crate::model::file_info::*;
+use crate::model::file_meta_info::*;
+use crate::model::hook_result::*;
+use crate::model::manifest_info::*;
+use crate::model::relay_result::*;
+use crate::model::transfer_info::*;
 
 use crate::configs::configs::*;
 
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::io_utils::*;
+use crate::utils_modules::logger_utils::*;
+
 use crate::service::file_service_impl::*;
+use crate::service::request_service_impl::*;
+
+use crate::handler::analyzer_reload_handler::*;
+use crate::handler::relay_handler::*;
+use crate::handler::sync_hook_handler::*;
+
+use crate::traits::service::{backup_service::*, file_service::*, receive_service::*};
 
-use crate::traits::service::file_service::*;
+/* relay 인 경우에만 등록되는 relay handler */
+type RelayHandlerData = Option<web::Data<Arc<RelayHandler<RequestServiceImpl, FileServiceImpl>>>>;
+
+/* slave 로컬 Elasticsearch 가 설정된 경우에만 등록되는 analyzer reload handler */
+type AnalyzerReloadHandlerData = Option<web::Data<Arc<AnalyzerReloadHandler<RequestServiceImpl>>>>;
 
 #[derive(Debug, new)]
 pub struct AppRouter;
@@ -17,6 +40,12 @@ impl AppRouter {
     /// * `cfg` - 웹 서비스 컨피그 객체 : 새로운 서비스를 호스팅할 수 있다.
     pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         cfg.service(web::resource("/upload").route(web::post().to(download_handler)));
+        cfg.service(web::resource("/delete").route(web::post().to(delete_handler)));
+        cfg.service(web::resource("/rename").route(web::post().to(rename_handler)));
+        cfg.service(web::resource("/manifest").route(web::get().to(manifest_handler)));
+        cfg.service(web::resource("/backups").route(web::get().to(backup_list_handler)));
+        cfg.service(web::resource("/backup").route(web::get().to(backup_file_handler)));
+        cfg.service(web::resource("/backup/restore").route(web::post().to(backup_restore_handler)));
 
         /* 새 라우트 추가는 아래와 같이 수행하면 된다. */
         // cfg.service(
@@ -28,23 +57,26 @@ impl AppRouter {
 
 #[doc = "파일 업로드 핸들러 - master 쪽에서 수정된 파일을 넘겨주는데 해당 정보를 가지고 slave 의 파일을 최신화 해주는 함수"]
 /// # Arguments
+/// * `http_req`        - Http 요청 정보 (요청을 보낸 master 의 주소 확인용)
 /// * `req`             - Request 객체 Http 통신을 통해서 넘어온 쿼리의 결과.
 /// * `payload`         - 파일 데이터 스트림을 청크방식으로 보내줌. -> 파일 데이터.
 /// * `file_service`    - file 관련 서비스 인스턴스
-/// * `request_service` - request 관련 서비스 인스턴스
+/// * `relay_handler`   - relay 인 경우 하위 slave 에 파일을 다시 전달해주는 인스턴스
+/// * `analyzer_reload_handler` - 반영한 파일을 사전으로 사용하는 검색 분석기를 리로드해주는 인스턴스
 ///
 /// # Return
 /// * Result<HttpResponse, Error>
 async fn download_handler(
+    http_req: HttpRequest,
     req: web::Query<FileInfo>,
     mut payload: web::Payload,
-    file_service: web::Data<Arc<FileServiceImpl>>
+    file_service: web::Data<Arc<FileServiceImpl>>,
+    relay_handler: RelayHandlerData,
+    analyzer_reload_handler: AnalyzerReloadHandlerData,
 ) -> Result<HttpResponse, Error> {
     info!("Receive a file modification signal from the master server");
 
-    let slave_backup_path: String; /* 백업파일 경로 */
     let watch_path_string: String; /* 감시대상 파일 경로 */
-    //let from_host: String; /* 호스트 정보 */
     {
         let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
             Ok(server_config) => server_config,
@@ -55,14 +87,6 @@ async fn download_handler(
         };
 
         watch_path_string = server_config.server.watch_path().clone();
-
-        slave_backup_path = server_config
-            .server
-            .slave_backup_path()
-            .clone()
-            .unwrap_or_default();
-
-        //from_host = server_config.server.host().clone();
     }
 
     /* 수정된 파일의 이름 */
@@ -71,8 +95,14 @@ async fn download_handler(
     /* 감시대상 파일 경로 */
     let watch_path: &Path = Path::new(watch_path_string.as_str());
 
-    /* 수정된 파일 실제 경로 */
-    let modified_file_path: PathBuf = watch_path.join(&modified_file_name);
+    /* 수정된 파일 실제 경로 -> 감시 디렉토리를 벗어나는 경로는 허용하지 않는다. */
+    let modified_file_path: PathBuf = match get_sync_target_path(watch_path, &modified_file_name) {
+        Ok(modified_file_path) => modified_file_path,
+        Err(e) => {
+            error!("[Error][upload_handler()] {:?}", e);
+            return Err(actix_web::error::ErrorBadRequest(e));
+        }
+    };
     
     /*
         수정된 파일 실제 경로 문자열 변환
@@ -89,41 +119,693 @@ async fn download_handler(
         }
     };  
 
-    /* 수정파일이 기존 slave 에도 존재하는 파일일 경우에 백업시작 -> 기존에 존재하지 않는 경우에는 백업 불필요 */
-    if modified_file_path.exists() {
-        /* 파일 백업 시작 */
-        match file_service.copy_file_for_backup(
-            modified_file_path.clone(),
-            &slave_backup_path,
+    /* master 가 보낸 파일의 버전 -> 이미 더 최신 버전을 가지고 있다면 수신하지 않는다. */
+    let file_version: Option<u64> = http_req
+        .headers()
+        .get(FILE_VERSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    match file_service.is_stale_receive_version(&modified_file_name, file_version) {
+        Ok(true) => {
+            warn!(
+                "[Warn][upload_handler()] Rejected stale version {:?} of '{}'.",
+                file_version, modified_file_name
+            );
+            return Ok(HttpResponse::Conflict().body("A newer version of the file already exists"));
+        }
+        Ok(false) => (),
+        Err(e) => {
+            error!("[Error][upload_handler()] {:?}", e);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    }
+
+    /* 수신한 데이터는 임시 파일에 먼저 기록한다. -> 수신 도중 실패해도 기존 파일은 그대로 유지된다. */
+    let staged_file_path: PathBuf = match create_parent_dir(&modified_file_path)
+        .and_then(|_| get_staging_file_path(&modified_file_path))
+    {
+        Ok(staged_file_path) => staged_file_path,
+        Err(e) => {
+            error!("[Error][upload_handler()] {:?}", e);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    };
+
+    let received_hash: String = match write_payload_to_staged_file(&mut payload, &staged_file_path).await {
+        Ok(received_hash) => received_hash,
+        Err(e) => {
+            error!("[Error][upload_handler()] Failed to receive file '{}' : {:?}", modified_file_name, e);
+            let _ = fs::remove_file(&staged_file_path);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    };
+
+    /* master 가 보낸 해시값과 실제로 수신한 내용의 해시값이 다르면 반영하지 않는다. */
+    let expected_hash: Option<&str> = http_req
+        .headers()
+        .get(CONTENT_SHA256_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    if let Some(expected_hash) = expected_hash {
+        if !expected_hash.eq_ignore_ascii_case(&received_hash) {
+            let err_msg: String = format!(
+                "[Error][upload_handler()] Content digest mismatch for '{}'. expected: {} // received: {}",
+                modified_file_name, expected_hash, received_hash
+            );
+            error!("{}", err_msg);
+            let _ = fs::remove_file(&staged_file_path);
+            return Err(actix_web::error::ErrorBadRequest(err_msg));
+        }
+    }
+
+    let from_host: String = http_req
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default();
+
+    /* 파일에 설정된 동기화 훅을 거쳐 기존 파일 백업 후 임시 파일로 교체하고, master 파일의 권한, 소유자, 수정시각을 적용해준다. */
+    let sync_hook_handler: SyncHookHandler<FileServiceImpl> = SyncHookHandler::new(Arc::clone(file_service.get_ref()));
+
+    let hook_results: Vec<HookResult> = match sync_hook_handler
+        .commit_with_sync_hooks(
+            &staged_file_path,
+            &modified_file_path,
             &modified_file_name,
-        ) {
-            Ok(_) => (),
+            &from_host,
+            file_version,
+            Some(&FileMetaInfo::from_headers(http_req.headers())),
+        )
+        .await
+    {
+        Ok(SyncWriteResult::Committed(hook_results)) => hook_results,
+        Ok(SyncWriteResult::Stale) => {
+            /* 수신하는 동안 더 최신 버전이 먼저 반영된 경우 */
+            warn!(
+                "[Warn][upload_handler()] Rejected stale version {:?} of '{}'.",
+                file_version, modified_file_name
+            );
+            return Ok(HttpResponse::Conflict().body("A newer version of the file already exists"));
+        }
+        Ok(SyncWriteResult::Rejected(hook_result)) => {
+            return Ok(HttpResponse::UnprocessableEntity().body(hook_result.get_report()));
+        }
+        Ok(SyncWriteResult::Reverted(hook_result, restore_detail)) => {
+            /* post-write 훅이 실패하여 되돌린 파일은 하위 slave 에 전달하지 않는다. */
+            return Ok(HttpResponse::InternalServerError()
+                .body(format!("{}\n{}", hook_result.get_report(), restore_detail)));
+        }
+        Err(e) => {
+            error!("[Error][upload_handler()] Failed to replace file '{}' : {:?}", modified_file_name, e);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    };
+
+    info!(
+        "The file '{:?}' has been changed. version: {:?}",
+        modified_file_path_str, file_version
+    );
+
+    /* 파일을 사전으로 사용하는 인덱스의 검색 분석기를 리로드하고, 결과는 master 에 응답한다. */
+    let analyzer_reload_report: Option<AnalyzerReloadReport> =
+        reload_file_analyzers(&analyzer_reload_handler, &modified_file_name).await;
+
+    /* relay 인 경우 하위 slave 들에게 전달한 결과까지 확인한 뒤 응답한다. */
+    let relay_result: Option<RelayResult> = match relay_handler {
+        Some(relay_handler) => Some(
+            relay_handler
+                .relay_file(
+                    modified_file_path_str,
+                    &modified_file_name,
+                    &received_hash,
+                    file_version.unwrap_or_default(),
+                )
+                .await
+                .map_err(|e| {
+                    error!("[Error][upload_handler()] {:?}", e);
+                    actix_web::error::ErrorInternalServerError(e)
+                })?,
+        ),
+        None => None,
+    };
+
+    /* 실행된 훅의 출력과 검색 분석기 리로드 결과도 응답에 담는다. */
+    let mut upload_message: String = "File uploaded successfully".to_string();
+    for hook_result in &hook_results {
+        upload_message.push_str(&format!("\n{}", hook_result.get_report()));
+    }
+
+    let mut response: HttpResponseBuilder = get_relay_response_builder(relay_result.as_ref());
+
+    if let Some(analyzer_reload_report) = &analyzer_reload_report {
+        insert_analyzer_reload_report(&mut response, &mut upload_message, analyzer_reload_report);
+    }
+
+    /* 검증된 해시값을 응답에 담아 master 가 동기화 여부를 확인할 수 있도록 한다. */
+    Ok(response
+        .insert_header((CONTENT_SHA256_HEADER, received_hash))
+        .body(get_relay_response_body(relay_result.as_ref(), &upload_message)))
+}
+
+#[doc = "반영한 파일을 사전으로 사용하는 인덱스의 검색 분석기를 리로드해주는 함수"]
+/// 파일은 이미 반영되었으므로 리로드 중 에러가 발생하더라도 에러만 남긴다.
+///
+/// # Arguments
+/// * `analyzer_reload_handler` - slave 로컬 Elasticsearch 가 설정되지 않은 경우 None
+/// * `file_name`               - 반영한 파일의 이름 (감시 디렉토리 기준 상대경로)
+///
+/// # Return
+/// * Option<AnalyzerReloadReport> - 리로드하지 않은 경우 None
+async fn reload_file_analyzers(
+    analyzer_reload_handler: &AnalyzerReloadHandlerData,
+    file_name: &str,
+) -> Option<AnalyzerReloadReport> {
+    match analyzer_reload_handler.as_ref()?.reload_file_analyzers(file_name).await {
+        Ok(analyzer_reload_report) => analyzer_reload_report,
+        Err(e) => {
+            error!("[Error][reload_file_analyzers()] {:?}", e);
+            None
+        }
+    }
+}
+
+#[doc = "relay 결과에 맞는 응답 빌더를 만들어주는 함수 - 하위 slave 중 하나라도 실패하면 상위 서버가 재시도하도록 502 로 응답한다."]
+/// # Arguments
+/// * `relay_result` - 하위 slave 에 전달한 결과 (relay 가 아닌 경우 None)
+///
+/// # Return
+/// * HttpResponseBuilder
+fn get_relay_response_builder(relay_result: Option<&RelayResult>) -> HttpResponseBuilder {
+    match relay_result {
+        Some(relay_result) => {
+            let mut response: HttpResponseBuilder = if relay_result.is_success() {
+                HttpResponse::Ok()
+            } else {
+                HttpResponse::BadGateway()
+            };
+
+            response.insert_header((RELAY_RESULT_HEADER, relay_result.get_summary()));
+            response
+        }
+        None => HttpResponse::Ok(),
+    }
+}
+
+#[doc = "relay 결과를 응답 본문으로 만들어주는 함수"]
+/// # Arguments
+/// * `relay_result` - 하위 slave 에 전달한 결과 (relay 가 아닌 경우 None)
+/// * `message`      - 작업이 성공한 경우의 응답 메시지
+///
+/// # Return
+/// * String
+fn get_relay_response_body(relay_result: Option<&RelayResult>, message: &str) -> String {
+    match relay_result {
+        Some(relay_result) if !relay_result.is_success() => format!(
+            "{} on the relay '{}', but failed to relay to {:?} ({})",
+            message,
+            relay_result.relay_host(),
+            relay_result.failed_slaves(),
+            relay_result.get_summary()
+        ),
+        Some(relay_result) => format!("{} ({})", message, relay_result.get_summary()),
+        None => message.to_string(),
+    }
+}
+
+#[doc = "요청 스트림의 데이터를 임시 파일에 기록하고 디스크에 동기화(fsync)해주는 함수"]
+/// 스트림 오류나 쓰기 오류가 발생하면 즉시 에러를 반환한다.
+///
+/// # Arguments
+/// * `payload`             - 파일 데이터 스트림
+/// * `staged_file_path`    - 임시 파일 경로
+///
+/// # Return
+/// * Result<String, anyhow::Error> - 기록한 내용의 SHA-256 값 (16진수)
+async fn write_payload_to_staged_file(
+    payload: &mut web::Payload,
+    staged_file_path: &Path,
+) -> Result<String, anyhow::Error> {
+    let mut staged_file: tokio::fs::File = tokio::fs::File::create(staged_file_path).await?;
+    let mut hasher = Sha256::new();
+
+    while let Some(chunk) = payload.try_next().await? {
+        hasher.update(&chunk);
+        staged_file.write_all(&chunk).await?;
+    }
+
+    staged_file.flush().await?;
+    staged_file.sync_all().await?;
+
+    Ok(hash_to_hex(&hasher.finalize()))
+}
+
+#[doc = "파일 삭제 핸들러 - master 쪽에서 삭제된 파일 정보를 넘겨주는데 해당 정보를 가지고 slave 의 파일을 백업 후 제거해주는 함수"]
+/// 삭제는 파일을 쓰지 않으므로 동기화 훅과 검색 분석기 리로드는 실행하지 않는다.
+///
+/// # Arguments
+/// * `req`             - Request 객체 Http 통신을 통해서 넘어온 쿼리의 결과.
+/// * `file_service`    - file 관련 서비스 인스턴스
+/// * `relay_handler`   - relay 인 경우 하위 slave 에 삭제를 다시 전달해주는 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn delete_handler(
+    req: web::Query<FileInfo>,
+    file_service: web::Data<Arc<FileServiceImpl>>,
+    relay_handler: RelayHandlerData,
+) -> Result<HttpResponse, Error> {
+    info!("Receive a file deletion signal from the master server");
+
+    let slave_backup_path: String; /* 백업파일 경로 */
+    let watch_path_string: String; /* 감시대상 파일 경로 */
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
             Err(e) => {
-                error!("[Error][upload_handler()] File backup Failed : {:?}", e);
+                error!("[Error][delete_handler()] {:?}", e);
                 return Err(actix_web::error::ErrorInternalServerError(e));
             }
+        };
+
+        watch_path_string = server_config.server.watch_path().clone();
+
+        slave_backup_path = server_config
+            .server
+            .slave_backup_path()
+            .clone()
+            .unwrap_or_default();
+    }
+
+    /* 삭제된 파일의 이름 */
+    let deleted_file_name: String = req.filename.clone();
+
+    /* 삭제된 파일 실제 경로 */
+    let deleted_file_path: PathBuf =
+        match get_sync_target_path(Path::new(watch_path_string.as_str()), &deleted_file_name) {
+            Ok(deleted_file_path) => deleted_file_path,
+            Err(e) => {
+                error!("[Error][delete_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorBadRequest(e));
+            }
+        };
+
+    /* 백업 후 파일 삭제 -> 파일이 이미 존재하지 않는 경우에도 성공으로 처리한다. */
+    match file_service.delete_file_with_backup(
+        deleted_file_path.clone(),
+        &slave_backup_path,
+        &deleted_file_name,
+    ) {
+        Ok(deleted_yn) => {
+            let message: &str = if deleted_yn {
+                info!("The file '{:?}' has been deleted.", deleted_file_path);
+                "File deleted successfully"
+            } else {
+                "File already deleted"
+            };
+
+            /* relay 인 경우 relay 에 이미 파일이 없더라도 하위 slave 에는 삭제를 전달한다. */
+            let relay_result: Option<RelayResult> = match relay_handler {
+                Some(relay_handler) => Some(
+                    relay_handler
+                        .relay_delete(&deleted_file_path.to_string_lossy(), &deleted_file_name)
+                        .await
+                        .map_err(|e| {
+                            error!("[Error][delete_handler()] {:?}", e);
+                            actix_web::error::ErrorInternalServerError(e)
+                        })?,
+                ),
+                None => None,
+            };
+
+            Ok(get_relay_response_builder(relay_result.as_ref())
+                .body(get_relay_response_body(relay_result.as_ref(), message)))
+        }
+        Err(e) => {
+            error!("[Error][delete_handler()] File delete Failed : {:?}", e);
+            Err(actix_web::error::ErrorInternalServerError(e))
         }
     }
+}
 
-    /* 전송된 파일로 기존 파일 덮어쓰기 */
-    let mut chg_file: File = match File::create(modified_file_path) {
-        Ok(chg_file) => chg_file,
+#[doc = "파일 이름변경 핸들러 - master 쪽에서 이름이 변경된 파일 정보를 넘겨주는데 해당 정보를 가지고 slave 의 파일 이름을 변경해주는 함수"]
+/// 새 이름에 동기화 훅이 설정된 경우 훅을 거쳐 반영하고, 새 이름을 사전으로 사용하는 검색 분석기도 리로드한다.
+///
+/// # Arguments
+/// * `http_req`        - Http 요청 정보 (요청을 보낸 master 의 주소 확인용)
+/// * `req`             - Request 객체 Http 통신을 통해서 넘어온 쿼리의 결과.
+/// * `file_service`    - file 관련 서비스 인스턴스
+/// * `relay_handler`   - relay 인 경우 하위 slave 에 이름변경을 다시 전달해주는 인스턴스
+/// * `analyzer_reload_handler` - 새 이름을 사전으로 사용하는 검색 분석기를 리로드해주는 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn rename_handler(
+    http_req: HttpRequest,
+    req: web::Query<RenameInfo>,
+    file_service: web::Data<Arc<FileServiceImpl>>,
+    relay_handler: RelayHandlerData,
+    analyzer_reload_handler: AnalyzerReloadHandlerData,
+) -> Result<HttpResponse, Error> {
+    info!("Receive a file rename signal from the master server");
+
+    let watch_path_string: String; /* 감시대상 파일 경로 */
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
+            Err(e) => {
+                error!("[Error][rename_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorInternalServerError(e));
+            }
+        };
+
+        watch_path_string = server_config.server.watch_path().clone();
+    }
+
+    let watch_path: &Path = Path::new(watch_path_string.as_str());
+    let (from_file_path, to_file_path) = match (
+        get_sync_target_path(watch_path, &req.from_filename),
+        get_sync_target_path(watch_path, &req.to_filename),
+    ) {
+        (Ok(from_file_path), Ok(to_file_path)) => (from_file_path, to_file_path),
+        (Err(e), _) | (_, Err(e)) => {
+            error!("[Error][rename_handler()] {:?}", e);
+            return Err(actix_web::error::ErrorBadRequest(e));
+        }
+    };
+
+    /* slave 에 이전 파일이 존재하지 않는 경우 master 가 파일 전체를 다시 보내도록 404 로 응답한다. */
+    if !from_file_path.exists() {
+        error!(
+            "[Error][rename_handler()] The file '{:?}' does not exist.",
+            from_file_path
+        );
+        return Ok(HttpResponse::NotFound().body("Source file not found"));
+    }
+
+    let from_host: String = http_req
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default();
+
+    /* 새 이름에 동기화 훅이 설정된 경우 이전 파일의 내용을 새 이름으로 쓰는 것과 같으므로 훅을 거쳐 반영한다. */
+    let sync_hook_handler: SyncHookHandler<FileServiceImpl> = SyncHookHandler::new(Arc::clone(file_service.get_ref()));
+
+    let hook_results: Vec<HookResult> = match sync_hook_handler
+        .rename_with_sync_hooks(
+            &from_file_path,
+            &to_file_path,
+            &req.from_filename,
+            &req.to_filename,
+            &from_host,
+        )
+        .await
+    {
+        Ok(SyncWriteResult::Committed(hook_results)) => hook_results,
+        Ok(SyncWriteResult::Stale) => {
+            warn!(
+                "[Warn][rename_handler()] A newer version of '{}' already exists.",
+                req.to_filename
+            );
+            return Ok(HttpResponse::Conflict().body("A newer version of the file already exists"));
+        }
+        Ok(SyncWriteResult::Rejected(hook_result)) => {
+            return Ok(HttpResponse::UnprocessableEntity().body(hook_result.get_report()));
+        }
+        Ok(SyncWriteResult::Reverted(hook_result, restore_detail)) => {
+            return Ok(HttpResponse::InternalServerError()
+                .body(format!("{}\n{}", hook_result.get_report(), restore_detail)));
+        }
         Err(e) => {
-            error!("[Error][upload_handler()] {:?}", e);
+            error!("[Error][rename_handler()] File rename Failed : {:?}", e);
             return Err(actix_web::error::ErrorInternalServerError(e));
         }
     };
 
-    /* 스트림에서 데이터를 읽고 파일에 쓴다. */
-    while let Ok(Some(chunk)) = payload.try_next().await {
-        let data = chunk;
-        let _ = chg_file.write_all(&data);
+    /* 새 이름을 사전으로 사용하는 인덱스의 검색 분석기를 리로드하고, 결과는 master 에 응답한다. */
+    let analyzer_reload_report: Option<AnalyzerReloadReport> =
+        reload_file_analyzers(&analyzer_reload_handler, &req.to_filename).await;
+
+    let relay_result: Option<RelayResult> = match relay_handler {
+        Some(relay_handler) => Some(
+            relay_handler
+                .relay_rename(
+                    &to_file_path.to_string_lossy(),
+                    &req.from_filename,
+                    &req.to_filename,
+                )
+                .await
+                .map_err(|e| {
+                    error!("[Error][rename_handler()] {:?}", e);
+                    actix_web::error::ErrorInternalServerError(e)
+                })?,
+        ),
+        None => None,
+    };
+
+    let mut rename_message: String = "File renamed successfully".to_string();
+    for hook_result in &hook_results {
+        rename_message.push_str(&format!("\n{}", hook_result.get_report()));
     }
 
-    info!(
-        "The file '{:?}' has been changed.",
-        modified_file_path_str
-    );
+    let mut response: HttpResponseBuilder = get_relay_response_builder(relay_result.as_ref());
+
+    if let Some(analyzer_reload_report) = &analyzer_reload_report {
+        insert_analyzer_reload_report(&mut response, &mut rename_message, analyzer_reload_report);
+    }
+
+    Ok(response.body(get_relay_response_body(relay_result.as_ref(), &rename_message)))
+}
+
+#[doc = "manifest 핸들러 - slave 의 감시 디렉토리 하위에 존재하는 파일과 해시값 목록을 반환해주는 함수"]
+/// # Arguments
+/// * `file_service`    - file 관련 서비스 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn manifest_handler(
+    file_service: web::Data<Arc<FileServiceImpl>>,
+) -> Result<HttpResponse, Error> {
+    let watch_path_string: String; /* 감시대상 파일 경로 */
+    let slave_host: String; /* 호스트 정보 */
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
+            Err(e) => {
+                error!("[Error][manifest_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorInternalServerError(e));
+            }
+        };
+
+        watch_path_string = server_config.server.watch_path().clone();
+        slave_host = server_config.server.host().clone();
+    }
+
+    match file_service.get_manifest_files(Path::new(watch_path_string.as_str())) {
+        Ok(manifest_files) => Ok(HttpResponse::Ok().json(ManifestInfo::new(slave_host, manifest_files))),
+        Err(e) => {
+            error!("[Error][manifest_handler()] {:?}", e);
+            Err(actix_web::error::ErrorInternalServerError(e))
+        }
+    }
+}
+
+#[doc = "백업 관련 요청에 필요한 설정값(감시 디렉토리, 백업 디렉토리, 호스트)을 가져와주는 함수"]
+/// # Arguments
+/// * `handler_name` - 에러 로그에 남길 핸들러 이름
+///
+/// # Returns
+/// * Result<(String, String, String), Error>
+fn get_backup_configs(handler_name: &str) -> Result<(String, String, String), Error> {
+    let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+        Ok(server_config) => server_config,
+        Err(e) => {
+            error!("[Error][{}()] {:?}", handler_name, e);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    };
+
+    let slave_backup_path: String = match server_config.server.slave_backup_path() {
+        Some(slave_backup_path) => slave_backup_path.clone(),
+        None => {
+            return Err(actix_web::error::ErrorNotFound(
+                "Backups are not configured on this server",
+            ))
+        }
+    };
+
+    Ok((
+        server_config.server.watch_path().clone(),
+        slave_backup_path,
+        server_config.server.host().clone(),
+    ))
+}
+
+#[doc = "특정 파일의 백업 목록(백업 시각, 해시값, 크기)을 반환해주는 핸들러"]
+/// # Arguments
+/// * `req`             - 조회할 파일의 이름
+/// * `file_service`    - file 관련 서비스 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn backup_list_handler(
+    req: web::Query<FileInfo>,
+    file_service: web::Data<Arc<FileServiceImpl>>,
+) -> Result<HttpResponse, Error> {
+    let (watch_path_string, slave_backup_path, slave_host): (String, String, String) =
+        get_backup_configs("backup_list_handler")?;
+
+    /* 감시 디렉토리를 벗어나는 파일이름은 허용하지 않는다. */
+    if let Err(e) = get_sync_target_path(Path::new(&watch_path_string), &req.filename) {
+        error!("[Error][backup_list_handler()] {:?}", e);
+        return Err(actix_web::error::ErrorBadRequest(e));
+    }
+
+    match file_service.get_backup_file_list(&slave_backup_path, &req.filename) {
+        Ok(backups) => Ok(HttpResponse::Ok().json(BackupListInfo::new(
+            slave_host,
+            req.filename.clone(),
+            backups,
+        ))),
+        Err(e) => {
+            error!("[Error][backup_list_handler()] {:?}", e);
+            Err(actix_web::error::ErrorInternalServerError(e))
+        }
+    }
+}
+
+#[doc = "특정 백업 파일의 내용을 반환해주는 핸들러"]
+/// # Arguments
+/// * `req`             - 조회할 파일의 이름과 백업 시각
+/// * `file_service`    - file 관련 서비스 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn backup_file_handler(
+    req: web::Query<BackupFileQuery>,
+    file_service: web::Data<Arc<FileServiceImpl>>,
+) -> Result<HttpResponse, Error> {
+    let (watch_path_string, slave_backup_path, _slave_host): (String, String, String) =
+        get_backup_configs("backup_file_handler")?;
+
+    if let Err(e) = get_sync_target_path(Path::new(&watch_path_string), &req.filename) {
+        error!("[Error][backup_file_handler()] {:?}", e);
+        return Err(actix_web::error::ErrorBadRequest(e));
+    }
+
+    match file_service.get_backup_file_data(&slave_backup_path, &req.filename, &req.backup_id) {
+        Ok(Some(file_data)) => Ok(HttpResponse::Ok()
+            .content_type("application/octet-stream")
+            .insert_header((CONTENT_SHA256_HEADER, hash_to_hex(&compute_hash_from_bytes(&file_data))))
+            .body(file_data)),
+        Ok(None) => Ok(HttpResponse::NotFound().body("The backup does not exist")),
+        Err(e) => {
+            error!("[Error][backup_file_handler()] {:?}", e);
+            Err(actix_web::error::ErrorInternalServerError(e))
+        }
+    }
+}
+
+#[doc = "선택한 백업으로 파일을 복원해주는 핸들러 - 현재 파일은 먼저 백업된 뒤 원자적으로 교체된다."]
+/// # Arguments
+/// * `req`             - 복원할 파일의 이름과 백업 시각
+/// * `file_service`    - file 관련 서비스 인스턴스
+/// * `analyzer_reload_handler` - 복원한 파일을 사전으로 사용하는 검색 분석기를 리로드해주는 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn backup_restore_handler(
+    req: web::Query<BackupFileQuery>,
+    file_service: web::Data<Arc<FileServiceImpl>>,
+    analyzer_reload_handler: AnalyzerReloadHandlerData,
+) -> Result<HttpResponse, Error> {
+    let (watch_path_string, slave_backup_path, slave_host): (String, String, String) =
+        get_backup_configs("backup_restore_handler")?;
+
+    let target_file_path: PathBuf =
+        match get_sync_target_path(Path::new(&watch_path_string), &req.filename) {
+            Ok(target_file_path) => target_file_path,
+            Err(e) => {
+                error!("[Error][backup_restore_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorBadRequest(e));
+            }
+        };
+
+    let file_data: Vec<u8> = match file_service.get_backup_file_data(&slave_backup_path, &req.filename, &req.backup_id) {
+        Ok(Some(file_data)) => file_data,
+        Ok(None) => return Ok(HttpResponse::NotFound().body("The backup does not exist")),
+        Err(e) => {
+            error!("[Error][backup_restore_handler()] {:?}", e);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    };
+
+    /* 복원도 다른 반영과 마찬가지로 파일에 설정된 동기화 훅을 거친다. -> 버전은 그대로 유지하고, 복원한 백업을 출처로 기록한다. */
+    let sync_hook_handler: SyncHookHandler<FileServiceImpl> = SyncHookHandler::new(Arc::clone(file_service.get_ref()));
+
+    let restore_res: Result<SyncWriteResult, anyhow::Error> = match create_parent_dir(&target_file_path)
+        .and_then(|_| file_service.write_staged_file(&target_file_path, &file_data))
+    {
+        Ok(staged_file_path) => {
+            sync_hook_handler
+                .commit_with_sync_hooks(
+                    &staged_file_path,
+                    &target_file_path,
+                    &req.filename,
+                    &format!("backup:{}", req.backup_id),
+                    None,
+                    None,
+                )
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    let (task_status, task_detail, hook_results): (&str, String, Vec<HookResult>) = match restore_res {
+        Ok(SyncWriteResult::Committed(hook_results)) => (
+            "restored",
+            format!("restored from backup {}", req.backup_id),
+            hook_results,
+        ),
+        Ok(SyncWriteResult::Stale) => {
+            return Ok(HttpResponse::Conflict().body("A newer version of the file already exists"));
+        }
+        Ok(SyncWriteResult::Rejected(hook_result)) => {
+            return Ok(HttpResponse::UnprocessableEntity().body(hook_result.get_report()));
+        }
+        Ok(SyncWriteResult::Reverted(hook_result, restore_detail)) => {
+            return Ok(HttpResponse::InternalServerError()
+                .body(format!("{}\n{}", hook_result.get_report(), restore_detail)));
+        }
+        Err(e) => {
+            error!("[Error][backup_restore_handler()] {:?}", e);
+            (
+                "failed",
+                format!("restore from backup {}: {:?}", req.backup_id, e),
+                Vec::new(),
+            )
+        }
+    };
+
+    match ElasticMsg::new(&slave_host, &slave_host, &req.filename, task_status, &task_detail) {
+        Ok(task_event) => write_task_event_log(&task_event),
+        Err(e) => error!("[Error][backup_restore_handler()] {:?}", e),
+    }
+
+    if task_status == "failed" {
+        return Err(actix_web::error::ErrorInternalServerError(task_detail));
+    }
+
+    let mut response: HttpResponseBuilder = HttpResponse::Ok();
+    let mut restore_message: String = task_detail;
+    for hook_result in &hook_results {
+        restore_message.push_str(&format!("\n{}", hook_result.get_report()));
+    }
+
+    if let Some(analyzer_reload_report) =
+        reload_file_analyzers(&analyzer_reload_handler, &req.filename).await
+    {
+        insert_analyzer_reload_report(&mut response, &mut restore_message, &analyzer_reload_report);
+    }
 
-    Ok(HttpResponse::Ok().body("File uploaded successfully"))
+    Ok(response.body(restore_message))
 }
@@ -0,0 +1,390 @@
+use crate::common::*;
+use crate::model::file_info::*;
+use crate::model::manifest_info::*;
+use crate::model::monitoring_path_info::*;
+use crate::model::rollback_result::*;
+
+use crate::configs::configs::*;
+
+use crate::service::file_service_impl::*;
+use crate::service::request_service_impl::*;
+
+use crate::handler::rollback_handler::*;
+
+use crate::traits::service::{file_service::*, receive_service::*};
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::io_utils::*;
+use crate::utils_modules::logger_utils::*;
+
+use crate::model::elastic_msg::*;
+
+/* 배포 이력을 보관하는 경우에만 등록되는 rollback handler */
+type RollbackHandlerData = Option<web::Data<Arc<RollbackHandler<RequestServiceImpl, FileServiceImpl>>>>;
+
+#[derive(Debug, new)]
+pub struct MasterRouter;
+
+impl MasterRouter {
+    #[doc = "master 가 제공하는 Router 서비스를 등록해주는 함수 - pull 모드 조회, slave 수정파일 검토 요청, 배포 이력 조회 및 롤백"]
+    /// # Arguments
+    /// * `cfg` - 웹 서비스 컨피그 객체 : 새로운 서비스를 호스팅할 수 있다.
+    pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+        cfg.service(web::resource("/manifest").route(web::get().to(master_manifest_handler)));
+        cfg.service(web::resource("/download").route(web::get().to(master_download_handler)));
+        cfg.service(web::resource("/review").route(web::post().to(master_review_handler)));
+        cfg.service(web::resource("/history").route(web::get().to(master_history_handler)));
+        cfg.service(web::resource("/rollback").route(web::post().to(master_rollback_handler)));
+    }
+}
+
+#[doc = "master manifest 핸들러 - 현재 배포중인 모니터링 대상 파일과 해시값 목록을 반환해주는 함수"]
+/// 라우팅 규칙상 요청한 slave 에 전달되는 파일만 포함된다.
+///
+/// # Arguments
+/// * `http_req`        - Http 요청 정보 (요청을 보낸 slave 의 주소 확인용)
+/// * `file_service`    - file 관련 서비스 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn master_manifest_handler(
+    http_req: HttpRequest,
+    file_service: web::Data<Arc<FileServiceImpl>>,
+) -> Result<HttpResponse, Error> {
+    let master_host: String;
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
+            Err(e) => {
+                error!("[Error][master_manifest_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorInternalServerError(e));
+            }
+        };
+
+        master_host = server_config.server.host().clone();
+    }
+
+    let monitor_file_list: Vec<MonitoringPathInfo> =
+        match get_routed_monitoring_file_list(&get_peer_ip(&http_req)) {
+            Ok(monitor_file_list) => monitor_file_list,
+            Err(e) => {
+                error!("[Error][master_manifest_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorInternalServerError(e));
+            }
+        };
+
+    match file_service.get_monitoring_manifest_files(&monitor_file_list) {
+        Ok(manifest_files) => Ok(HttpResponse::Ok().json(ManifestInfo::new(master_host, manifest_files))),
+        Err(e) => {
+            error!("[Error][master_manifest_handler()] {:?}", e);
+            Err(actix_web::error::ErrorInternalServerError(e))
+        }
+    }
+}
+
+#[doc = "master 파일 다운로드 핸들러 - slave 가 요청한 모니터링 대상 파일의 내용을 반환해주는 함수"]
+/// # Arguments
+/// * `http_req`    - Http 요청 정보 (요청을 보낸 slave 의 주소 확인용)
+/// * `req`         - Request 객체 Http 통신을 통해서 넘어온 쿼리의 결과. (manifest 의 file_path)
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn master_download_handler(
+    http_req: HttpRequest,
+    req: web::Query<FileInfo>,
+) -> Result<HttpResponse, Error> {
+    let monitor_file_list: Vec<MonitoringPathInfo> =
+        match get_routed_monitoring_file_list(&get_peer_ip(&http_req)) {
+            Ok(monitor_file_list) => monitor_file_list,
+            Err(e) => {
+                error!("[Error][master_download_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorInternalServerError(e));
+            }
+        };
+
+    /* 요청한 slave 에 전달되는 모니터링 대상 파일만 내려받을 수 있다. */
+    let monitoring_file: &MonitoringPathInfo = match monitor_file_list
+        .iter()
+        .find(|monitoring_file| monitoring_file.file_path() == &req.filename)
+    {
+        Some(monitoring_file) => monitoring_file,
+        None => {
+            error!(
+                "[Error][master_download_handler()] The file '{}' is not a monitoring target file.",
+                req.filename
+            );
+            return Ok(HttpResponse::NotFound().body("File not found"));
+        }
+    };
+
+    match tokio::fs::read(monitoring_file.full_file_path()).await {
+        Ok(file_data) => {
+            info!("The file '{}' was pulled by a slave server.", req.filename);
+            Ok(HttpResponse::Ok()
+                .content_type("application/octet-stream")
+                .body(file_data))
+        }
+        Err(e) => {
+            error!("[Error][master_download_handler()] {:?}", e);
+            Err(actix_web::error::ErrorInternalServerError(e))
+        }
+    }
+}
+
+#[doc = "slave 수정파일 검토 핸들러 - slave 에서 직접 수정된 파일을 검토 디렉토리에 slave 별로 보관해주는 함수"]
+/// 보관만 할 뿐 모니터링 대상 파일에는 반영하지 않는다. -> 운영자가 검토 후 master 에 반영한다.
+///
+/// # Arguments
+/// * `http_req`        - Http 요청 정보 (요청을 보낸 slave 의 주소 확인용)
+/// * `req`             - Request 객체 Http 통신을 통해서 넘어온 쿼리의 결과.
+/// * `body`            - 수정된 파일 데이터
+/// * `file_service`    - file 관련 서비스 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn master_review_handler(
+    http_req: HttpRequest,
+    req: web::Query<FileInfo>,
+    body: web::Bytes,
+    file_service: web::Data<Arc<FileServiceImpl>>,
+) -> Result<HttpResponse, Error> {
+    let review_path: String;
+    let master_host: String;
+    {
+        let server_config: RwLockReadGuard<'_, Configs> = match get_config_read() {
+            Ok(server_config) => server_config,
+            Err(e) => {
+                error!("[Error][master_review_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorInternalServerError(e));
+            }
+        };
+
+        master_host = server_config.server.host().clone();
+        review_path = match server_config.server.review_path() {
+            Some(review_path) => review_path.clone(),
+            None => return Ok(HttpResponse::NotFound().body("Review is not enabled")),
+        };
+    }
+
+    let slave_ip: String = get_peer_ip(&http_req);
+
+    /* 검토 디렉토리/slave 주소/파일 이름 -> 검토 디렉토리를 벗어나는 경로는 허용하지 않는다. */
+    let review_file_path: PathBuf =
+        match get_sync_target_path(&Path::new(&review_path).join(&slave_ip), &req.filename) {
+            Ok(review_file_path) => review_file_path,
+            Err(e) => {
+                error!("[Error][master_review_handler()] {:?}", e);
+                return Err(actix_web::error::ErrorBadRequest(e));
+            }
+        };
+
+    let received_hash: String = hash_to_hex(&compute_hash_from_bytes(&body));
+
+    let expected_hash: Option<&str> = http_req
+        .headers()
+        .get(CONTENT_SHA256_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    if let Some(expected_hash) = expected_hash {
+        if !expected_hash.eq_ignore_ascii_case(&received_hash) {
+            let err_msg: String = format!(
+                "[Error][master_review_handler()] Content digest mismatch for '{}'. expected: {} // received: {}",
+                req.filename, expected_hash, received_hash
+            );
+            error!("{}", err_msg);
+            return Err(actix_web::error::ErrorBadRequest(err_msg));
+        }
+    }
+
+    /* 이전에 보관된 검토 파일은 검토 디렉토리의 backup 디렉토리로 옮겨진다. */
+    let review_backup_path: String = Path::new(&review_path)
+        .join("backup")
+        .to_string_lossy()
+        .to_string();
+
+    let review_res: Result<(), anyhow::Error> = file_service
+        .write_staged_file(&review_file_path, &body)
+        .and_then(|staged_file_path| {
+            file_service.commit_staged_file(
+                &staged_file_path,
+                &review_file_path,
+                &review_backup_path,
+                &req.filename,
+                None,
+                "review",
+            )
+        });
+
+    if let Err(e) = review_res {
+        error!("[Error][master_review_handler()] {:?}", e);
+        return Err(actix_web::error::ErrorInternalServerError(e));
+    }
+
+    warn!(
+        "The file '{}' was modified on the slave '{}' and has been stored for review: {:?}",
+        req.filename, slave_ip, review_file_path
+    );
+
+    match ElasticMsg::new(
+        &slave_ip,
+        &master_host,
+        &req.filename,
+        "review",
+        "local modification forwarded for review",
+    ) {
+        Ok(task_event) => write_task_event_log(&task_event),
+        Err(e) => error!("[Error][master_review_handler()] {:?}", e),
+    }
+
+    Ok(HttpResponse::Ok()
+        .insert_header((CONTENT_SHA256_HEADER, received_hash))
+        .body("File stored for review"))
+}
+
+#[doc = "배포 이력 조회 핸들러 - 파일별로 배포된 버전과 각 버전을 수신한 slave 목록을 반환해주는 함수"]
+/// # Arguments
+/// * `http_req`            - Http 요청 정보 (관리자 여부 확인용)
+/// * `req`                 - 조회할 파일의 이름
+/// * `rollback_handler`    - 배포 이력을 보관하는 경우 등록되는 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn master_history_handler(
+    http_req: HttpRequest,
+    req: web::Query<FileInfo>,
+    rollback_handler: RollbackHandlerData,
+) -> Result<HttpResponse, Error> {
+    let rollback_handler: web::Data<Arc<RollbackHandler<RequestServiceImpl, FileServiceImpl>>> =
+        check_admin_request(&http_req, rollback_handler, "master_history_handler")?;
+
+    let master_host: String = match get_config_read() {
+        Ok(server_config) => server_config.server.host().clone(),
+        Err(e) => {
+            error!("[Error][master_history_handler()] {:?}", e);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    };
+
+    match rollback_handler.get_history(&req.filename) {
+        Ok(versions) => Ok(HttpResponse::Ok().json(HistoryListInfo::new(
+            master_host,
+            req.filename.clone(),
+            versions,
+        ))),
+        Err(e) => {
+            error!("[Error][master_history_handler()] {:?}", e);
+            Err(actix_web::error::ErrorNotFound(e))
+        }
+    }
+}
+
+#[doc = "롤백 핸들러 - 배포된 파일을 이전 버전(또는 지정한 버전)으로 되돌려 해당 파일을 수신했던 모든 slave 에 다시 배포해주는 함수"]
+/// 일부 slave 에 반영하지 못한 경우 502 로 응답한다. (반영하지 못한 slave 는 outbox 에서 재시도된다.)
+///
+/// # Arguments
+/// * `http_req`            - Http 요청 정보 (관리자 여부 확인용)
+/// * `req`                 - 되돌릴 파일의 이름과 버전
+/// * `rollback_handler`    - 배포 이력을 보관하는 경우 등록되는 인스턴스
+///
+/// # Return
+/// * Result<HttpResponse, Error>
+async fn master_rollback_handler(
+    http_req: HttpRequest,
+    req: web::Query<RollbackQuery>,
+    rollback_handler: RollbackHandlerData,
+) -> Result<HttpResponse, Error> {
+    let rollback_handler: web::Data<Arc<RollbackHandler<RequestServiceImpl, FileServiceImpl>>> =
+        check_admin_request(&http_req, rollback_handler, "master_rollback_handler")?;
+
+    info!(
+        "Received a rollback request of '{}' (version: {:?}) from {}",
+        req.filename,
+        req.version,
+        get_peer_ip(&http_req)
+    );
+
+    match rollback_handler.rollback_file(&req.filename, req.version).await {
+        Ok(rollback_result) if rollback_result.is_success() => Ok(HttpResponse::Ok().json(rollback_result)),
+        Ok(rollback_result) => Ok(HttpResponse::BadGateway().json(rollback_result)),
+        Err(e) => {
+            error!("[Error][master_rollback_handler()] {:?}", e);
+            Err(actix_web::error::ErrorBadRequest(e))
+        }
+    }
+}
+
+#[doc = "배포 이력 조회/롤백 요청이 관리자로부터 온 것인지 확인해주는 함수"]
+/// # Arguments
+/// * `http_req`            - Http 요청 정보
+/// * `rollback_handler`    - 배포 이력을 보관하는 경우 등록되는 인스턴스
+/// * `handler_name`        - 에러 로그에 남길 핸들러 이름
+///
+/// # Return
+/// * Result<web::Data<Arc<RollbackHandler<..>>>, Error> - 배포 이력을 보관하지 않으면 404, 관리자가 아니면 403
+fn check_admin_request(
+    http_req: &HttpRequest,
+    rollback_handler: RollbackHandlerData,
+    handler_name: &str,
+) -> Result<web::Data<Arc<RollbackHandler<RequestServiceImpl, FileServiceImpl>>>, Error> {
+    let rollback_handler: web::Data<Arc<RollbackHandler<RequestServiceImpl, FileServiceImpl>>> =
+        rollback_handler.ok_or_else(|| {
+            actix_web::error::ErrorNotFound("Deployment history is not enabled on this server")
+        })?;
+
+    let admin_ip_list: Vec<String> = match get_config_read() {
+        Ok(server_config) => server_config.server.get_admin_ip_list(),
+        Err(e) => {
+            error!("[Error][{}()] {:?}", handler_name, e);
+            return Err(actix_web::error::ErrorInternalServerError(e));
+        }
+    };
+
+    let peer_ip: String = get_peer_ip(http_req);
+
+    if !admin_ip_list.contains(&peer_ip) {
+        warn!("[Warn][{}()] Rejected a request from a non-admin host: {}", handler_name, peer_ip);
+        return Err(actix_web::error::ErrorForbidden("Only admin hosts are allowed"));
+    }
+
+    Ok(rollback_handler)
+}
+
+#[doc = "요청을 보낸 서버의 IP 를 반환해주는 함수"]
+/// # Arguments
+/// * `http_req` - Http 요청 정보
+///
+/// # Return
+/// * String - 확인할 수 없는 경우 unknown
+fn get_peer_ip(http_req: &HttpRequest) -> String {
+    http_req
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[doc = "라우팅 규칙상 해당 slave 에 전달되는 모니터링 대상 파일 목록을 반환해주는 함수"]
+/// 파일 경로는 해당 slave 의 경로 매핑이 적용된 저장 경로로 바뀐다.
+///
+/// # Arguments
+/// * `slave_ip` - 요청을 보낸 slave 의 IP
+///
+/// # Return
+/// * Result<Vec<MonitoringPathInfo>, anyhow::Error>
+fn get_routed_monitoring_file_list(slave_ip: &str) -> Result<Vec<MonitoringPathInfo>, anyhow::Error> {
+    let mut routed_file_list: Vec<MonitoringPathInfo> = Vec::new();
+
+    for monitoring_file in get_monitoring_file_list()? {
+        let slave_url: String = match get_routed_slave_url(monitoring_file.file_path(), slave_ip)? {
+            Some(slave_url) => slave_url,
+            None => continue,
+        };
+
+        routed_file_list.push(MonitoringPathInfo::new(
+            monitoring_file.root_name().clone(),
+            get_slave_dest_file_name(monitoring_file.file_path(), &slave_url)?,
+            monitoring_file.full_file_path().clone(),
+        ));
+    }
+
+    Ok(routed_file_list)
+}
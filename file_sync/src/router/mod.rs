@@ -1 +1,2 @@
 pub mod app_router;
+pub mod master_router;
@@ -0,0 +1,732 @@
+use crate::common::*;
+
+use crate::service::file_service_impl::*;
+
+use crate::utils_modules::io_utils::*;
+use crate::utils_modules::pattern_utils::*;
+use crate::utils_modules::time_utils::*;
+
+use crate::repository::receive_repository::*;
+use crate::repository::version_store_repository::*;
+
+use crate::traits::service::backup_service::*;
+
+use crate::configs::backup_retention_config::*;
+use crate::configs::configs::*;
+
+use crate::model::backup_info::*;
+
+#[async_trait]
+impl BackupService for FileServiceImpl {
+    #[doc = "특정 파일을 백업 디렉토리의 버전 저장소에 백업하는 코드"]
+    /// 같은 내용이 이미 저장되어 있다면 내용은 다시 저장하지 않고 기록만 추가된다.
+    ///
+    /// # Arguments
+    /// * `backup_target_file_path`     - 동기화 대상이 될 파일 경로
+    /// * `backup_dir_path`             - 백업 디렉토리 경로
+    /// * `modified_file_name`          - 변경된 파일의 이름
+    /// * `backup_version`              - 백업되는 내용의 버전 (알 수 없는 경우 None)
+    /// * `backup_source`               - 백업되는 내용을 보낸 곳
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn copy_file_for_backup(
+        &self,
+        backup_target_file_path: PathBuf,
+        backup_dir_path: &str,
+        modified_file_name: &str,
+        backup_version: Option<u64>,
+        backup_source: &str,
+    ) -> Result<(), anyhow::Error> {
+        let file_name: &str = backup_target_file_path
+            .file_name()
+            .ok_or_else(|| anyhow!("Invalid file name from path"))?
+            .to_str()
+            .ok_or_else(|| anyhow!("Non-UTF8 file name"))?;
+
+        let compression: String = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            server_config.server.get_backup_compression()
+        };
+
+        let file_data: Vec<u8> = fs::read(&backup_target_file_path)?;
+
+        /* 백업 디렉토리 관련 -> 오래된 백업은 백업 정리 태스크가 주기적으로 삭제해준다. */
+        let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(Path::new(backup_dir_path))?;
+        let mut version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+            Ok(version_store) => version_store,
+            Err(e) => return Err(anyhow!("[Error][copy_file_for_backup()] {:?}", e)),
+        };
+
+        /* 백업 식별자가 겹치면 기존 백업이 교체되므로, 이미 사용중인 식별자라면 다음 마이크로초를 사용한다. */
+        let mut backup_time: NaiveDateTime = get_currnet_utc_naivedatetime();
+        let mut timestamp: String = get_str_from_naivedatetime(backup_time, BACKUP_TIMESTAMP_FORMAT)?;
+
+        while version_store.get_record(modified_file_name, &timestamp).is_some() {
+            backup_time += chrono::Duration::microseconds(1);
+            timestamp = get_str_from_naivedatetime(backup_time, BACKUP_TIMESTAMP_FORMAT)?;
+        }
+
+        let created_at: String = get_str_from_naivedatetime(backup_time, "%Y-%m-%dT%H:%M:%SZ")?;
+
+        /* 동기화 대상 파일의 내용을 저장소에 보관한다. (압축 설정시 압축하여 저장) */
+        let hash: String = version_store.put_blob(&file_data, &compression)?;
+
+        version_store.add_record(VersionRecord::new(
+            timestamp,
+            modified_file_name.to_string(),
+            backup_version,
+            created_at,
+            backup_source.to_string(),
+            hash,
+            file_data.len() as u64,
+        ))?;
+        version_store.save()?;
+
+        info!("Backup of file '{}' completed.", &file_name);
+        Ok(())
+    }
+
+    #[doc = "master 에서 삭제된 파일을 백업한 뒤 slave 에서도 제거해주는 함수 - 수신 정보도 함께 제거된다."]
+    /// 로컬 변경 감지가 삭제 도중의 파일을 되돌리지 않도록 삭제부터 수신 정보 제거까지 수신 저장소를 잠근 상태로 진행한다.
+    ///
+    /// # Arguments
+    /// * `delete_target_file_path` - 삭제 대상이 될 파일 경로
+    /// * `backup_dir_path`         - 백업 디렉토리 경로
+    /// * `deleted_file_name`       - 삭제된 파일의 이름
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 파일이 존재해서 삭제한 경우 True, 이미 존재하지 않는 경우 False
+    fn delete_file_with_backup(
+        &self,
+        delete_target_file_path: PathBuf,
+        backup_dir_path: &str,
+        deleted_file_name: &str,
+    ) -> Result<bool, anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let mut receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][delete_file_with_backup()] {:?}", e)),
+        };
+
+        /* 이미 존재하지 않는 파일이라면 삭제가 완료된 것으로 본다. */
+        if !delete_target_file_path.exists() {
+            info!(
+                "The file '{:?}' does not exist. There is nothing to delete.",
+                delete_target_file_path
+            );
+            receive_storage.remove_receive_info(deleted_file_name)?;
+            return Ok(false);
+        }
+
+        /* 삭제 전에 반드시 백업을 진행해준다. */
+        let (backup_version, backup_source): (Option<u64>, String) =
+            receive_storage.get_backup_source(deleted_file_name);
+
+        self.copy_file_for_backup(
+            delete_target_file_path.clone(),
+            backup_dir_path,
+            deleted_file_name,
+            backup_version,
+            &backup_source,
+        )?;
+
+        fs::remove_file(&delete_target_file_path)?;
+
+        receive_storage.remove_receive_info(deleted_file_name)?;
+
+        info!("The file '{:?}' has been deleted.", delete_target_file_path);
+        Ok(true)
+    }
+
+    #[doc = "master 에서 이름이 변경된 파일을 slave 에서도 이름변경 해주는 함수 - 덮어써지는 파일은 백업해준다."]
+    /// 로컬 변경 감지가 이름변경 도중의 파일을 되돌리지 않도록 이름변경부터 수신 정보 이동까지 수신 저장소를 잠근 상태로 진행한다.
+    ///
+    /// # Arguments
+    /// * `from_file_path`  - 변경 전 파일 경로
+    /// * `to_file_path`    - 변경 후 파일 경로
+    /// * `backup_dir_path` - 백업 디렉토리 경로
+    /// * `from_file_name`  - 변경 전 파일의 이름
+    /// * `to_file_name`    - 변경 후 파일의 이름
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn rename_file_with_backup(
+        &self,
+        from_file_path: PathBuf,
+        to_file_path: PathBuf,
+        backup_dir_path: &str,
+        from_file_name: &str,
+        to_file_name: &str,
+    ) -> Result<(), anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let mut receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][rename_file_with_backup()] {:?}", e)),
+        };
+
+        if !from_file_path.exists() {
+            return Err(anyhow!(
+                "[Error][rename_file_with_backup()] The file '{:?}' does not exist.",
+                from_file_path
+            ));
+        }
+
+        /* 덮어써지는 파일이 존재하는 경우에는 백업을 먼저 진행해준다. */
+        if to_file_path.exists() {
+            let (backup_version, backup_source): (Option<u64>, String) =
+                receive_storage.get_backup_source(to_file_name);
+
+            self.copy_file_for_backup(
+                to_file_path.clone(),
+                backup_dir_path,
+                to_file_name,
+                backup_version,
+                &backup_source,
+            )?;
+        }
+
+        create_parent_dir(&to_file_path)?;
+        fs::rename(&from_file_path, &to_file_path)?;
+
+        receive_storage.rename_receive_info(from_file_name, to_file_name)?;
+
+        info!(
+            "The file '{:?}' has been renamed to '{:?}'.",
+            from_file_path, to_file_path
+        );
+        Ok(())
+    }
+
+    #[doc = "보관기간이 지난 백업을 주기적으로 제거해주는 함수"]
+    /// # Arguments
+    /// * `backup_file_dir` - 백업 디렉토리 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn backup_file_delete(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error> {
+        /* 백업 유지기간 */
+        /* 백업유지기간이 설정이 안되어있다면 기본적으로 7일보존 */
+        let backup_days: i64 = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            (*server_config.server.backup_days()).unwrap_or(7)
+        };
+
+        let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(backup_file_dir)?;
+        let mut version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+            Ok(version_store) => version_store,
+            Err(e) => return Err(anyhow!("[Error][backup_file_delete()] {:?}", e)),
+        };
+
+        let mut expired_records: Vec<(String, String)> = Vec::new();
+
+        for version_record in &version_store.records {
+            /* 백업 식별자(백업 시각)의 날짜가 오늘 기준으로 며칠이 되었는지 확인해준다. */
+            let days_diff: i64 = match NaiveDateTime::parse_from_str(version_record.record_id(), BACKUP_TIMESTAMP_FORMAT)
+                .map_err(|e| anyhow!("Invalid backup id: {:?}", e))
+                .and_then(|backup_time| get_str_from_naivedate(backup_time.date(), "%Y%m%d"))
+                .and_then(|backup_date| calculate_date_difference_utc(&backup_date))
+            {
+                Ok(days_diff) => days_diff,
+                Err(e) => {
+                    error!("[Error][backup_file_delete()] {:?}", e);
+                    continue;
+                }
+            };
+
+            if days_diff >= backup_days {
+                expired_records.push((version_record.file_name.clone(), version_record.record_id.clone()));
+            }
+        }
+
+        /* 날짜가 보관 기간을 넘었을 경우 해당 백업을 삭제해준다. -> 다른 백업과 공유하지 않는 내용만 지워진다. */
+        for (file_name, record_id) in &expired_records {
+            if let Err(e) = version_store.remove_record(file_name, record_id) {
+                error!("[Error][backup_file_delete()] {:?}", e);
+            }
+        }
+
+        if !expired_records.is_empty() {
+            version_store.save()?;
+            remove_empty_dirs(&backup_file_dir.join(VERSION_STORE_DIR))?;
+            info!(
+                "Removed {} backups older than {} days in {:?}",
+                expired_records.len(),
+                backup_days,
+                backup_file_dir
+            );
+        }
+
+        Ok(())
+    }
+
+    #[doc = "버전 저장소 도입 이전의 날짜별 폴더에 남아있는 백업을 버전 저장소로 옮겨주는 함수"]
+    /// 옮겨진 백업은 보낸 곳을 알 수 없으므로 `legacy` 로 기록된다.
+    ///
+    /// # Arguments
+    /// * `backup_file_dir` - 백업 디렉토리 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn import_legacy_backups(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error> {
+        let compression: String = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            server_config.server.get_backup_compression()
+        };
+
+        let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(backup_file_dir)?;
+        let mut version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+            Ok(version_store) => version_store,
+            Err(e) => return Err(anyhow!("[Error][import_legacy_backups()] {:?}", e)),
+        };
+
+        let mut imported_count: usize = 0;
+
+        for date_entry in fs::read_dir(backup_file_dir)? {
+            let date_dir: PathBuf = date_entry?.path();
+
+            /* 이전 형식의 백업은 `날짜별 폴더/파일이름.백업시각[.압축확장자]` 형태로 저장되어 있다. */
+            if !date_dir.is_dir() || date_dir.file_name().is_some_and(|name| name == VERSION_STORE_DIR) {
+                continue;
+            }
+
+            for backup_file_path in get_all_file_paths(&date_dir)? {
+                let relative_path: String = match get_relative_path_str(&date_dir, &backup_file_path) {
+                    Some(relative_path) => relative_path,
+                    None => continue,
+                };
+
+                let (parent_dir, backup_file_name): (&str, &str) = match relative_path.rsplit_once('/') {
+                    Some((parent_dir, backup_file_name)) => (parent_dir, backup_file_name),
+                    None => ("", relative_path.as_str()),
+                };
+
+                let (file_name, backup_id): (String, String) = match parse_backup_file_name(backup_file_name) {
+                    Some(parsed) => parsed,
+                    None => {
+                        warn!("[Warn][import_legacy_backups()] Not a backup file: {:?}", backup_file_path);
+                        continue;
+                    }
+                };
+
+                let file_name: String = if parent_dir.is_empty() {
+                    file_name
+                } else {
+                    format!("{}/{}", parent_dir, file_name)
+                };
+
+                let file_data: Vec<u8> = read_backup_file(&backup_file_path)?;
+                let backup_time: NaiveDateTime = NaiveDateTime::parse_from_str(&backup_id, BACKUP_TIMESTAMP_FORMAT)?;
+                let hash: String = version_store.put_blob(&file_data, &compression)?;
+
+                version_store.add_record(VersionRecord::new(
+                    backup_id,
+                    file_name,
+                    None,
+                    backup_time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                    "legacy".to_string(),
+                    hash,
+                    file_data.len() as u64,
+                ))?;
+
+                /* 색인을 먼저 저장한 뒤 원본을 지워야 도중에 실패하더라도 백업을 잃지 않는다. */
+                version_store.save()?;
+                fs::remove_file(&backup_file_path)?;
+                imported_count += 1;
+            }
+        }
+
+        if imported_count > 0 {
+            remove_empty_dirs(backup_file_dir)?;
+            info!(
+                "Imported {} legacy backups into the version store in {:?}",
+                imported_count, backup_file_dir
+            );
+        }
+
+        Ok(())
+    }
+
+    #[doc = "파일별 백업 보관 정책에 따라 오래된 백업을 삭제해주는 함수"]
+    /// 정책에 지정한 조건은 아래 순서로 모두 적용된다. (일치하는 정책이 없는 파일은 backup_days 만 적용된다.)
+    /// 1. keep_last        - 최근 N 개를 넘는 백업 삭제
+    /// 2. daily_after_days - N 일이 지난 백업은 그 날의 마지막 백업만 남기고 삭제
+    /// 3. max_total_mb     - 남은 백업의 전체 크기가 X MB 를 넘으면 오래된 것부터 삭제 (가장 최근 백업은 보관)
+    ///
+    /// 같은 내용의 백업은 저장소에 한번만 저장되므로, 전체 크기에도 한번만 포함된다.
+    ///
+    /// # Arguments
+    /// * `backup_file_dir` - 백업 디렉토리 경로
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn apply_backup_retention(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error> {
+        if !backup_file_dir.is_dir() {
+            return Ok(());
+        }
+
+        let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(backup_file_dir)?;
+        let mut version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+            Ok(version_store) => version_store,
+            Err(e) => return Err(anyhow!("[Error][apply_backup_retention()] {:?}", e)),
+        };
+
+        /* 파일별 백업 목록 : 파일이름 -> 백업 기록 */
+        let mut backup_files: HashMap<String, Vec<VersionRecord>> = HashMap::new();
+
+        for version_record in &version_store.records {
+            backup_files
+                .entry(version_record.file_name.clone())
+                .or_default()
+                .push(version_record.clone());
+        }
+
+        let now: NaiveDateTime = get_currnet_utc_naivedatetime();
+        let mut expired_records: Vec<VersionRecord> = Vec::new();
+
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+
+            for (file_name, backups) in backup_files {
+                let backup_retention: &BackupRetentionConfig =
+                    match server_config.server.get_backup_retention(&file_name)? {
+                        Some(backup_retention) => backup_retention,
+                        None => continue,
+                    };
+
+                let mut file_expired_records: Vec<VersionRecord> =
+                    get_expired_backups(backups, backup_retention, now, |hash: &str| {
+                        version_store.get_blob_size(hash)
+                    })?;
+
+                expired_records.append(&mut file_expired_records);
+            }
+        }
+
+        for expired_record in &expired_records {
+            if let Err(e) = version_store.remove_record(expired_record.file_name(), expired_record.record_id()) {
+                error!(
+                    "[Error][apply_backup_retention()] Failed to remove the backup '{}' of '{}': {:?}",
+                    expired_record.record_id(),
+                    expired_record.file_name(),
+                    e
+                );
+            }
+        }
+
+        if !expired_records.is_empty() {
+            version_store.save()?;
+            remove_empty_dirs(&backup_file_dir.join(VERSION_STORE_DIR))?;
+            info!(
+                "Removed {} backups by the retention policy in {:?}",
+                expired_records.len(),
+                backup_file_dir
+            );
+        }
+
+        Ok(())
+    }
+
+    #[doc = "특정 파일의 백업 목록을 조회해주는 함수 - 최신 백업이 먼저 온다."]
+    /// # Arguments
+    /// * `backup_dir_path` - 백업 디렉토리 경로
+    /// * `file_name`       - 조회할 파일의 이름 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<Vec<BackupFileInfo>, anyhow::Error>
+    fn get_backup_file_list(&self, backup_dir_path: &str, file_name: &str) -> Result<Vec<BackupFileInfo>, anyhow::Error> {
+        let backup_dir: &Path = Path::new(backup_dir_path);
+        let mut backup_list: Vec<BackupFileInfo> = Vec::new();
+
+        if !backup_dir.is_dir() {
+            return Ok(backup_list);
+        }
+
+        let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(backup_dir)?;
+        let version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+            Ok(version_store) => version_store,
+            Err(e) => return Err(anyhow!("[Error][get_backup_file_list()] {:?}", e)),
+        };
+
+        for version_record in version_store.get_records(file_name) {
+            /* 압축된 백업이라도 해시값과 크기는 원래 내용을 기준으로 한다. */
+            let stored_size: u64 = version_store.get_blob_size(version_record.hash())?;
+
+            backup_list.push(BackupFileInfo::new(
+                version_record.record_id,
+                version_record.version,
+                version_record.source,
+                version_record.hash,
+                version_record.size,
+                stored_size,
+                version_record.created_at,
+            ));
+        }
+
+        backup_list.sort_by(|a, b| b.backup_id().cmp(a.backup_id()));
+
+        Ok(backup_list)
+    }
+
+    #[doc = "특정 백업의 원래 내용을 읽어주는 함수"]
+    /// # Arguments
+    /// * `backup_dir_path` - 백업 디렉토리 경로
+    /// * `file_name`       - 백업된 파일의 이름 (감시 디렉토리 기준 상대경로)
+    /// * `backup_id`       - 백업 시각
+    ///
+    /// # Returns
+    /// * Result<Option<Vec<u8>>, anyhow::Error> - 백업이 존재하지 않는 경우 None
+    fn get_backup_file_data(
+        &self,
+        backup_dir_path: &str,
+        file_name: &str,
+        backup_id: &str,
+    ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+        let backup_dir: &Path = Path::new(backup_dir_path);
+
+        if !backup_dir.is_dir() {
+            return Ok(None);
+        }
+
+        let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(backup_dir)?;
+        let version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+            Ok(version_store) => version_store,
+            Err(e) => return Err(anyhow!("[Error][get_backup_file_data()] {:?}", e)),
+        };
+
+        match version_store.get_record(file_name, backup_id) {
+            Some(version_record) => Ok(Some(version_store.read_blob(version_record.hash())?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[doc = "파일 하나의 백업 목록에 보관 정책을 적용해 제거할 백업을 골라주는 함수"]
+/// 최신 백업부터 keep_last -> daily_after_days -> max_total_mb 순서로 적용하며, 가장 최근 백업은 크기와 관계없이 보관한다.
+///
+/// # Arguments
+/// * `backups`             - 파일 하나의 백업 기록 목록
+/// * `backup_retention`    - 파일에 적용할 보관 정책
+/// * `now`                 - 기준 시각 (UTC)
+/// * `get_blob_size`       - 내용 해시값으로 저장된 크기를 조회해주는 함수
+///
+/// # Returns
+/// * Result<Vec<VersionRecord>, anyhow::Error> - 제거할 백업 기록 목록
+fn get_expired_backups<S>(
+    mut backups: Vec<VersionRecord>,
+    backup_retention: &BackupRetentionConfig,
+    now: NaiveDateTime,
+    get_blob_size: S,
+) -> Result<Vec<VersionRecord>, anyhow::Error>
+where
+    S: Fn(&str) -> Result<u64, anyhow::Error>,
+{
+    let mut expired_records: Vec<VersionRecord> = Vec::new();
+
+    /* 백업 식별자는 시각 순서대로 정렬된다. -> 최신 백업부터 */
+    backups.sort_by(|a, b| b.record_id.cmp(&a.record_id));
+
+    let daily_after: Option<NaiveDateTime> = backup_retention
+        .daily_after_days()
+        .map(|days| now - chrono::Duration::days(days));
+
+    let mut kept_dates: Vec<String> = Vec::new();
+    let mut kept_hashes: Vec<String> = Vec::new();
+    let mut kept_size: u64 = 0;
+
+    for (index, version_record) in backups.into_iter().enumerate() {
+        if backup_retention.keep_last().is_some_and(|keep_last| index >= keep_last) {
+            expired_records.push(version_record);
+            continue;
+        }
+
+        /* 백업 식별자를 해석할 수 없는 백업은 건너뛴다. -> 다른 백업들의 보관 정책 적용은 계속 진행된다. */
+        let backup_time: NaiveDateTime =
+            match NaiveDateTime::parse_from_str(version_record.record_id(), BACKUP_TIMESTAMP_FORMAT) {
+                Ok(backup_time) => backup_time,
+                Err(e) => {
+                    error!(
+                        "[Error][get_expired_backups()] Invalid backup id '{}' of '{}': {:?}",
+                        version_record.record_id(),
+                        version_record.file_name(),
+                        e
+                    );
+                    continue;
+                }
+            };
+        let backup_date: String = backup_time.format("%Y%m%d").to_string();
+
+        if daily_after.is_some_and(|daily_after| backup_time < daily_after)
+            && kept_dates.contains(&backup_date)
+        {
+            expired_records.push(version_record);
+            continue;
+        }
+
+        /* 이미 보관하기로 한 백업과 같은 내용이라면 크기가 늘어나지 않는다. */
+        let stored_size: u64 = if kept_hashes.contains(version_record.hash()) {
+            0
+        } else {
+            get_blob_size(version_record.hash())?
+        };
+
+        if index > 0
+            && backup_retention
+                .max_total_mb()
+                .is_some_and(|max_total_mb| kept_size + stored_size > max_total_mb * 1024 * 1024)
+        {
+            expired_records.push(version_record);
+            continue;
+        }
+
+        kept_dates.push(backup_date);
+        kept_hashes.push(version_record.hash.clone());
+        kept_size += stored_size;
+    }
+
+    Ok(expired_records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KB: u64 = 1024;
+
+    fn get_test_record(record_id: &str, hash: &str) -> VersionRecord {
+        VersionRecord::new(
+            record_id.to_string(),
+            "synonyms.txt".to_string(),
+            None,
+            record_id.to_string(),
+            "10.0.0.1:9000".to_string(),
+            hash.to_string(),
+            0,
+        )
+    }
+
+    fn get_test_now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2024_01_20_000000.000000", BACKUP_TIMESTAMP_FORMAT).unwrap()
+    }
+
+    fn get_expired_ids(
+        backups: Vec<VersionRecord>,
+        backup_retention: &BackupRetentionConfig,
+        blob_sizes: &HashMap<&str, u64>,
+    ) -> Vec<String> {
+        get_expired_backups(backups, backup_retention, get_test_now(), |hash: &str| {
+            Ok(blob_sizes.get(hash).copied().unwrap_or_default())
+        })
+        .unwrap()
+        .into_iter()
+        .map(|version_record| version_record.record_id)
+        .collect()
+    }
+
+    #[test]
+    fn keep_last_expires_oldest_backups() {
+        let backup_retention: BackupRetentionConfig = BackupRetentionConfig::new(vec![], Some(2), None, None);
+        let backups: Vec<VersionRecord> = vec![
+            get_test_record("2024_01_17_090000.000000", "c"),
+            get_test_record("2024_01_19_090000.000000", "a"),
+            get_test_record("2024_01_16_090000.000000", "d"),
+            get_test_record("2024_01_18_090000.000000", "b"),
+        ];
+
+        assert_eq!(
+            get_expired_ids(backups, &backup_retention, &HashMap::new()),
+            vec!["2024_01_17_090000.000000", "2024_01_16_090000.000000"]
+        );
+    }
+
+    #[test]
+    fn daily_keeps_last_backup_of_old_days() {
+        let backup_retention: BackupRetentionConfig = BackupRetentionConfig::new(vec![], None, None, Some(7));
+        let backups: Vec<VersionRecord> = vec![
+            get_test_record("2024_01_19_090000.000000", "a"),
+            get_test_record("2024_01_19_180000.000000", "b"),
+            get_test_record("2024_01_05_090000.000000", "c"),
+            get_test_record("2024_01_05_180000.000000", "d"),
+            get_test_record("2024_01_04_180000.000000", "e"),
+        ];
+
+        /* 최근 7일 안의 백업은 같은 날이라도 모두 보관된다. */
+        assert_eq!(
+            get_expired_ids(backups, &backup_retention, &HashMap::new()),
+            vec!["2024_01_05_090000.000000"]
+        );
+    }
+
+    #[test]
+    fn max_total_mb_keeps_newest_and_dedupes_content() {
+        let backup_retention: BackupRetentionConfig = BackupRetentionConfig::new(vec![], None, Some(1), None);
+        let blob_sizes: HashMap<&str, u64> = HashMap::from([("a", 600 * KB), ("b", 300 * KB), ("c", 200 * KB)]);
+        let backups: Vec<VersionRecord> = vec![
+            get_test_record("2024_01_19_090000.000000", "a"),
+            get_test_record("2024_01_18_090000.000000", "a"),
+            get_test_record("2024_01_17_090000.000000", "b"),
+            get_test_record("2024_01_16_090000.000000", "c"),
+        ];
+
+        /* 같은 내용의 백업은 크기가 늘어나지 않는다. -> 600KB + 300KB 까지만 보관 */
+        assert_eq!(
+            get_expired_ids(backups, &backup_retention, &blob_sizes),
+            vec!["2024_01_16_090000.000000"]
+        );
+
+        let blob_sizes: HashMap<&str, u64> = HashMap::from([("a", 2048 * KB)]);
+        let backups: Vec<VersionRecord> = vec![get_test_record("2024_01_19_090000.000000", "a")];
+
+        /* 가장 최근 백업은 크기 제한을 넘더라도 보관한다. */
+        assert!(get_expired_ids(backups, &backup_retention, &blob_sizes).is_empty());
+    }
+
+    #[test]
+    fn policies_apply_in_order() {
+        let backup_retention: BackupRetentionConfig = BackupRetentionConfig::new(vec![], Some(4), Some(1), Some(7));
+        let blob_sizes: HashMap<&str, u64> =
+            HashMap::from([("a", 400 * KB), ("c", 400 * KB), ("d", 100 * KB), ("e", 100 * KB), ("f", 400 * KB)]);
+        let backups: Vec<VersionRecord> = vec![
+            get_test_record("2024_01_19_120000.000000", "a"),
+            get_test_record("2024_01_19_100000.000000", "a"),
+            get_test_record("2024_01_05_180000.000000", "c"),
+            get_test_record("2024_01_05_090000.000000", "d"),
+            get_test_record("2024_01_04_090000.000000", "e"),
+        ];
+
+        /*
+            keep_last 는 보관 여부와 관계없이 최신 순서로 센다. -> 5번째 백업 제거
+            일별 보관으로 오래된 날의 이전 백업 제거 -> 크기 제한(1MB)은 남은 백업들로 계산된다.
+        */
+        assert_eq!(
+            get_expired_ids(backups, &backup_retention, &blob_sizes),
+            vec!["2024_01_05_090000.000000", "2024_01_04_090000.000000"]
+        );
+
+        let backups: Vec<VersionRecord> = vec![
+            get_test_record("2024_01_19_120000.000000", "a"),
+            get_test_record("2024_01_18_120000.000000", "c"),
+            get_test_record("2024_01_17_120000.000000", "f"),
+        ];
+
+        assert_eq!(
+            get_expired_ids(backups, &backup_retention, &blob_sizes),
+            vec!["2024_01_17_120000.000000"]
+        );
+    }
+
+    #[test]
+    fn invalid_backup_id_is_kept() {
+        let backup_retention: BackupRetentionConfig = BackupRetentionConfig::new(vec![], None, None, Some(7));
+        let backups: Vec<VersionRecord> = vec![
+            get_test_record("2024_01_05_180000.000000", "a"),
+            get_test_record("invalid", "b"),
+            get_test_record("2024_01_05_090000.000000", "c"),
+        ];
+
+        assert_eq!(
+            get_expired_ids(backups, &backup_retention, &HashMap::new()),
+            vec!["2024_01_05_090000.000000"]
+        );
+    }
+}
@@ -2,26 +2,36 @@ use crate::common::*;
 
 use crate::utils_modules::hash_utils::*;
 use crate::utils_modules::io_utils::*;
+use crate::utils_modules::pattern_utils::*;
 use crate::utils_modules::time_utils::*;
 
 use crate::repository::hash_repository::*;
+use crate::repository::receive_repository::*;
 
 use crate::traits::service::file_service::*;
+use crate::traits::service::hash_storage_service::*;
 
 use crate::configs::configs::*;
 
+use crate::model::file_event_info::*;
+use crate::model::manifest_info::*;
+use crate::model::monitoring_path_info::*;
+
 #[derive(Debug, Deserialize, Serialize, new)]
 pub struct FileServiceImpl {}
 
 #[async_trait]
 impl FileService for FileServiceImpl {
     #[doc = "변경된 파일을 기존 파일과 비교하는 함수"]
+    /// 마지막으로 감지한 해쉬값은 항상 갱신되며, 해당 해쉬값을 아직 수신하지 못한 slave 가 있다면 변경된 것으로 본다.
+    ///
     /// # Arguments
     /// * `target_file_path` - 변화가 생긴 파일의 경로
+    /// * `slave_url`        - 해당 파일을 전달받는 slave 목록 (라우팅 규칙 적용)
     ///
     /// # Returns
-    /// * Result<bool, anyhow::Error> - 파일의 변화가 있는 경우에 True, 변화가 없는 경우에는 False
-    fn comparison_file(&self, target_file_path: &Path) -> Result<bool, anyhow::Error> {
+    /// * Result<bool, anyhow::Error> - 아직 반영되지 않은 slave 가 있는 경우에 True, 모든 slave 에 반영된 경우에는 False
+    fn comparison_file(&self, target_file_path: &Path, slave_url: &[String]) -> Result<bool, anyhow::Error> {
         let target_file_path_str: &str = target_file_path
             .to_str()
             .ok_or_else(|| anyhow!("[Error][comparison_file()] There was a problem converting 'target_file_path' to string."))?;
@@ -47,138 +57,244 @@ impl FileService for FileServiceImpl {
             storage_hash.save()?;
 
             info!("The '{}' file has been modified.", target_file_path_str);
-            Ok(true) /* 변경 표시 */
-        } else {
+        }
+
+        let unacked_slaves: Vec<String> =
+            storage_hash.get_unacked_slaves(target_file_path_str, slave_url);
+
+        if unacked_slaves.is_empty() {
             info!("The '{}' file has not been modified.", target_file_path_str);
             Ok(false) /* 변경없음 표시 */
+        } else {
+            info!(
+                "The '{}' file has not yet been delivered to {:?}.",
+                target_file_path_str, unacked_slaves
+            );
+            Ok(true) /* 변경 표시 */
         }
     }
 
-    #[doc = "백업 디렉토리를 주기적으로 제거해주는 함수"]
+    #[doc = "삭제된 파일을 해시 저장소에 tombstone 으로 기록하는 함수"]
     /// # Arguments
-    /// * `backup_file_dir` - 백업 디렉토리 경로
+    /// * `target_file_path` - 삭제가 감지된 파일의 경로
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    fn backup_file_delete(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error> {
-        /* 백업 유지기간 */
-        /* 백업유지기간이 설정이 안되어있다면 기본적으로 7일보존 */
-        let backup_days: i64 = {
-            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
-            (*server_config.server.backup_days()).unwrap_or(7)
+    /// * Result<bool, anyhow::Error> - 아직 파일을 가지고 있는 것으로 기록된 slave 가 있거나 새롭게 삭제가 기록된 경우에 True
+    fn comparison_deleted_file(&self, target_file_path: &Path) -> Result<bool, anyhow::Error> {
+        let target_file_path_str: &str = target_file_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][comparison_deleted_file()] There was a problem converting 'target_file_path' to string."))?;
+
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let mut storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][comparison_deleted_file()] {:?}", e)),
         };
 
-        let entries: fs::ReadDir = fs::read_dir(backup_file_dir)?;
+        if storage_hash.is_tombstone(target_file_path_str) {
+            /* 이미 삭제가 기록되어 있고 모든 slave 에서 삭제가 확인된 경우라면 다시 전파할 필요가 없다. */
+            if !storage_hash.has_acked_slaves(target_file_path_str) {
+                info!("The deletion of '{}' has already been recorded.", target_file_path_str);
+                return Ok(false);
+            }
 
-        for entry in entries {
-            let entry: fs::DirEntry = match entry {
-                Ok(entry) => entry,
-                Err(e) => {
-                    error!("[Error][backup_file_delete()] There is a problem with the value of 'entry'. : {:?}", e);
-                    continue;
-                }
+            info!(
+                "The deletion of '{}' has not yet been delivered to all slaves.",
+                target_file_path_str
+            );
+            return Ok(true);
+        }
+
+        let deleted_at: String = get_current_utc_naivedatetime_str("%Y-%m-%dT%H:%M:%SZ")?;
+        storage_hash.update_tombstone(target_file_path_str.to_string(), deleted_at);
+        storage_hash.save()?;
+
+        info!("The '{}' file has been deleted.", target_file_path_str);
+        Ok(true)
+    }
+
+    #[doc = "이름이 변경된 파일을 해시 저장소에 반영해주는 함수"]
+    /// # Arguments
+    /// * `from_file_path`  - 변경 전 파일의 경로
+    /// * `to_file_path`    - 변경 후 파일의 경로
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 내용 변화 없이 이름만 변경된 경우에 True, 내용까지 다른 경우에는 False
+    fn comparison_renamed_file(
+        &self,
+        from_file_path: &Path,
+        to_file_path: &Path,
+    ) -> Result<bool, anyhow::Error> {
+        let from_file_path_str: &str = from_file_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][comparison_renamed_file()] There was a problem converting 'from_file_path' to string."))?;
+
+        let to_file_path_str: &str = to_file_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][comparison_renamed_file()] There was a problem converting 'to_file_path' to string."))?;
+
+        let event_hash_val: Vec<u8> = conpute_hash(to_file_path).unwrap_or_else(|_| vec![]);
+
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let mut storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][comparison_renamed_file()] {:?}", e)),
+        };
+
+        /* 이전 파일의 해쉬값과 다르다면 이름변경만으로는 동기화할 수 없다. */
+        if event_hash_val.is_empty() || storage_hash.get_hash(from_file_path_str) != event_hash_val {
+            info!(
+                "The '{}' file was renamed to '{}' but its contents are different.",
+                from_file_path_str, to_file_path_str
+            );
+            return Ok(false);
+        }
+
+        let renamed_at: String = get_current_utc_naivedatetime_str("%Y-%m-%dT%H:%M:%SZ")?;
+        storage_hash.rename_hash(from_file_path_str, to_file_path_str.to_string(), renamed_at);
+        storage_hash.save()?;
+
+        info!(
+            "The '{}' file has been renamed to '{}'.",
+            from_file_path_str, to_file_path_str
+        );
+        Ok(true)
+    }
+
+    #[doc = "디렉토리 하위의 모든 파일과 해시값, 크기, 수정시각, 마지막 수신 버전 목록(manifest)을 만들어주는 함수"]
+    /// # Arguments
+    /// * `base_dir` - manifest 를 만들 기준 디렉토리 (slave 의 감시 디렉토리)
+    ///
+    /// # Returns
+    /// * Result<Vec<ManifestFileInfo>, anyhow::Error>
+    fn get_manifest_files(&self, base_dir: &Path) -> Result<Vec<ManifestFileInfo>, anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][get_manifest_files()] {:?}", e)),
+        };
+
+        let mut manifest_files: Vec<ManifestFileInfo> = Vec::new();
+
+        for file_path in get_all_file_paths(base_dir)? {
+            /* 수신중인 임시 파일은 제외한다. */
+            if is_staging_file(&file_path) {
+                continue;
+            }
+
+            let relative_path: String = match get_relative_path_str(base_dir, &file_path) {
+                Some(relative_path) => relative_path,
+                None => continue,
             };
 
-            /* 백업 디렉토리 날짜별 폴더 경로 */
-            let path: PathBuf = entry.path();
-
-            /* `path`가 디렉토리인 경우의 처리 */
-            if path.is_dir() {
-                if let Some(dir_name) = path.file_name().and_then(|name| name.to_str()) {
-                    /* 해당 폴더의 생성일자가 오늘 기준으로 며칠이 되었는지 확인해준다. */
-                    let days_diff = match calculate_date_difference_utc(dir_name) {
-                        Ok(days_diff) => days_diff,
-                        Err(e) => {
-                            error!("[Error][backup_file_delete()] {:?}", e);
-                            continue;
-                        }
-                    };
-
-                    /* 날짜가 보관 기간을 넘었을 경우 해당 디렉토리 삭제해준다. */
-                    if days_diff >= backup_days {
-                        match delete_directory(&path) {
-                            Ok(_) => (),
-                            Err(e) => {
-                                error!("[Error][backup_file_delete()] {:?}", e);
-                                continue;
-                            }
-                        }
-                    }
+            /* master 로부터 수신한 적이 없는 파일은 버전 정보가 존재하지 않는다. */
+            let receive_info: Option<ReceiveFileInfo> = receive_storage.get_file(&relative_path);
+
+            match self.read_manifest_file_info(
+                relative_path,
+                &file_path,
+                receive_info.as_ref().and_then(|info| *info.version()),
+                receive_info.map(|info| info.received_at().to_string()),
+            ) {
+                Ok(manifest_file) => manifest_files.push(manifest_file),
+                Err(e) => {
+                    error!("[Error][get_manifest_files()] {:?}", e);
+                    continue;
                 }
             }
-            /* `path` 가 디렉토리가 아닌 경우의 처리 -> 로깅만 수행 */
-            else {
-                warn!("It cannot be processed if it is not a `Directory`.");
-            }
         }
 
-        Ok(())
+        Ok(manifest_files)
     }
 
-    #[doc = "특정 파일을 백업 디렉토리 경로에 복사하는 코드"]
+    #[doc = "master 의 모니터링 대상 파일 목록(manifest)을 만들어주는 함수 - pull 모드에서 slave 가 조회한다."]
     /// # Arguments
-    /// * `backup_target_file_path`     - 동기화 대상이 될 파일 경로
-    /// * `backup_dir_path`             - 백업 디렉토리 경로
-    /// * `modified_file_name`          - 변경된 파일의 이름
+    /// * `monitor_file_list` - 모니터링 대상 파일 목록
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    fn copy_file_for_backup(
+    /// * Result<Vec<ManifestFileInfo>, anyhow::Error>
+    fn get_monitoring_manifest_files(
         &self,
-        backup_target_file_path: PathBuf,
-        backup_dir_path: &str,
-        modified_file_name: &str,
-    ) -> Result<(), anyhow::Error> {
-        let file_name: &str = backup_target_file_path
-            .file_name()
-            .ok_or_else(|| anyhow!("Invalid file name from path"))?
-            .to_str()
-            .ok_or_else(|| anyhow!("Non-UTF8 file name"))?;
-
-        /* 백업 폴더관련 로직 */
-        let cur_date: String = get_current_utc_naivedate_str("%Y%m%d")?;
-        let timestamp: String = get_current_utc_naivedatetime_str("%Y_%m_%d_%H%M%S")?;
-        let new_file_name: String = format!("{}.{}", modified_file_name, timestamp);
-
-        /* 백업 디렉토리 관련 */
-        let mut backup_file_path: PathBuf = PathBuf::from(backup_dir_path);
+        monitor_file_list: &[MonitoringPathInfo],
+    ) -> Result<Vec<ManifestFileInfo>, anyhow::Error> {
+        let preserve_owner: bool = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            server_config.server.is_preserve_owner()
+        };
 
-        self.backup_file_delete(&backup_file_path)?; /* 백업 파일 삭제 로직 -> 주기적으로 삭제해주는 함수 */
+        let mut manifest_files: Vec<ManifestFileInfo> = Vec::new();
 
-        backup_file_path.push(cur_date);
-        backup_file_path = create_dir_and_file(backup_file_path, &new_file_name)?;
+        for monitoring_file in monitor_file_list {
+            let version: u64 = self.get_storage_version(monitoring_file.full_file_path())?;
+            let full_file_path: &Path = Path::new(monitoring_file.full_file_path());
 
-        /* 동기화 대상 파일을 백업 디렉토리에 복사한다. */
-        fs::copy(&backup_target_file_path, backup_file_path.as_path())?;
+            match self.read_manifest_file_info(
+                monitoring_file.file_path().to_string(),
+                full_file_path,
+                Some(version),
+                None,
+            ) {
+                /* pull 모드의 slave 도 파일을 교체한 뒤 권한/소유자/수정시각을 적용할 수 있도록 포함한다. */
+                Ok(mut manifest_file) => {
+                    manifest_file.file_meta = get_file_meta(full_file_path, preserve_owner).ok();
+                    manifest_files.push(manifest_file);
+                }
+                Err(e) => {
+                    error!("[Error][get_monitoring_manifest_files()] {:?}", e);
+                    continue;
+                }
+            }
+        }
 
-        info!("Backup of file '{}' completed.", &file_name);
-        Ok(())
+        Ok(manifest_files)
     }
 
     #[doc = "파일 이벤트를 처리해주는 함수"]
     /// # Arguments
-    /// * `event`   - 모니터링 파일 관련 이벤트  
-    /// * `sender`  -  스레드 간 메시지 전달자
+    /// * `event`       - 모니터링 파일 관련 이벤트
+    /// * `sender`      - 스레드 간 메시지 전달자
+    /// * `event_type`  - 이벤트 종류 (Modify, Create, Remove, Rename)
     fn file_event_process(
         &self,
         event: &Event,
-        sender: &Sender<Result<String, String>>,
+        sender: &UnboundedSender<Result<FileEventInfo, String>>,
         event_type: &str,
     ) {
-        match event.paths[0].to_str() {
-            Some(file_path) => {
-                /*
-                    변경이 감지된 파일 경로를 파싱해주는 부분
-                    - windows os 의 경우 경로 앞에 의미없는 문자열이 붙는걸 확인. 해당 문자열을 제거해야함.
-                */
-                let cleaned_path: String = file_path.replace(r"\\?\", "");
+        /*
+            변경이 감지된 파일 경로를 파싱해주는 부분
+            - windows os 의 경우 경로 앞에 의미없는 문자열이 붙는걸 확인. 해당 문자열을 제거해야함.
+        */
+        let cleaned_paths: Option<Vec<String>> = event
+            .paths
+            .iter()
+            .map(|path| path.to_str().map(|path| path.replace(r"\\?\", "")))
+            .collect();
+
+        let file_event: Option<FileEventInfo> = match cleaned_paths.as_deref() {
+            /* 이름변경 이벤트의 경우 [이전 경로, 새로운 경로] 두개의 경로가 넘어온다. */
+            Some([from_path, to_path]) if event_type == "Rename" => Some(FileEventInfo::new(
+                event_type.to_string(),
+                to_path.to_string(),
+                Some(from_path.to_string()),
+            )),
+            Some([file_path, ..]) => Some(FileEventInfo::new(
+                event_type.to_string(),
+                file_path.to_string(),
+                None,
+            )),
+            _ => None,
+        };
 
+        match file_event {
+            Some(file_event) => {
                 info!(
-                    "[event type]: {}, [file name]: {}",
-                    event_type, cleaned_path
+                    "[event type]: {}, [file name]: {}, [from file name]: {:?}",
+                    event_type,
+                    file_event.file_path(),
+                    file_event.from_file_path()
                 );
 
-                sender.send(Ok(cleaned_path.clone())).unwrap_or_else(|err| {
+                sender.send(Ok(file_event)).unwrap_or_else(|err| {
                     error!(
                         "[Error][master_task()] Failed to send error message: {}",
                         err
@@ -200,3 +316,40 @@ impl FileService for FileServiceImpl {
         }
     }
 }
+
+impl FileServiceImpl {
+    #[doc = "파일 하나의 manifest 정보(해시값, 크기, 수정시각)를 읽어주는 함수"]
+    /// # Arguments
+    /// * `relative_path`   - manifest 에 기록될 상대경로
+    /// * `file_path`       - 실제 파일 경로
+    /// * `version`         - 파일 버전
+    /// * `received_at`     - 파일을 수신한 시각
+    ///
+    /// # Returns
+    /// * Result<ManifestFileInfo, anyhow::Error>
+    fn read_manifest_file_info(
+        &self,
+        relative_path: String,
+        file_path: &Path,
+        version: Option<u64>,
+        received_at: Option<String>,
+    ) -> Result<ManifestFileInfo, anyhow::Error> {
+        let hash: Vec<u8> = conpute_hash(file_path)
+            .map_err(|e| anyhow!("Failed to compute hash '{:?}' : {:?}", file_path, e))?;
+        let metadata: fs::Metadata = fs::metadata(file_path)
+            .map_err(|e| anyhow!("Failed to read metadata '{:?}' : {:?}", file_path, e))?;
+
+        let mtime: String =
+            get_utc_str_from_systemtime(metadata.modified()?, "%Y-%m-%dT%H:%M:%SZ")?;
+
+        Ok(ManifestFileInfo::new(
+            relative_path,
+            hash_to_hex(&hash),
+            metadata.len(),
+            mtime,
+            version,
+            received_at,
+            None,
+        ))
+    }
+}
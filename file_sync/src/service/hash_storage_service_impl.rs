@@ -0,0 +1,176 @@
+use crate::common::*;
+
+use crate::service::file_service_impl::*;
+
+use crate::utils_modules::hash_utils::*;
+
+use crate::repository::hash_repository::*;
+use crate::repository::history_repository::*;
+
+use crate::traits::service::hash_storage_service::*;
+
+use crate::configs::configs::*;
+
+#[async_trait]
+impl HashStorageService for FileServiceImpl {
+    #[doc = "해시 저장소에 저장되어 있는 파일의 해시값을 조회해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 조회할 파일의 경로 (해시 저장소의 키)
+    ///
+    /// # Returns
+    /// * Result<Vec<u8>, anyhow::Error> - 저장된 해시값이 없는 경우에는 empty vector
+    fn get_storage_hash(&self, file_path: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][get_storage_hash()] {:?}", e)),
+        };
+
+        Ok(storage_hash.get_hash(file_path))
+    }
+
+    #[doc = "해시 저장소에 저장되어 있는 파일의 버전을 조회해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 조회할 파일의 경로 (해시 저장소의 키)
+    ///
+    /// # Returns
+    /// * Result<u64, anyhow::Error> - 기록된 적이 없는 경우에는 0
+    fn get_storage_version(&self, file_path: &str) -> Result<u64, anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][get_storage_version()] {:?}", e)),
+        };
+
+        Ok(storage_hash.get_version(file_path))
+    }
+
+    #[doc = "해시 저장소에 해시값이 저장되어 있는 파일 목록을 조회해주는 함수"]
+    /// # Returns
+    /// * Result<Vec<String>, anyhow::Error> - 해시 저장소의 키(파일 경로) 목록
+    fn get_storage_file_list(&self) -> Result<Vec<String>, anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][get_storage_file_list()] {:?}", e)),
+        };
+
+        Ok(storage_hash.hashes.keys().cloned().collect())
+    }
+
+    #[doc = "마지막으로 감지한 해시값을 아직 수신하지 못한 slave 목록을 조회해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 조회할 파일의 경로 (해시 저장소의 키)
+    /// * `slave_url` - 확인할 slave 목록
+    ///
+    /// # Returns
+    /// * Result<Vec<String>, anyhow::Error>
+    fn get_unacked_slaves(&self, file_path: &str, slave_url: &[String]) -> Result<Vec<String>, anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][get_unacked_slaves()] {:?}", e)),
+        };
+
+        Ok(storage_hash.get_unacked_slaves(file_path, slave_url))
+    }
+
+    #[doc = "해시 저장소의 파일 버전을 주어진 버전 이상으로 맞춰주는 함수"]
+    /// # Arguments
+    /// * `file_path`   - 파일의 경로 (해시 저장소의 키)
+    /// * `min_version` - 맞춰줄 최소 버전
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 버전이 변경된 경우 true
+    fn rebase_storage_version(&self, file_path: &str, min_version: u64) -> Result<bool, anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let mut storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][rebase_storage_version()] {:?}", e)),
+        };
+
+        if !storage_hash.rebase_version(file_path, min_version) {
+            return Ok(false);
+        }
+
+        storage_hash.save()?;
+        Ok(true)
+    }
+
+    #[doc = "slave 가 파일을 수신했음을 해시 저장소에 기록해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 전달한 파일의 경로 (해시 저장소의 키)
+    /// * `slave_url` - 파일을 수신한 slave
+    /// * `hash`      - slave 가 수신한 파일의 해시값
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn update_acked_hash(&self, file_path: &str, slave_url: &str, hash: Vec<u8>) -> Result<(), anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let mut storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][update_acked_hash()] {:?}", e)),
+        };
+
+        storage_hash.update_acked_hash(file_path.to_string(), slave_url.to_string(), hash.clone());
+        storage_hash.save()?;
+        drop(storage_hash);
+
+        /* 배포 이력을 보관하는 경우 해당 버전을 수신한 slave 로 기록한다. -> 롤백 대상을 정할 때 사용된다. */
+        let history_enabled: bool = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            server_config.server.is_history_enabled()
+        };
+
+        if !history_enabled {
+            return Ok(());
+        }
+
+        let history_storage_guard: Arc<Mutex<HistoryStorage>> = get_history_storage();
+        let mut history_storage: MutexGuard<'_, HistoryStorage> = match history_storage_guard.lock() {
+            Ok(history_storage) => history_storage,
+            Err(e) => return Err(anyhow!("[Error][update_acked_hash()] {:?}", e)),
+        };
+
+        if history_storage.update_received_slave(file_path, slave_url, &hash_to_hex(&hash)) {
+            history_storage.save()?;
+        }
+
+        Ok(())
+    }
+
+    #[doc = "slave 의 수신 확인 정보를 해시 저장소에서 제거해주는 함수 - 삭제가 전달되었거나 slave 의 파일이 어긋난 경우"]
+    /// # Arguments
+    /// * `file_path` - 대상 파일의 경로 (해시 저장소의 키)
+    /// * `slave_url` - 대상 slave
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn remove_acked_hash(&self, file_path: &str, slave_url: &str) -> Result<(), anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let mut storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][remove_acked_hash()] {:?}", e)),
+        };
+
+        storage_hash.remove_acked_hash(file_path, slave_url);
+        storage_hash.save()
+    }
+
+    #[doc = "파일의 모든 slave 수신 확인 정보를 해시 저장소에서 제거해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 대상 파일의 경로 (해시 저장소의 키)
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn clear_acked_hashes(&self, file_path: &str) -> Result<(), anyhow::Error> {
+        let storage_hash_guard: Arc<Mutex<HashStorage>> = get_hash_storage();
+        let mut storage_hash: MutexGuard<'_, HashStorage> = match storage_hash_guard.lock() {
+            Ok(storage_hash) => storage_hash,
+            Err(e) => return Err(anyhow!("[Error][clear_acked_hashes()] {:?}", e)),
+        };
+
+        storage_hash.clear_acked_hashes(file_path);
+        storage_hash.save()
+    }
+}
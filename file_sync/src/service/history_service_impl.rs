@@ -0,0 +1,171 @@
+use crate::common::*;
+
+use crate::service::file_service_impl::*;
+
+use crate::utils_modules::io_utils::*;
+use crate::utils_modules::time_utils::*;
+
+use crate::repository::history_repository::*;
+use crate::repository::version_store_repository::*;
+
+use crate::traits::service::history_service::*;
+use crate::traits::service::receive_service::*;
+
+use crate::configs::configs::*;
+
+#[async_trait]
+impl HistoryService for FileServiceImpl {
+    #[doc = "slave 에 배포하는 파일의 버전을 배포 이력에 기록해주는 함수 - 파일 내용도 함께 보관된다."]
+    /// 이미 기록된 버전이거나 배포 이력을 보관하지 않는 경우에는 아무것도 하지 않는다.
+    ///
+    /// # Arguments
+    /// * `file_path`   - 배포하는 파일의 경로 (해시 저장소의 키)
+    /// * `file_name`   - 감시 디렉토리 기준 상대경로
+    /// * `version`     - 배포하는 파일의 버전
+    /// * `rollback_of` - 롤백으로 배포하는 경우 되돌린 대상 버전
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn record_history_version(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        version: u64,
+        rollback_of: Option<u64>,
+    ) -> Result<(), anyhow::Error> {
+        let (max_versions, compression): (usize, String) = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+
+            if !server_config.server.is_history_enabled() {
+                return Ok(());
+            }
+
+            (
+                server_config.server.get_history_max_versions(),
+                server_config.server.get_backup_compression(),
+            )
+        };
+
+        let history_storage_guard: Arc<Mutex<HistoryStorage>> = get_history_storage();
+        let mut history_storage: MutexGuard<'_, HistoryStorage> = match history_storage_guard.lock() {
+            Ok(history_storage) => history_storage,
+            Err(e) => return Err(anyhow!("[Error][record_history_version()] {:?}", e)),
+        };
+
+        if history_storage
+            .get_latest_version(file_path)
+            .is_some_and(|latest| *latest.version() >= version)
+        {
+            return Ok(());
+        }
+
+        let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(&history_storage.get_history_dir()?)?;
+        let mut version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+            Ok(version_store) => version_store,
+            Err(e) => return Err(anyhow!("[Error][record_history_version()] {:?}", e)),
+        };
+
+        /*
+            배포 이후에 파일이 다시 바뀌더라도 배포한 내용을 되돌릴 수 있도록 버전 저장소에 보관한다.
+            - 감시 디렉토리가 여러개일 수 있으므로 파일의 절대경로로 기록한다.
+            - 롤백으로 같은 내용이 다시 배포되더라도 내용은 한번만 저장된다.
+        */
+        let file_data: Vec<u8> = fs::read(file_path)?;
+        let pushed_at: String = get_current_utc_naivedatetime_str("%Y-%m-%dT%H:%M:%SZ")?;
+        let hash: String = version_store.put_blob(&file_data, &compression)?;
+
+        version_store.add_record(VersionRecord::new(
+            version.to_string(),
+            file_path.to_string(),
+            Some(version),
+            pushed_at.clone(),
+            if rollback_of.is_some() { "rollback" } else { "push" }.to_string(),
+            hash.clone(),
+            file_data.len() as u64,
+        ))?;
+
+        let history_version: HistoryVersion = HistoryVersion::new(
+            version,
+            hash,
+            file_name.to_string(),
+            pushed_at,
+            Vec::new(),
+            rollback_of,
+        );
+
+        let expired_versions: Vec<HistoryVersion> =
+            history_storage.push_version(file_path.to_string(), history_version, max_versions);
+
+        for expired_version in expired_versions {
+            if let Err(e) = version_store.remove_record(file_path, &expired_version.version().to_string()) {
+                warn!(
+                    "[Warn][record_history_version()] Failed to remove the expired version {} of {:?}: {:?}",
+                    expired_version.version(),
+                    file_path,
+                    e
+                );
+            }
+        }
+
+        version_store.save()?;
+        history_storage.save()
+    }
+
+    #[doc = "해당 파일의 배포 이력을 조회해주는 함수 - 오래된 버전부터"]
+    /// # Arguments
+    /// * `file_path` - 조회할 파일의 경로 (해시 저장소의 키)
+    ///
+    /// # Returns
+    /// * Result<Vec<HistoryVersion>, anyhow::Error>
+    fn get_history_versions(&self, file_path: &str) -> Result<Vec<HistoryVersion>, anyhow::Error> {
+        let history_storage_guard: Arc<Mutex<HistoryStorage>> = get_history_storage();
+        let history_storage: MutexGuard<'_, HistoryStorage> = match history_storage_guard.lock() {
+            Ok(history_storage) => history_storage,
+            Err(e) => return Err(anyhow!("[Error][get_history_versions()] {:?}", e)),
+        };
+
+        Ok(history_storage.get_versions(file_path))
+    }
+
+    #[doc = "master 의 파일을 배포 이력에 보관된 버전의 내용으로 원자적으로 교체해주는 함수"]
+    /// # Arguments
+    /// * `file_path`   - 교체할 파일의 경로 (해시 저장소의 키)
+    /// * `version`     - 되돌릴 버전
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 해당 버전의 사본이 존재하지 않는 경우 False
+    fn restore_history_version(&self, file_path: &Path, version: u64) -> Result<bool, anyhow::Error> {
+        let file_path_str: &str = file_path
+            .to_str()
+            .ok_or_else(|| anyhow!("[Error][restore_history_version()] There was a problem converting 'file_path' to string."))?;
+
+        let file_data: Vec<u8> = {
+            let history_storage_guard: Arc<Mutex<HistoryStorage>> = get_history_storage();
+            let history_storage: MutexGuard<'_, HistoryStorage> = match history_storage_guard.lock() {
+                Ok(history_storage) => history_storage,
+                Err(e) => return Err(anyhow!("[Error][restore_history_version()] {:?}", e)),
+            };
+
+            let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(&history_storage.get_history_dir()?)?;
+            let version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+                Ok(version_store) => version_store,
+                Err(e) => return Err(anyhow!("[Error][restore_history_version()] {:?}", e)),
+            };
+
+            match version_store.get_record(file_path_str, &version.to_string()) {
+                Some(version_record) => version_store.read_blob(version_record.hash())?,
+                None => return Ok(false),
+            }
+        };
+
+        let staged_file_path: PathBuf = self.write_staged_file(file_path, &file_data)?;
+
+        if let Err(e) = fs::rename(&staged_file_path, file_path) {
+            let _ = fs::remove_file(&staged_file_path);
+            return Err(anyhow!("[Error][restore_history_version()] {:?}", e));
+        }
+
+        sync_parent_dir(file_path)?;
+        Ok(true)
+    }
+}
@@ -1,2 +1,7 @@
+pub mod backup_service_impl;
 pub mod file_service_impl;
+pub mod hash_storage_service_impl;
+pub mod history_service_impl;
+pub mod outbox_service_impl;
+pub mod receive_service_impl;
 pub mod request_service_impl;
@@ -0,0 +1,106 @@
+use crate::common::*;
+
+use crate::service::file_service_impl::*;
+
+use crate::repository::outbox_repository::*;
+
+use crate::traits::service::outbox_service::*;
+
+#[async_trait]
+impl OutboxService for FileServiceImpl {
+    #[doc = "slave 에 전달하지 못한 작업을 outbox 에 기록해주는 함수 - 같은 slave 의 같은 파일에 대한 이전 작업은 교체된다."]
+    /// 이전 작업이 대기중이었다면 시도 횟수, 최초 실패 시각, 다음 재시도 시각은 그대로 이어진다.
+    ///
+    /// # Arguments
+    /// * `delivery` - 전달 대기 작업
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn enqueue_outbox_delivery(&self, delivery: OutboxDelivery) -> Result<(), anyhow::Error> {
+        let outbox_storage_guard: Arc<Mutex<OutboxStorage>> = get_outbox_storage();
+        let mut outbox_storage: MutexGuard<'_, OutboxStorage> = match outbox_storage_guard.lock() {
+            Ok(outbox_storage) => outbox_storage,
+            Err(e) => return Err(anyhow!("[Error][enqueue_outbox_delivery()] {:?}", e)),
+        };
+
+        outbox_storage.enqueue_delivery(delivery);
+        outbox_storage.save()
+    }
+
+    #[doc = "전달에 성공한 작업을 outbox 에서 제거해주는 함수"]
+    /// # Arguments
+    /// * `slave_url` - 전달 대상 slave 주소
+    /// * `file_name` - 전달한 파일의 이름
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn remove_outbox_delivery(&self, slave_url: &str, file_name: &str) -> Result<(), anyhow::Error> {
+        let outbox_storage_guard: Arc<Mutex<OutboxStorage>> = get_outbox_storage();
+        let mut outbox_storage: MutexGuard<'_, OutboxStorage> = match outbox_storage_guard.lock() {
+            Ok(outbox_storage) => outbox_storage,
+            Err(e) => return Err(anyhow!("[Error][remove_outbox_delivery()] {:?}", e)),
+        };
+
+        if outbox_storage
+            .remove_delivery(&get_outbox_key(slave_url, file_name))
+            .is_some()
+        {
+            outbox_storage.save()?;
+        }
+
+        Ok(())
+    }
+
+    #[doc = "재시도 시각이 된 전달 대기 작업 목록을 반환해주는 함수"]
+    /// # Arguments
+    /// * `now_secs` - 현재 UNIX time (초)
+    ///
+    /// # Returns
+    /// * Result<Vec<OutboxDelivery>, anyhow::Error>
+    fn get_due_outbox_deliveries(&self, now_secs: u64) -> Result<Vec<OutboxDelivery>, anyhow::Error> {
+        let outbox_storage_guard: Arc<Mutex<OutboxStorage>> = get_outbox_storage();
+        let outbox_storage: MutexGuard<'_, OutboxStorage> = match outbox_storage_guard.lock() {
+            Ok(outbox_storage) => outbox_storage,
+            Err(e) => return Err(anyhow!("[Error][get_due_outbox_deliveries()] {:?}", e)),
+        };
+
+        Ok(outbox_storage.get_due_deliveries(now_secs))
+    }
+
+    #[doc = "재시도한 작업의 결과를 outbox 에 반영해주는 함수"]
+    /// 재시도하는 동안 새로운 작업으로 교체된 경우에는 반영하지 않는다.
+    ///
+    /// # Arguments
+    /// * `prev_delivery` - 재시도한 작업
+    /// * `next_delivery` - 교체할 작업 (None 인 경우 제거)
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 반영된 경우 True
+    fn replace_outbox_delivery(
+        &self,
+        prev_delivery: &OutboxDelivery,
+        next_delivery: Option<OutboxDelivery>,
+    ) -> Result<bool, anyhow::Error> {
+        let outbox_storage_guard: Arc<Mutex<OutboxStorage>> = get_outbox_storage();
+        let mut outbox_storage: MutexGuard<'_, OutboxStorage> = match outbox_storage_guard.lock() {
+            Ok(outbox_storage) => outbox_storage,
+            Err(e) => return Err(anyhow!("[Error][replace_outbox_delivery()] {:?}", e)),
+        };
+
+        let key: String = prev_delivery.get_key();
+
+        if outbox_storage.get_delivery(&key).as_ref() != Some(prev_delivery) {
+            return Ok(false);
+        }
+
+        match next_delivery {
+            Some(next_delivery) => outbox_storage.update_delivery(next_delivery),
+            None => {
+                outbox_storage.remove_delivery(&key);
+            }
+        }
+
+        outbox_storage.save()?;
+        Ok(true)
+    }
+}
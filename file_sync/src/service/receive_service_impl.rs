@@ -0,0 +1,386 @@
+use crate::common::*;
+
+use crate::service::file_service_impl::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::io_utils::*;
+use crate::utils_modules::time_utils::*;
+
+use crate::repository::receive_repository::*;
+use crate::repository::version_store_repository::*;
+
+use crate::traits::service::receive_service::*;
+use crate::traits::service::backup_service::*;
+
+use crate::configs::configs::*;
+
+use crate::model::file_meta_info::*;
+
+#[async_trait]
+impl ReceiveService for FileServiceImpl {
+    #[doc = "수신 저장소에 기록되어 있는 파일 목록을 조회해주는 함수"]
+    /// # Returns
+    /// * Result<Vec<String>, anyhow::Error> - master 로부터 수신한 파일 이름(감시 디렉토리 기준 상대경로) 목록
+    fn get_receive_file_list(&self) -> Result<Vec<String>, anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][get_receive_file_list()] {:?}", e)),
+        };
+
+        Ok(receive_storage.files.keys().cloned().collect())
+    }
+
+    #[doc = "master 로부터 받은 파일 데이터를 대상 파일과 같은 디렉토리의 임시 파일에 기록해주는 함수"]
+    /// # Arguments
+    /// * `target_file_path`    - 최종적으로 교체될 파일 경로
+    /// * `file_data`           - 파일 데이터
+    ///
+    /// # Returns
+    /// * Result<PathBuf, anyhow::Error> - 디스크에 동기화(fsync)까지 완료된 임시 파일 경로
+    fn write_staged_file(&self, target_file_path: &Path, file_data: &[u8]) -> Result<PathBuf, anyhow::Error> {
+        create_parent_dir(target_file_path)?;
+
+        let staged_file_path: PathBuf = get_staging_file_path(target_file_path)?;
+
+        let write_res: Result<(), std::io::Error> = File::create(&staged_file_path)
+            .and_then(|mut staged_file| {
+                staged_file.write_all(file_data)?;
+                staged_file.sync_all()
+            });
+
+        if let Err(e) = write_res {
+            let _ = fs::remove_file(&staged_file_path);
+            return Err(anyhow!(
+                "[Error][write_staged_file()] Failed to write '{:?}' : {:?}",
+                staged_file_path,
+                e
+            ));
+        }
+
+        Ok(staged_file_path)
+    }
+
+    #[doc = "임시 파일을 대상 파일로 원자적으로 교체해주는 함수 - 기존 파일이 존재하면 교체 전에 백업해준다."]
+    /// 교체에 실패한 경우 임시 파일은 제거되고 기존 파일은 그대로 유지된다.
+    ///
+    /// # Arguments
+    /// * `staged_file_path`    - 수신이 완료된 임시 파일 경로
+    /// * `target_file_path`    - 교체 대상이 되는 파일 경로
+    /// * `backup_dir_path`     - 백업 디렉토리 경로
+    /// * `file_name`           - 대상 파일의 이름
+    /// * `backup_version`      - 교체되는 기존 파일의 버전 (알 수 없는 경우 None)
+    /// * `backup_source`       - 교체되는 기존 파일을 보낸 곳
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn commit_staged_file(
+        &self,
+        staged_file_path: &Path,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+        backup_version: Option<u64>,
+        backup_source: &str,
+    ) -> Result<(), anyhow::Error> {
+        let commit_res: Result<(), anyhow::Error> = (|| {
+            /* 수정파일이 기존 slave 에도 존재하는 파일일 경우에 백업시작 -> 기존에 존재하지 않는 경우에는 백업 불필요 */
+            if target_file_path.exists() {
+                self.copy_file_for_backup(
+                    target_file_path.to_path_buf(),
+                    backup_dir_path,
+                    file_name,
+                    backup_version,
+                    backup_source,
+                )?;
+            }
+
+            fs::rename(staged_file_path, target_file_path)?;
+            sync_parent_dir(target_file_path)
+        })();
+
+        if commit_res.is_err() && staged_file_path.exists() {
+            let _ = fs::remove_file(staged_file_path);
+        }
+
+        commit_res
+    }
+
+    #[doc = "master 로부터 받은 파일이 이미 가지고 있는 버전보다 오래된 것인지 확인해주는 함수"]
+    /// # Arguments
+    /// * `file_name`   - 수신한 파일의 이름 (감시 디렉토리 기준 상대경로)
+    /// * `version`     - 수신한 파일의 버전 (master 가 보내주지 않은 경우 None)
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 오래된 버전인 경우 True
+    fn is_stale_receive_version(&self, file_name: &str, version: Option<u64>) -> Result<bool, anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][is_stale_receive_version()] {:?}", e)),
+        };
+
+        Ok(receive_storage.is_stale_version(file_name, version))
+    }
+
+    #[doc = "수신이 완료된 임시 파일을 반영하고 수신 정보를 기록해주는 함수"]
+    /// 버전 확인부터 수신 정보 기록까지 수신 저장소를 잠근 상태로 진행하므로,
+    /// 같은 파일을 동시에 수신하더라도 오래된 버전이 나중에 반영되는 일은 없다.
+    ///
+    /// # Arguments
+    /// * `staged_file_path`    - 수신이 완료된 임시 파일 경로
+    /// * `target_file_path`    - 교체 대상이 되는 파일 경로
+    /// * `backup_dir_path`     - 백업 디렉토리 경로
+    /// * `file_name`           - 수신한 파일의 이름 (감시 디렉토리 기준 상대경로)
+    /// * `from_host`           - 파일을 보낸 호스트 주소
+    /// * `version`             - 수신한 파일의 버전 (master 가 보내주지 않은 경우 None)
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 오래된 버전이라 반영하지 않은 경우 False
+    fn commit_received_file(
+        &self,
+        staged_file_path: &Path,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+        from_host: &str,
+        version: Option<u64>,
+    ) -> Result<bool, anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let mut receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][commit_received_file()] {:?}", e)),
+        };
+
+        if receive_storage.is_stale_version(file_name, version) {
+            let _ = fs::remove_file(staged_file_path);
+            return Ok(false);
+        }
+
+        /* 교체되는 기존 파일은 마지막으로 수신한 버전으로 백업된다. */
+        let (backup_version, backup_source): (Option<u64>, String) = receive_storage.get_backup_source(file_name);
+
+        self.commit_staged_file(
+            staged_file_path,
+            target_file_path,
+            backup_dir_path,
+            file_name,
+            backup_version,
+            &backup_source,
+        )?;
+
+        let hash: Vec<u8> = conpute_hash(target_file_path)?;
+        let received_at: String = get_current_utc_naivedatetime_str("%Y-%m-%dT%H:%M:%SZ")?;
+
+        /* 버전 정보는 master 가 전달해준 경우에만 갱신된다. */
+        let version: Option<u64> = version.or_else(|| {
+            receive_storage
+                .get_file(file_name)
+                .and_then(|info| *info.version())
+        });
+
+        receive_storage.update_file(
+            file_name.to_string(),
+            ReceiveFileInfo::new(hash_to_hex(&hash), version, received_at, from_host.to_string()),
+        );
+        receive_storage.save()?;
+
+        /* slave 에서 직접 수정된 경우 되돌릴 수 있도록 수신한 파일의 사본을 남겨둔다. */
+        let last_good_copy_path: PathBuf = receive_storage.get_last_good_copy_path(file_name)?;
+        create_parent_dir(&last_good_copy_path)?;
+        fs::copy(target_file_path, &last_good_copy_path)?;
+
+        Ok(true)
+    }
+
+    #[doc = "master 로부터 마지막으로 수신한 파일의 정보(해시값, 버전)를 조회해주는 함수"]
+    /// # Arguments
+    /// * `file_name` - 조회할 파일의 이름 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<Option<ReceiveFileInfo>, anyhow::Error> - 수신한 적이 없는 파일인 경우 None
+    fn get_receive_file_info(&self, file_name: &str) -> Result<Option<ReceiveFileInfo>, anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][get_receive_file_info()] {:?}", e)),
+        };
+
+        Ok(receive_storage.get_file(file_name))
+    }
+
+    #[doc = "교체된 파일에 master 파일의 권한, 소유자, 수정시각 정보를 적용해주는 함수"]
+    /// slave 에 file_meta_override 가 설정된 경우 해당 권한/소유자가 우선한다.
+    ///
+    /// # Arguments
+    /// * `target_file_path`    - 교체된 파일 경로
+    /// * `file_meta`           - master 가 보낸 파일 메타정보
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn apply_received_file_meta(&self, target_file_path: &Path, file_meta: &FileMetaInfo) -> Result<(), anyhow::Error> {
+        let file_meta: FileMetaInfo = {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+
+            match server_config.server.file_meta_override() {
+                Some(file_meta_override) => file_meta_override.apply(file_meta)?,
+                None => file_meta.clone(),
+            }
+        };
+
+        apply_file_meta(target_file_path, &file_meta)
+    }
+
+    #[doc = "slave 에서 직접 수정된 파일을 마지막으로 수신한 사본으로 되돌려주는 함수 - 수정된 파일은 백업된다."]
+    /// # Arguments
+    /// * `target_file_path`    - 되돌릴 파일 경로
+    /// * `backup_dir_path`     - 백업 디렉토리 경로
+    /// * `file_name`           - 되돌릴 파일의 이름 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 사본이 존재하지 않아 되돌리지 못한 경우 False
+    fn restore_last_good_copy(
+        &self,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+    ) -> Result<bool, anyhow::Error> {
+        /* master 가 보낸 삭제나 이름변경이 진행되는 동안에는 기다렸다가, 수신 정보가 남아있는 경우에만 되돌린다. */
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][restore_last_good_copy()] {:?}", e)),
+        };
+
+        if receive_storage.get_file(file_name).is_none() {
+            return Ok(false);
+        }
+
+        let last_good_copy_path: PathBuf = receive_storage.get_last_good_copy_path(file_name)?;
+
+        if !last_good_copy_path.exists() {
+            return Ok(false);
+        }
+
+        let file_data: Vec<u8> = fs::read(&last_good_copy_path)?;
+        let staged_file_path: PathBuf = self.write_staged_file(target_file_path, &file_data)?;
+
+        /* slave 에서 직접 수정된 내용이 백업된다. */
+        self.commit_staged_file(
+            &staged_file_path,
+            target_file_path,
+            backup_dir_path,
+            file_name,
+            None,
+            "local",
+        )?;
+
+        Ok(true)
+    }
+
+    #[doc = "반영을 취소한 새 파일을 수신 정보와 함께 제거해주는 함수"]
+    /// 로컬 변경 감지가 제거된 파일을 되돌리지 않도록 파일 제거와 수신 정보 제거는 수신 저장소를 잠근 상태로 진행한다.
+    ///
+    /// # Arguments
+    /// * `target_file_path`    - 제거할 파일 경로
+    /// * `file_name`           - 제거할 파일의 이름 (감시 디렉토리 기준 상대경로)
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn remove_received_file(&self, target_file_path: &Path, file_name: &str) -> Result<(), anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let mut receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][remove_received_file()] {:?}", e)),
+        };
+
+        if target_file_path.exists() {
+            fs::remove_file(target_file_path)?;
+            sync_parent_dir(target_file_path)?;
+        }
+
+        receive_storage.remove_receive_info(file_name)
+    }
+
+    #[doc = "방금 반영한 파일을 교체하면서 남긴 백업으로 되돌려주는 함수 - 반영을 취소하는 것이므로 현재 파일은 백업하지 않는다."]
+    /// 수신 정보도 백업된 내용의 버전과 보낸 곳으로 되돌린다. (수신한 적이 없던 파일이라면 수신 정보를 제거한다.)
+    ///
+    /// # Arguments
+    /// * `target_file_path`    - 되돌릴 파일 경로
+    /// * `backup_dir_path`     - 백업 디렉토리 경로
+    /// * `file_name`           - 되돌릴 파일의 이름 (감시 디렉토리 기준 상대경로)
+    /// * `backup_id`           - 반영하면서 남긴 백업 시각
+    ///
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 백업이 존재하지 않아 되돌리지 못한 경우 False
+    fn revert_received_file(
+        &self,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+        backup_id: &str,
+    ) -> Result<bool, anyhow::Error> {
+        let receive_storage_guard: Arc<Mutex<ReceiveStorage>> = get_receive_storage();
+        let mut receive_storage: MutexGuard<'_, ReceiveStorage> = match receive_storage_guard.lock() {
+            Ok(receive_storage) => receive_storage,
+            Err(e) => return Err(anyhow!("[Error][revert_received_file()] {:?}", e)),
+        };
+
+        let (version_record, file_data): (VersionRecord, Vec<u8>) = {
+            let version_store_guard: Arc<Mutex<VersionStore>> = get_version_store(Path::new(backup_dir_path))?;
+            let version_store: MutexGuard<'_, VersionStore> = match version_store_guard.lock() {
+                Ok(version_store) => version_store,
+                Err(e) => return Err(anyhow!("[Error][revert_received_file()] {:?}", e)),
+            };
+
+            match version_store.get_record(file_name, backup_id) {
+                Some(version_record) => {
+                    let file_data: Vec<u8> = version_store.read_blob(version_record.hash())?;
+                    (version_record, file_data)
+                }
+                None => return Ok(false),
+            }
+        };
+
+        let staged_file_path: PathBuf = self.write_staged_file(target_file_path, &file_data)?;
+
+        if let Err(e) = fs::rename(&staged_file_path, target_file_path) {
+            let _ = fs::remove_file(&staged_file_path);
+            return Err(anyhow!("[Error][revert_received_file()] {:?}", e));
+        }
+        sync_parent_dir(target_file_path)?;
+
+        let last_good_copy_path: PathBuf = receive_storage.get_last_good_copy_path(file_name)?;
+
+        if version_record.source() == UNKNOWN_BACKUP_SOURCE {
+            receive_storage.remove_file(file_name);
+            receive_storage.save()?;
+
+            if last_good_copy_path.exists() {
+                fs::remove_file(&last_good_copy_path)?;
+            }
+        } else {
+            receive_storage.update_file(
+                file_name.to_string(),
+                ReceiveFileInfo::new(
+                    version_record.hash.clone(),
+                    version_record.version,
+                    get_current_utc_naivedatetime_str("%Y-%m-%dT%H:%M:%SZ")?,
+                    version_record.source.clone(),
+                ),
+            );
+            receive_storage.save()?;
+
+            create_parent_dir(&last_good_copy_path)?;
+            fs::copy(target_file_path, &last_good_copy_path)?;
+        }
+
+        info!(
+            "The file '{}' was reverted to the backup '{}'. version: {:?}",
+            file_name,
+            backup_id,
+            version_record.version()
+        );
+        Ok(true)
+    }
+}
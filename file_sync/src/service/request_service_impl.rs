@@ -4,6 +4,14 @@ use crate::configs::configs::*;
 
 use crate::traits::service::request_service::*;
 
+use crate::model::analyzer_reload_result::*;
+use crate::model::manifest_info::*;
+use crate::model::transfer_info::*;
+
+use crate::utils_modules::hash_utils::*;
+use crate::utils_modules::io_utils::*;
+
+use crate::external_clients::elastic_client::*;
 use crate::external_clients::file_transfer_client::*;
 use crate::external_clients::secure_file_transfer_client::*;
 
@@ -12,50 +20,160 @@ pub struct RequestServiceImpl;
 
 #[async_trait]
 impl RequestService for RequestServiceImpl {
-    #[doc = "master server 에서 파일이 변경되는 경우 해당 변경 정보를 slave server에 공유해준다."]
+    #[doc = "수정된 파일을 지정한 slave server 들에만 공유해준다."]
     /// # Arguments
     /// * `file_path` - 수정된 파일경로
     /// * `file_name` - 수정된 파일이름
+    /// * `slave_url` - 동기화 대상이 되는 서버들
+    /// * `content_hash` - 변경 감지 시 해시 저장소에 기록된 파일의 해시값 (16진수)
+    /// * `file_version` - 수정된 파일의 버전
     ///
     /// # Returns
     /// * Result<(), anyhow::Error>
-    async fn send_info_to_slave(
+    async fn send_info_to_target_slave(
         &self,
         file_path: &str,
         file_name: &str,
+        slave_url: Vec<String>,
+        content_hash: &str,
+        file_version: u64,
     ) -> Result<(), anyhow::Error> {
-        let slave_url: Vec<String>;
         let io_improvement_option: bool; /* io 효율코드 옵션 적용 유무 */
         let secure_mode: bool; /* 보안모드 적용 유무 */
         {
             let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
-            slave_url = server_config
-                .server
-                .slave_address()
-                .clone()
-                .ok_or_else(|| anyhow!("[Error][send_info_to_slave()] 'slave_url' not found."))?;
-
             io_improvement_option = *server_config.server.io_bound_improvement();
             secure_mode = server_config.server.is_secure_mode();
         }
 
         if io_improvement_option {
-            self.send_info_to_slave_io(file_path, file_name, slave_url.clone(), secure_mode)
+            self.send_info_to_slave_io(file_path, file_name, slave_url, content_hash, file_version, secure_mode)
                 .await?;
         } else {
             /* io 효율코드 옵션을 적용하지 않으면 메모리 효율코드 옵션이 지정된다. */
-            self.send_info_to_slave_memory(file_path, file_name, slave_url.clone(), secure_mode)
+            self.send_info_to_slave_memory(file_path, file_name, slave_url, content_hash, file_version, secure_mode)
                 .await?;
         }
 
         Ok(())
     }
-    
+
+    #[doc = "slave server 가 현재 가지고 있는 파일 목록(manifest)을 조회해준다."]
+    /// # Arguments
+    /// * `slave_url` - 조회 대상이 되는 slave 서버
+    ///
+    /// # Returns
+    /// * Result<ManifestInfo, anyhow::Error>
+    async fn get_slave_manifest(&self, slave_url: &str) -> Result<ManifestInfo, anyhow::Error> {
+        let secure_mode: bool;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            secure_mode = server_config.server.is_secure_mode();
+        }
+
+        let protocol: &str = if secure_mode { "https" } else { "http" };
+        let parsing_url: String = format!("{}://{}/manifest", protocol, slave_url);
+
+        if secure_mode {
+            let req_repo: Arc<SecureFileTransferClient> = get_secure_request_client();
+            req_repo.get_json_from_url::<ManifestInfo>(&parsing_url).await
+        } else {
+            let req_repo: Arc<FileTransferClient> = get_request_client();
+            req_repo.get_json_from_url::<ManifestInfo>(&parsing_url).await
+        }
+    }
+
+    #[doc = "pull 모드 - master server 가 현재 배포중인 파일 목록(manifest)을 조회해준다."]
+    /// # Arguments
+    /// * `master_url` - 조회 대상이 되는 master 서버
+    ///
+    /// # Returns
+    /// * Result<ManifestInfo, anyhow::Error>
+    async fn get_master_manifest(&self, master_url: &str) -> Result<ManifestInfo, anyhow::Error> {
+        /* master 의 manifest 라우트는 slave 와 동일한 형식이다. */
+        self.get_slave_manifest(master_url).await
+    }
+
+    #[doc = "pull 모드 - master server 로부터 파일 데이터를 내려받는다."]
+    /// # Arguments
+    /// * `master_url` - 조회 대상이 되는 master 서버
+    /// * `file_name`  - 내려받을 파일이름 (manifest 의 file_path)
+    ///
+    /// # Returns
+    /// * Result<Vec<u8>, anyhow::Error>
+    async fn get_master_file(&self, master_url: &str, file_name: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let secure_mode: bool;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            secure_mode = server_config.server.is_secure_mode();
+        }
+
+        let protocol: &str = if secure_mode { "https" } else { "http" };
+        let parsing_url: String = format!("{}://{}/download?filename={}", protocol, master_url, file_name);
+
+        if secure_mode {
+            let req_repo: Arc<SecureFileTransferClient> = get_secure_request_client();
+            req_repo.get_bytes_from_url(&parsing_url).await
+        } else {
+            let req_repo: Arc<FileTransferClient> = get_request_client();
+            req_repo.get_bytes_from_url(&parsing_url).await
+        }
+    }
+
+    #[doc = "slave 에서 직접 수정된 파일을 검토용으로 master server 에 보내준다."]
+    /// # Arguments
+    /// * `master_url`   - 검토 요청을 받을 master 서버
+    /// * `file_path`    - 수정된 파일경로
+    /// * `file_name`    - 수정된 파일이름 (감시 디렉토리 기준 상대경로)
+    /// * `file_version` - 마지막으로 수신한 파일의 버전
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn send_review_to_master(
+        &self,
+        master_url: &str,
+        file_path: &str,
+        file_name: &str,
+        file_version: u64,
+    ) -> Result<(), anyhow::Error> {
+        let secure_mode: bool;
+        let from_host: String;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            secure_mode = server_config.server.is_secure_mode();
+            from_host = server_config.server.host().to_string();
+        }
+
+        let file_data: Vec<u8> = tokio::fs::read(&file_path).await?;
+        let transfer_info: TransferInfo = TransferInfo::new(
+            hash_to_hex(&compute_hash_from_bytes(&file_data)),
+            file_version,
+            get_file_meta(Path::new(file_path), false)?,
+        );
+
+        let protocol: &str = if secure_mode { "https" } else { "http" };
+        let parsing_url: String = format!("{}://{}/review?filename={}", protocol, master_url, file_name);
+
+        if secure_mode {
+            let req_repo: Arc<SecureFileTransferClient> = get_secure_request_client();
+            req_repo
+                .send_file_to_url(&parsing_url, &file_data, &transfer_info, file_path, &from_host, master_url)
+                .await
+        } else {
+            let req_repo: Arc<FileTransferClient> = get_request_client();
+            req_repo
+                .send_file_to_url(&parsing_url, &file_data, &transfer_info, file_path, &from_host, master_url)
+                .await
+        }
+    }
+
     #[doc = "i/o bound 효율코드"]
     /// # Arguments
     /// * `file_path` - 수정된 파일 경로
     /// * `file_name` - 수정된 파일 이름
     /// * `slave_url` - 동기화 대상이 되는 서버들
+    /// * `content_hash` - 변경 감지 시 해시 저장소에 기록된 파일의 해시값 (16진수)
+    /// * `file_version` - 수정된 파일의 버전
     /// * `secure_mode` - TLS 사용 여부
     ///
     /// # Returns
@@ -65,21 +183,35 @@ impl RequestService for RequestServiceImpl {
         file_path: &str,
         file_name: &str,
         slave_url: Vec<String>,
+        content_hash: &str,
+        file_version: u64,
         secure_mode: bool,
     ) -> Result<(), anyhow::Error> {
         /* 변경된 파일의 데이터를 read 하여 메모리에 상주시킨다. */
         let file_data: Vec<u8> = tokio::fs::read(&file_path).await?;
+        verify_content_hash(file_path, &file_data, content_hash)?;
+
         let from_host: String;
+        let preserve_owner: bool;
         {
             let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
             from_host = server_config.server.host().to_string();
+            preserve_owner = server_config.server.is_preserve_owner();
         }
+
+        /* 파일 내용과 함께 권한/소유자/수정시각 정보를 보내 slave 에도 동일하게 적용되도록 한다. */
+        let transfer_info: TransferInfo = TransferInfo::new(
+            content_hash.to_string(),
+            file_version,
+            get_file_meta(Path::new(file_path), preserve_owner)?,
+        );
         
         /* Slave Server 는 지금 Actix-web 으로 작동되고 있으므로 api 형식을 사용할때처럼 데이터를 송신해주는 것. */
         let tasks: Vec<_> = slave_url
             .into_iter()
             .map(|url: String| {
                 let data_clone: Vec<u8> = file_data.clone(); /* 변경된 파일 데이터 복제: 소유권으로 인한 문제 */
+                let transfer_info_clone: TransferInfo = transfer_info.clone();
                 let protocol: &str = if secure_mode { "https" } else { "http" };
                 let parsing_url: String = format!("{}://{}/upload?filename={}", protocol, url, file_name);
                 let file_path: String = file_path.to_string().clone();
@@ -94,6 +226,7 @@ impl RequestService for RequestServiceImpl {
                             .send_file_to_url(
                                 &parsing_url,
                                 &data_clone,
+                                &transfer_info_clone,
                                 &file_path,
                                 &from_host_move_clone,
                                 &url,
@@ -105,6 +238,7 @@ impl RequestService for RequestServiceImpl {
                             .send_file_to_url(
                                 &parsing_url,
                                 &data_clone,
+                                &transfer_info_clone,
                                 &file_path,
                                 &from_host_move_clone,
                                 &url,
@@ -125,6 +259,8 @@ impl RequestService for RequestServiceImpl {
     /// * `file_path` - 수정된 파일 경로
     /// * `file_name` - 수정된 파일 이름
     /// * `slave_url` - 동기화 대상이 되는 서버들
+    /// * `content_hash` - 변경 감지 시 해시 저장소에 기록된 파일의 해시값 (16진수)
+    /// * `file_version` - 수정된 파일의 버전
     /// * `secure_mode` - TLS 사용 여부
     ///
     /// # Returns
@@ -134,12 +270,16 @@ impl RequestService for RequestServiceImpl {
         file_path: &str,
         file_name: &str,
         slave_url: Vec<String>,
+        content_hash: &str,
+        file_version: u64,
         secure_mode: bool,
     ) -> Result<(), anyhow::Error> {
         let from_host: String;
+        let preserve_owner: bool;
         {
             let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
             from_host = server_config.server.host().to_string();
+            preserve_owner = server_config.server.is_preserve_owner();
         }
 
         let tasks: Vec<_> = slave_url
@@ -148,10 +288,18 @@ impl RequestService for RequestServiceImpl {
                 let protocol: &str = if secure_mode { "https" } else { "http" };
                 let parsing_url: String = format!("{}://{}/upload?filename={}", protocol, url, file_name);
                 let file_path: String = file_path.to_string().clone();
+                let content_hash: String = content_hash.to_string();
                 let from_host_clone: String = from_host.clone();
 
                 task::spawn(async move {
                     let file_data: Vec<u8> = tokio::fs::read(&file_path).await?;
+                    verify_content_hash(&file_path, &file_data, &content_hash)?;
+
+                    let transfer_info: TransferInfo = TransferInfo::new(
+                        content_hash,
+                        file_version,
+                        get_file_meta(Path::new(&file_path), preserve_owner)?,
+                    );
                     let from_host_move_clone: String = from_host_clone.clone();
                     
                     if secure_mode {
@@ -160,6 +308,7 @@ impl RequestService for RequestServiceImpl {
                             .send_file_to_url(
                                 &parsing_url,
                                 &file_data,
+                                &transfer_info,
                                 &file_path,
                                 &from_host_move_clone,
                                 &url,
@@ -171,6 +320,7 @@ impl RequestService for RequestServiceImpl {
                             .send_file_to_url(
                                 &parsing_url,
                                 &file_data,
+                                &transfer_info,
                                 &file_path,
                                 &from_host_move_clone,
                                 &url,
@@ -187,6 +337,151 @@ impl RequestService for RequestServiceImpl {
         self.handle_async_function(results)
     }
 
+    #[doc = "파일 삭제 정보를 지정한 slave server 들에만 공유해준다."]
+    /// # Arguments
+    /// * `file_path` - 삭제된 파일경로
+    /// * `file_name` - 삭제된 파일이름
+    /// * `slave_url` - 동기화 대상이 되는 서버들
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn send_delete_to_target_slave(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        slave_url: Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        let query: String = format!("delete?filename={}", file_name);
+        self.send_operation_to_slave(file_path, "delete", &query, slave_url).await
+    }
+
+    #[doc = "master server 에서 파일 이름이 변경되는 경우 해당 정보를 slave server에 공유해준다. -> 파일 내용은 다시 보내지 않는다."]
+    /// # Arguments
+    /// * `file_path`       - 이름이 변경된 파일경로
+    /// * `from_file_name`  - 변경 전 파일이름
+    /// * `to_file_name`    - 변경 후 파일이름
+    /// * `slave_url`       - 이름변경을 전달할 서버들
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn send_rename_to_slave(
+        &self,
+        file_path: &str,
+        from_file_name: &str,
+        to_file_name: &str,
+        slave_url: Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        let query: String = format!(
+            "rename?from_filename={}&to_filename={}",
+            from_file_name, to_file_name
+        );
+        self.send_operation_to_slave(file_path, "rename", &query, slave_url).await
+    }
+
+    #[doc = "파일 내용 없이 작업(삭제, 이름변경)만 slave server 에 공유해주는 함수"]
+    /// # Arguments
+    /// * `file_path` - 대상 파일경로
+    /// * `operation` - 작업 이름
+    /// * `query`     - slave 의 라우트와 쿼리 문자열 (예: delete?filename=a.txt)
+    /// * `slave_url` - 동기화 대상이 되는 서버들
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn send_operation_to_slave(
+        &self,
+        file_path: &str,
+        operation: &str,
+        query: &str,
+        slave_url: Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        let secure_mode: bool; /* 보안모드 적용 유무 */
+        let from_host: String;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            secure_mode = server_config.server.is_secure_mode();
+            from_host = server_config.server.host().to_string();
+        }
+
+        let tasks: Vec<_> = slave_url
+            .into_iter()
+            .map(|url| {
+                let protocol: &str = if secure_mode { "https" } else { "http" };
+                let parsing_url: String = format!("{}://{}/{}", protocol, url, query);
+                let operation: String = operation.to_string();
+                let file_path: String = file_path.to_string();
+                let from_host_clone: String = from_host.clone();
+
+                task::spawn(async move {
+                    if secure_mode {
+                        let req_repo: Arc<SecureFileTransferClient> = get_secure_request_client();
+                        req_repo
+                            .send_operation_to_url(
+                                &parsing_url,
+                                &operation,
+                                &file_path,
+                                &from_host_clone,
+                                &url,
+                            )
+                            .await
+                    } else {
+                        let req_repo: Arc<FileTransferClient> = get_request_client();
+                        req_repo
+                            .send_operation_to_url(
+                                &parsing_url,
+                                &operation,
+                                &file_path,
+                                &from_host_clone,
+                                &url,
+                            )
+                            .await
+                    }
+                })
+            })
+            .collect();
+
+        let results: Vec<Result<Result<(), anyhow::Error>, task::JoinError>> =
+            join_all(tasks).await;
+
+        self.handle_async_function(results)
+    }
+
+    #[doc = "slave 로컬 Elasticsearch 인덱스의 검색 분석기를 리로드해주는 함수"]
+    /// 요청이 실패하더라도 에러를 반환하지 않고 실패한 결과로 기록한다.
+    ///
+    /// # Arguments
+    /// * `elastic_url` - Elasticsearch 주소
+    /// * `index`       - 리로드할 인덱스
+    ///
+    /// # Returns
+    /// * Result<AnalyzerReloadResult, anyhow::Error>
+    async fn reload_search_analyzers(
+        &self,
+        elastic_url: &str,
+        index: &str,
+    ) -> Result<AnalyzerReloadResult, anyhow::Error> {
+        let elastic_id: Option<String>;
+        let elastic_pw: Option<String>;
+        {
+            let server_config: RwLockReadGuard<'_, Configs> = get_config_read()?;
+            elastic_id = server_config.server.elastic_id().clone();
+            elastic_pw = server_config.server.elastic_pw().clone();
+        }
+
+        let elastic_client: Arc<ElasticClient> = get_elastic_client();
+
+        match elastic_client
+            .reload_search_analyzers(elastic_url, index, elastic_id.as_deref(), elastic_pw.as_deref())
+            .await
+        {
+            Ok((status_code, response_body)) => Ok(AnalyzerReloadResult::from_response(
+                index,
+                status_code,
+                &response_body,
+            )),
+            Err(e) => Ok(AnalyzerReloadResult::from_request_error(index, &e)),
+        }
+    }
+
     #[doc = "async 함수들의 결과를 파싱해주는 함수"]
     /// # Arguments
     /// * `task_res` - 비동기 함수의 결과
@@ -226,3 +521,28 @@ impl RequestService for RequestServiceImpl {
         }
     }
 }
+
+#[doc = "전송하려고 읽은 파일 내용이 변경 감지 시점의 해시값과 같은지 확인해주는 함수"]
+/// 비교 이후 파일이 다시 수정되었다면 보내지 않는다. -> 수정된 내용은 다음 이벤트에서 새 해시값으로 전달된다.
+///
+/// # Arguments
+/// * `file_path`    - 전송할 파일경로
+/// * `file_data`    - 전송하려고 읽은 파일 내용
+/// * `content_hash` - 변경 감지 시 해시 저장소에 기록된 파일의 해시값 (16진수)
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+fn verify_content_hash(file_path: &str, file_data: &[u8], content_hash: &str) -> Result<(), anyhow::Error> {
+    let read_hash: String = hash_to_hex(&compute_hash_from_bytes(file_data));
+
+    if !read_hash.eq_ignore_ascii_case(content_hash) {
+        return Err(anyhow!(
+            "[Error][verify_content_hash()] The file '{}' has changed since it was compared. expected: {} // read: {}",
+            file_path,
+            content_hash,
+            read_hash
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::common::*;
+
+use crate::model::backup_info::*;
+
+#[async_trait]
+pub trait BackupService {
+    fn copy_file_for_backup(
+        &self,
+        backup_target_file_path: PathBuf,
+        backup_dir_path: &str,
+        modified_file_name: &str,
+        backup_version: Option<u64>,
+        backup_source: &str,
+    ) -> Result<(), anyhow::Error>;
+    fn delete_file_with_backup(
+        &self,
+        delete_target_file_path: PathBuf,
+        backup_dir_path: &str,
+        deleted_file_name: &str,
+    ) -> Result<bool, anyhow::Error>;
+    fn rename_file_with_backup(
+        &self,
+        from_file_path: PathBuf,
+        to_file_path: PathBuf,
+        backup_dir_path: &str,
+        from_file_name: &str,
+        to_file_name: &str,
+    ) -> Result<(), anyhow::Error>;
+    fn backup_file_delete(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error>;
+    fn import_legacy_backups(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error>;
+    fn apply_backup_retention(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error>;
+    fn get_backup_file_list(&self, backup_dir_path: &str, file_name: &str) -> Result<Vec<BackupFileInfo>, anyhow::Error>;
+    fn get_backup_file_data(
+        &self,
+        backup_dir_path: &str,
+        file_name: &str,
+        backup_id: &str,
+    ) -> Result<Option<Vec<u8>>, anyhow::Error>;
+}
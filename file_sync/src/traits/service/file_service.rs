@@ -1,19 +1,27 @@
 use crate::common::*;
 
+use crate::model::file_event_info::*;
+use crate::model::manifest_info::*;
+use crate::model::monitoring_path_info::*;
+
 #[async_trait]
 pub trait FileService {
-    fn comparison_file(&self, file_path_slice: &Path) -> Result<bool, anyhow::Error>;
-    fn copy_file_for_backup(
+    fn comparison_file(&self, file_path_slice: &Path, slave_url: &[String]) -> Result<bool, anyhow::Error>;
+    fn comparison_deleted_file(&self, file_path_slice: &Path) -> Result<bool, anyhow::Error>;
+    fn comparison_renamed_file(
+        &self,
+        from_file_path: &Path,
+        to_file_path: &Path,
+    ) -> Result<bool, anyhow::Error>;
+    fn get_manifest_files(&self, base_dir: &Path) -> Result<Vec<ManifestFileInfo>, anyhow::Error>;
+    fn get_monitoring_manifest_files(
         &self,
-        backup_target_file_path: PathBuf,
-        backup_dir_path: &str,
-        modified_file_name: &str,
-    ) -> Result<(), anyhow::Error>;
-    fn backup_file_delete(&self, backup_file_dir: &Path) -> Result<(), anyhow::Error>;
+        monitor_file_list: &[MonitoringPathInfo],
+    ) -> Result<Vec<ManifestFileInfo>, anyhow::Error>;
     fn file_event_process(
         &self,
         event: &Event,
-        sender: &Sender<Result<String, String>>,
+        sender: &UnboundedSender<Result<FileEventInfo, String>>,
         event_type: &str,
     );
 }
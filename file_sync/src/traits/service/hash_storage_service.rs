@@ -0,0 +1,13 @@
+use crate::common::*;
+
+#[async_trait]
+pub trait HashStorageService {
+    fn get_storage_hash(&self, file_path: &str) -> Result<Vec<u8>, anyhow::Error>;
+    fn get_storage_version(&self, file_path: &str) -> Result<u64, anyhow::Error>;
+    fn get_storage_file_list(&self) -> Result<Vec<String>, anyhow::Error>;
+    fn get_unacked_slaves(&self, file_path: &str, slave_url: &[String]) -> Result<Vec<String>, anyhow::Error>;
+    fn rebase_storage_version(&self, file_path: &str, min_version: u64) -> Result<bool, anyhow::Error>;
+    fn update_acked_hash(&self, file_path: &str, slave_url: &str, hash: Vec<u8>) -> Result<(), anyhow::Error>;
+    fn remove_acked_hash(&self, file_path: &str, slave_url: &str) -> Result<(), anyhow::Error>;
+    fn clear_acked_hashes(&self, file_path: &str) -> Result<(), anyhow::Error>;
+}
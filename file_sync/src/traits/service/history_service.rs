@@ -0,0 +1,16 @@
+use crate::common::*;
+
+use crate::repository::history_repository::*;
+
+#[async_trait]
+pub trait HistoryService {
+    fn record_history_version(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        version: u64,
+        rollback_of: Option<u64>,
+    ) -> Result<(), anyhow::Error>;
+    fn get_history_versions(&self, file_path: &str) -> Result<Vec<HistoryVersion>, anyhow::Error>;
+    fn restore_history_version(&self, file_path: &Path, version: u64) -> Result<bool, anyhow::Error>;
+}
@@ -1,2 +1,7 @@
+pub mod backup_service;
 pub mod file_service;
+pub mod hash_storage_service;
+pub mod history_service;
+pub mod outbox_service;
+pub mod receive_service;
 pub mod request_service;
@@ -0,0 +1,15 @@
+use crate::common::*;
+
+use crate::repository::outbox_repository::*;
+
+#[async_trait]
+pub trait OutboxService {
+    fn enqueue_outbox_delivery(&self, delivery: OutboxDelivery) -> Result<(), anyhow::Error>;
+    fn remove_outbox_delivery(&self, slave_url: &str, file_name: &str) -> Result<(), anyhow::Error>;
+    fn get_due_outbox_deliveries(&self, now_secs: u64) -> Result<Vec<OutboxDelivery>, anyhow::Error>;
+    fn replace_outbox_delivery(
+        &self,
+        prev_delivery: &OutboxDelivery,
+        next_delivery: Option<OutboxDelivery>,
+    ) -> Result<bool, anyhow::Error>;
+}
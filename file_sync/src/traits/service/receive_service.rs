@@ -0,0 +1,46 @@
+use crate::common::*;
+
+use crate::model::file_meta_info::*;
+
+use crate::repository::receive_repository::*;
+
+#[async_trait]
+pub trait ReceiveService {
+    fn get_receive_file_list(&self) -> Result<Vec<String>, anyhow::Error>;
+    fn write_staged_file(&self, target_file_path: &Path, file_data: &[u8]) -> Result<PathBuf, anyhow::Error>;
+    fn commit_staged_file(
+        &self,
+        staged_file_path: &Path,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+        backup_version: Option<u64>,
+        backup_source: &str,
+    ) -> Result<(), anyhow::Error>;
+    fn is_stale_receive_version(&self, file_name: &str, version: Option<u64>) -> Result<bool, anyhow::Error>;
+    fn commit_received_file(
+        &self,
+        staged_file_path: &Path,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+        from_host: &str,
+        version: Option<u64>,
+    ) -> Result<bool, anyhow::Error>;
+    fn get_receive_file_info(&self, file_name: &str) -> Result<Option<ReceiveFileInfo>, anyhow::Error>;
+    fn apply_received_file_meta(&self, target_file_path: &Path, file_meta: &FileMetaInfo) -> Result<(), anyhow::Error>;
+    fn restore_last_good_copy(
+        &self,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+    ) -> Result<bool, anyhow::Error>;
+    fn remove_received_file(&self, target_file_path: &Path, file_name: &str) -> Result<(), anyhow::Error>;
+    fn revert_received_file(
+        &self,
+        target_file_path: &Path,
+        backup_dir_path: &str,
+        file_name: &str,
+        backup_id: &str,
+    ) -> Result<bool, anyhow::Error>;
+}
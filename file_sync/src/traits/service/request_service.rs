@@ -1,17 +1,35 @@
 use crate::common::*;
 
+use crate::model::analyzer_reload_result::*;
+use crate::model::manifest_info::*;
+
 #[async_trait]
 pub trait RequestService {
-    async fn send_info_to_slave(
+    async fn send_info_to_target_slave(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        slave_url: Vec<String>,
+        content_hash: &str,
+        file_version: u64,
+    ) -> Result<(), anyhow::Error>;
+    async fn get_slave_manifest(&self, slave_url: &str) -> Result<ManifestInfo, anyhow::Error>;
+    async fn get_master_manifest(&self, master_url: &str) -> Result<ManifestInfo, anyhow::Error>;
+    async fn send_review_to_master(
         &self,
+        master_url: &str,
         file_path: &str,
         file_name: &str,
+        file_version: u64,
     ) -> Result<(), anyhow::Error>;
+    async fn get_master_file(&self, master_url: &str, file_name: &str) -> Result<Vec<u8>, anyhow::Error>;
     async fn send_info_to_slave_io(
         &self,
         file_path: &str,
         file_name: &str,
         slave_url: Vec<String>,
+        content_hash: &str,
+        file_version: u64,
         secure_mode: bool,
     ) -> Result<(), anyhow::Error>;
     async fn send_info_to_slave_memory(
@@ -19,8 +37,35 @@ pub trait RequestService {
         file_path: &str,
         file_name: &str,
         slave_url: Vec<String>,
+        content_hash: &str,
+        file_version: u64,
         secure_mode: bool,
     ) -> Result<(), anyhow::Error>;
+    async fn send_delete_to_target_slave(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        slave_url: Vec<String>,
+    ) -> Result<(), anyhow::Error>;
+    async fn send_rename_to_slave(
+        &self,
+        file_path: &str,
+        from_file_name: &str,
+        to_file_name: &str,
+        slave_url: Vec<String>,
+    ) -> Result<(), anyhow::Error>;
+    async fn send_operation_to_slave(
+        &self,
+        file_path: &str,
+        operation: &str,
+        query: &str,
+        slave_url: Vec<String>,
+    ) -> Result<(), anyhow::Error>;
+    async fn reload_search_analyzers(
+        &self,
+        elastic_url: &str,
+        index: &str,
+    ) -> Result<AnalyzerReloadResult, anyhow::Error>;
     fn handle_async_function(
         &self,
         task_res: Vec<Result<Result<(), anyhow::Error>, task::JoinError>>,
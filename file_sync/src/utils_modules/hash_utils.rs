@@ -1,5 +1,8 @@
 use crate::common::*;
 
+#[doc = "전송하는 파일 내용의 SHA-256 값을 담는 헤더 - slave 는 검증한 해시값을 같은 헤더로 응답한다."]
+pub const CONTENT_SHA256_HEADER: &str = "X-Content-Sha256";
+
 #[doc = "파일을 읽어서 해당 파일의 내용을 해시로 변환해주는 함수"]
 /// # Arguments
 /// * `path` - 해시값 계산의 대상이 되는 파일의 절대경로
@@ -23,3 +26,25 @@ pub fn conpute_hash(path: &Path) -> Result<Vec<u8>, anyhow::Error> {
 
     Ok(hasher.finalize().to_vec())
 }
+
+#[doc = "메모리에 있는 데이터의 해시값을 계산해주는 함수"]
+/// # Arguments
+/// * `data` - 해시값 계산의 대상이 되는 데이터
+///
+/// # Returns
+/// * Vec<u8>
+pub fn compute_hash_from_bytes(data: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+#[doc = "해시값을 16진수 문자열로 변환해주는 함수"]
+/// # Arguments
+/// * `hash` - 해시값
+///
+/// # Returns
+/// * String - 16진수 소문자 문자열
+pub fn hash_to_hex(hash: &[u8]) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,89 @@
+use crate::common::*;
+
+use crate::model::hook_result::*;
+
+#[doc = "응답에 담는 훅 출력(stdout, stderr 각각)의 최대 크기 - 넘는 경우 마지막 부분만 남긴다."]
+pub const HOOK_OUTPUT_LIMIT: usize = 8 * 1024;
+
+#[doc = "동기화 훅 명령을 실행하고 결과를 반환해주는 함수"]
+/// 명령은 `sh -c` 로 실행되며, 대상 파일 정보는 환경변수로 전달된다.
+/// 제한 시간을 넘기면 명령을 종료하고 시간 초과로 기록한다.
+///
+/// # Arguments
+/// * `stage`       - 훅 단계 (pre-write, post-write)
+/// * `command`     - 실행할 명령
+/// * `hook_envs`   - 명령에 전달할 환경변수 목록
+/// * `timeout`     - 최대 실행 시간
+///
+/// # Returns
+/// * HookResult - 실행하지 못한 경우에도 실패한 결과를 반환한다.
+pub async fn run_sync_hook(
+    stage: &str,
+    command: &str,
+    hook_envs: &[(&str, &str)],
+    timeout: Duration,
+) -> HookResult {
+    let started_at: Instant = Instant::now();
+
+    let mut hook_command: tokio::process::Command = tokio::process::Command::new("sh");
+    hook_command
+        .arg("-c")
+        .arg(command)
+        .envs(hook_envs.iter().copied())
+        .kill_on_drop(true); /* 시간 초과로 future 가 drop 되면 명령도 종료된다. */
+
+    let output_res: Result<std::io::Result<std::process::Output>, tokio::time::error::Elapsed> =
+        tokio::time::timeout(timeout, hook_command.output()).await;
+    let elapsed_ms: u64 = started_at.elapsed().as_millis() as u64;
+
+    match output_res {
+        Ok(Ok(output)) => HookResult::new(
+            stage.to_string(),
+            command.to_string(),
+            output.status.code(),
+            false,
+            get_limited_output(&output.stdout),
+            get_limited_output(&output.stderr),
+            elapsed_ms,
+        ),
+        Ok(Err(e)) => HookResult::new(
+            stage.to_string(),
+            command.to_string(),
+            None,
+            false,
+            String::new(),
+            format!("Failed to run the hook: {:?}", e),
+            elapsed_ms,
+        ),
+        Err(_) => HookResult::new(
+            stage.to_string(),
+            command.to_string(),
+            None,
+            true,
+            String::new(),
+            format!("The hook did not finish within {:?}", timeout),
+            elapsed_ms,
+        ),
+    }
+}
+
+#[doc = "명령의 출력을 문자열로 변환해주는 함수 - HOOK_OUTPUT_LIMIT 를 넘는 경우 마지막 부분만 남긴다."]
+/// # Arguments
+/// * `output` - 명령의 출력
+///
+/// # Returns
+/// * String
+fn get_limited_output(output: &[u8]) -> String {
+    let output: std::borrow::Cow<'_, str> = String::from_utf8_lossy(output);
+
+    if output.len() <= HOOK_OUTPUT_LIMIT {
+        return output.to_string();
+    }
+
+    let mut start: usize = output.len() - HOOK_OUTPUT_LIMIT;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+
+    format!("...(truncated)\n{}", &output[start..])
+}
@@ -1,5 +1,17 @@
 use crate::common::*;
 
+use crate::model::file_meta_info::*;
+
+use crate::utils_modules::time_utils::*;
+
+#[doc = "수신중인 파일을 임시로 저장할 때 붙이는 접미사 - 감시/manifest 대상에서 제외된다."]
+pub const STAGING_FILE_SUFFIX: &str = ".file_sync_tmp";
+
+#[doc = "백업 파일 이름에 붙는 백업 시각 형식 - 백업 파일의 식별자(backup_id)로도 사용된다."]
+/// 같은 초에 여러 번 백업되더라도 식별자가 겹치지 않도록 마이크로초까지 기록한다.
+/// (소수점 이하가 없는 이전 형식의 식별자도 그대로 읽을 수 있다.)
+pub const BACKUP_TIMESTAMP_FORMAT: &str = "%Y_%m_%d_%H%M%S%.6f";
+
 #[doc = "toml 파일을 읽어서 객체로 변환해주는 함수"]
 /// # Arguments
 /// * `file_path` - 읽을 대상 toml 파일이 존재하는 경로
@@ -61,16 +73,419 @@ pub fn create_dir_and_file<P: AsRef<Path>, Q: AsRef<Path>>(
     Ok(file_path)
 }
 
-#[doc = "디렉토리를 제거해주는 함수"]
+#[doc = "master 에서 넘어온 상대경로를 기준 디렉토리 하위의 실제 경로로 변환해주는 함수"]
+/// # Arguments
+/// * `base_dir`            - 기준 디렉토리 (slave 의 감시 디렉토리)
+/// * `relative_file_path`  - master 에서 넘어온 파일의 상대경로 (예: analysis/synonyms.txt)
+///
+/// # Returns
+/// * Result<PathBuf, anyhow::Error> - 절대경로나 '..' 등으로 기준 디렉토리를 벗어나는 경우에는 에러를 반환.
+pub fn get_sync_target_path(
+    base_dir: &Path,
+    relative_file_path: &str,
+) -> Result<PathBuf, anyhow::Error> {
+    let relative_path: &Path = Path::new(relative_file_path);
+
+    let is_valid: bool = !relative_file_path.is_empty()
+        && relative_path
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)));
+
+    if !is_valid {
+        return Err(anyhow!(
+            "[Error][get_sync_target_path()] Invalid file path: '{}'",
+            relative_file_path
+        ));
+    }
+
+    Ok(base_dir.join(relative_path))
+}
+
+#[doc = "파일이 위치할 상위 디렉토리가 존재하지 않는 경우 생성해주는 함수"]
+/// # Arguments
+/// * `file_path` - 생성될 파일의 경로
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn create_parent_dir(file_path: &Path) -> Result<(), anyhow::Error> {
+    if let Some(parent_dir) = file_path.parent() {
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            fs::create_dir_all(parent_dir).map_err(|e| {
+                anyhow!(
+                    "[Error][create_parent_dir()] Failed to create directory '{:?}' : {:?}",
+                    parent_dir,
+                    e
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+#[doc = "디렉토리 하위에 존재하는 모든 파일의 경로를 재귀적으로 반환해주는 함수"]
+/// # Arguments
+/// * `dir_path` - 탐색할 디렉토리 경로
+///
+/// # Returns
+/// * Result<Vec<PathBuf>, anyhow::Error>
+pub fn get_all_file_paths(dir_path: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+    let mut file_paths: Vec<PathBuf> = Vec::new();
+
+    if !dir_path.is_dir() {
+        return Ok(file_paths);
+    }
+
+    for entry in fs::read_dir(dir_path)? {
+        let path: PathBuf = entry?.path();
+
+        if path.is_dir() {
+            file_paths.extend(get_all_file_paths(&path)?);
+        } else if path.is_file() {
+            file_paths.push(path);
+        }
+    }
+
+    Ok(file_paths)
+}
+
+#[doc = "하위의 비어있는 디렉토리를 모두 제거해주는 함수 - 기준 디렉토리는 제거하지 않는다."]
 /// # Arguments
-/// * `path` - 삭제해줄 디렉토리 경로
+/// * `dir_path` - 기준 디렉토리 경로
 ///
 /// # Returns
 /// * Result<(), anyhow::Error>
-pub fn delete_directory(path: &PathBuf) -> Result<(), anyhow::Error> {
-    fs::remove_dir_all(path).map_err(|e| anyhow!("Failed to delete '{:?}' : {:?}", path, e))?;
+pub fn remove_empty_dirs(dir_path: &Path) -> Result<(), anyhow::Error> {
+    if !dir_path.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir_path)? {
+        let path: PathBuf = entry?.path();
+
+        if path.is_dir() {
+            remove_empty_dirs(&path)?;
 
-    info!("Success to delete '{:?}'", path);
+            if fs::read_dir(&path)?.next().is_none() {
+                fs::remove_dir(&path)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[doc = "대상 파일과 같은 디렉토리에 위치하는 임시(staging) 파일 경로를 반환해주는 함수"]
+/// 같은 파일시스템 안에서 rename 해야 원자적으로 교체되므로 반드시 대상 파일과 같은 디렉토리를 사용한다.
+/// 같은 파일을 동시에 수신하는 경우를 위해 임시 파일 이름에는 무작위 값이 포함된다.
+///
+/// # Arguments
+/// * `target_file_path` - 최종적으로 교체될 파일 경로
+///
+/// # Returns
+/// * Result<PathBuf, anyhow::Error>
+pub fn get_staging_file_path(target_file_path: &Path) -> Result<PathBuf, anyhow::Error> {
+    let file_name: &str = target_file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("[Error][get_staging_file_path()] Invalid file name: {:?}", target_file_path))?;
+
+    let staging_id: u32 = rand::thread_rng().gen();
+
+    Ok(target_file_path.with_file_name(format!(
+        ".{}.{:08x}{}",
+        file_name, staging_id, STAGING_FILE_SUFFIX
+    )))
+}
+
+#[doc = "임시(staging) 파일인지 확인해주는 함수"]
+pub fn is_staging_file(file_path: &Path) -> bool {
+    file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.ends_with(STAGING_FILE_SUFFIX))
+        .unwrap_or(false)
+}
+
+#[doc = "파일이 위치한 디렉토리를 디스크에 동기화(fsync)해주는 함수 - rename 결과를 영구적으로 반영하기 위함."]
+/// # Arguments
+/// * `file_path` - 디렉토리 내부의 파일 경로
+pub fn sync_parent_dir(file_path: &Path) -> Result<(), anyhow::Error> {
+    /* windows 는 디렉토리 핸들의 fsync 를 지원하지 않는다. */
+    if cfg!(unix) {
+        if let Some(parent_dir) = file_path.parent() {
+            File::open(parent_dir)?.sync_all()?;
+        }
+    }
 
     Ok(())
 }
+
+#[doc = "파일을 원자적으로 기록해주는 함수 - 같은 디렉토리의 임시 파일에 기록하고 동기화(fsync)한 뒤 교체한다."]
+/// 기록 도중 프로세스가 종료되더라도 기존 파일은 온전하게 남는다.
+///
+/// # Arguments
+/// * `file_path`   - 기록할 파일 경로
+/// * `contents`    - 기록할 내용
+pub fn write_file_atomic(file_path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let staged_file_path: PathBuf = get_staging_file_path(file_path)?;
+
+    let write_res: Result<(), anyhow::Error> = (|| {
+        let mut staged_file: File = File::create(&staged_file_path)?;
+        staged_file.write_all(contents)?;
+        staged_file.sync_all()?;
+
+        fs::rename(&staged_file_path, file_path)?;
+        sync_parent_dir(file_path)
+    })();
+
+    if write_res.is_err() && staged_file_path.exists() {
+        let _ = fs::remove_file(&staged_file_path);
+    }
+
+    write_res
+}
+
+#[doc = "읽을 수 없는 저장소 파일을 격리해주는 함수 - 파일 이름 뒤에 격리 시각을 붙여서 옮긴다."]
+/// 격리된 파일은 운영자가 확인 후 직접 복구할 수 있다.
+///
+/// # Arguments
+/// * `file_path` - 격리할 파일 경로
+///
+/// # Returns
+/// * Result<PathBuf, anyhow::Error> - 격리된 파일 경로
+pub fn quarantine_corrupt_file(file_path: &Path) -> Result<PathBuf, anyhow::Error> {
+    let quarantine_path: PathBuf = PathBuf::from(format!(
+        "{}.corrupt_{}",
+        file_path.to_string_lossy(),
+        get_current_utc_naivedatetime_str("%Y%m%d%H%M%S")?
+    ));
+    fs::rename(file_path, &quarantine_path)?;
+
+    Ok(quarantine_path)
+}
+
+#[doc = "파일의 크기와 수정시각을 반환해주는 함수 - 파일이 기록 중인지(안정화 여부) 판단할 때 사용한다."]
+/// # Arguments
+/// * `file_path` - 대상 파일 경로
+///
+/// # Returns
+/// * Option<(u64, SystemTime)> - 파일이 존재하지 않는 경우 None
+pub fn get_file_stat(file_path: &Path) -> Option<(u64, SystemTime)> {
+    let metadata: fs::Metadata = fs::metadata(file_path).ok()?;
+    let modified: SystemTime = metadata.modified().ok()?;
+
+    Some((metadata.len(), modified))
+}
+
+#[doc = "파일의 권한, 소유자, 수정시각 정보를 읽어주는 함수"]
+/// 권한과 소유자 정보는 unix 계열에서만 읽는다.
+///
+/// # Arguments
+/// * `file_path`       - 대상 파일 경로
+/// * `preserve_owner`  - 소유자(uid/gid) 정보를 포함할지 여부
+///
+/// # Returns
+/// * Result<FileMetaInfo, anyhow::Error>
+pub fn get_file_meta(file_path: &Path, preserve_owner: bool) -> Result<FileMetaInfo, anyhow::Error> {
+    let metadata: fs::Metadata = fs::metadata(file_path)?;
+
+    let mtime: Option<u64> = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        Ok(FileMetaInfo::new(
+            Some(metadata.mode() & 0o7777),
+            preserve_owner.then(|| metadata.uid()),
+            preserve_owner.then(|| metadata.gid()),
+            mtime,
+        ))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = preserve_owner;
+        Ok(FileMetaInfo::new(None, None, None, mtime))
+    }
+}
+
+#[doc = "파일에 권한, 소유자, 수정시각 정보를 적용해주는 함수"]
+/// 권한을 먼저 바꾸면 수정시각을 바꾸지 못할 수 있으므로 수정시각 -> 소유자 -> 권한 순서로 적용한다.
+/// 권한과 소유자는 unix 계열에서만 적용된다.
+///
+/// # Arguments
+/// * `file_path` - 대상 파일 경로
+/// * `file_meta` - 적용할 메타정보 (값이 없는 항목은 그대로 둔다.)
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn apply_file_meta(file_path: &Path, file_meta: &FileMetaInfo) -> Result<(), anyhow::Error> {
+    if let Some(mtime) = file_meta.mtime() {
+        let file: File = fs::OpenOptions::new().write(true).open(file_path)?;
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(*mtime))
+            .map_err(|e| anyhow!("[Error][apply_file_meta()] Failed to set mtime of '{:?}' : {:?}", file_path, e))?;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if file_meta.uid().is_some() || file_meta.gid().is_some() {
+            std::os::unix::fs::chown(file_path, *file_meta.uid(), *file_meta.gid())
+                .map_err(|e| anyhow!("[Error][apply_file_meta()] Failed to change owner of '{:?}' : {:?}", file_path, e))?;
+        }
+
+        if let Some(mode) = file_meta.mode() {
+            fs::set_permissions(file_path, fs::Permissions::from_mode(*mode))
+                .map_err(|e| anyhow!("[Error][apply_file_meta()] Failed to change mode of '{:?}' : {:?}", file_path, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[doc = "압축 방식별 백업 파일 확장자를 반환해주는 함수"]
+/// # Arguments
+/// * `compression` - 압축 방식 (none, gzip, zstd)
+///
+/// # Returns
+/// * Result<&'static str, anyhow::Error> - 지원하지 않는 압축 방식인 경우 에러
+pub fn get_backup_compression_ext(compression: &str) -> Result<&'static str, anyhow::Error> {
+    match compression {
+        "none" => Ok(""),
+        "gzip" => Ok(".gz"),
+        "zstd" => Ok(".zst"),
+        _ => Err(anyhow!(
+            "[Error][get_backup_compression_ext()] Unsupported backup compression: {}",
+            compression
+        )),
+    }
+}
+
+#[doc = "내용을 압축 방식에 맞게 압축하여 파일로 저장해주는 함수"]
+/// # Arguments
+/// * `file_data`   - 저장할 내용
+/// * `file_path`   - 저장할 파일 경로 (압축 확장자 포함)
+/// * `compression` - 압축 방식 (none, gzip, zstd)
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn write_compressed_file(file_data: &[u8], file_path: &Path, compression: &str) -> Result<(), anyhow::Error> {
+    let mut target_file: File = File::create(file_path)?;
+
+    match compression {
+        "none" => target_file.write_all(file_data)?,
+        "gzip" => {
+            let mut encoder: GzEncoder<File> = GzEncoder::new(target_file, GzCompression::default());
+            encoder.write_all(file_data)?;
+            target_file = encoder.finish()?;
+        }
+        "zstd" => {
+            zstd::stream::copy_encode(file_data, &mut target_file, 0)?;
+        }
+        _ => {
+            drop(target_file);
+            let _ = fs::remove_file(file_path);
+            return Err(anyhow!(
+                "[Error][write_compressed_file()] Unsupported compression: {}",
+                compression
+            ));
+        }
+    }
+
+    target_file.sync_all()?;
+    Ok(())
+}
+
+#[doc = "백업 파일(blob)의 원래 내용을 읽어주는 함수 - 확장자를 보고 압축을 풀어준다."]
+/// # Arguments
+/// * `backup_file_path` - 백업 파일 경로
+///
+/// # Returns
+/// * Result<Vec<u8>, anyhow::Error>
+pub fn read_backup_file(backup_file_path: &Path) -> Result<Vec<u8>, anyhow::Error> {
+    let backup_file: File = File::open(backup_file_path)?;
+    let mut file_data: Vec<u8> = Vec::new();
+
+    match backup_file_path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => {
+            GzDecoder::new(backup_file).read_to_end(&mut file_data)?;
+        }
+        Some("zst") => {
+            file_data = zstd::stream::decode_all(backup_file)?;
+        }
+        _ => {
+            BufReader::new(backup_file).read_to_end(&mut file_data)?;
+        }
+    }
+
+    Ok(file_data)
+}
+
+#[doc = "이전 형식의 백업 파일 이름에서 원래 파일 이름과 백업 식별자(backup_id)를 분리해주는 함수"]
+/// 버전 저장소 도입 이전의 백업 파일은 `날짜별 폴더/파일이름.backup_id` 에 압축한 경우 압축 확장자가 붙는다.
+///
+/// # Arguments
+/// * `backup_file_name` - 백업 파일 이름 (예: synonyms.txt.2025_01_15_093000.gz)
+///
+/// # Returns
+/// * Option<(String, String)> - 백업 파일 형식이 아닌 경우 None
+pub fn parse_backup_file_name(backup_file_name: &str) -> Option<(String, String)> {
+    let backup_file_name: &str = backup_file_name
+        .strip_suffix(".gz")
+        .or_else(|| backup_file_name.strip_suffix(".zst"))
+        .unwrap_or(backup_file_name);
+
+    let (file_name, backup_id) = backup_file_name.rsplit_once('.')?;
+
+    if file_name.is_empty() || NaiveDateTime::parse_from_str(backup_id, BACKUP_TIMESTAMP_FORMAT).is_err() {
+        return None;
+    }
+
+    Some((file_name.to_string(), backup_id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_target_path_joins_relative_path() {
+        let base_dir: &Path = Path::new("/data/dict");
+
+        assert_eq!(
+            get_sync_target_path(base_dir, "analysis/synonyms.txt").unwrap(),
+            PathBuf::from("/data/dict/analysis/synonyms.txt")
+        );
+        assert_eq!(
+            get_sync_target_path(base_dir, "synonyms.txt").unwrap(),
+            PathBuf::from("/data/dict/synonyms.txt")
+        );
+    }
+
+    #[test]
+    fn sync_target_path_rejects_traversal() {
+        let base_dir: &Path = Path::new("/data/dict");
+
+        for relative_file_path in [
+            "../synonyms.txt",
+            "analysis/../../synonyms.txt",
+            "analysis/..",
+            "/etc/passwd",
+            "./synonyms.txt",
+            "",
+        ] {
+            assert!(
+                get_sync_target_path(base_dir, relative_file_path).is_err(),
+                "'{}' must be rejected",
+                relative_file_path
+            );
+        }
+    }
+}
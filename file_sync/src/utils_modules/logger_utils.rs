@@ -23,6 +23,16 @@ pub fn set_global_logger() {
         .unwrap_or_else(|e| panic!("Logger initialization failed: {}", e));
 }
 
+#[doc = "동기화 작업 결과(이벤트)를 json 형식으로 로그 파일에 남겨주는 함수"]
+/// # Arguments
+/// * `task_event` - 로그로 남길 작업 이벤트 객체
+pub fn write_task_event_log<T: Serialize>(task_event: &T) {
+    match serde_json::to_string(task_event) {
+        Ok(task_event_json) => info!("[TASK EVENT] {}", task_event_json),
+        Err(e) => error!("[Error][write_task_event_log()] {:?}", e),
+    }
+}
+
 #[doc = "Custom Log Format Function."]
 fn custom_format(
     w: &mut dyn Write,
@@ -1,5 +1,7 @@
 pub mod hash_utils;
+pub mod hook_utils;
 pub mod io_utils;
 pub mod logger_utils;
+pub mod pattern_utils;
 pub mod time_utils;
 pub mod tls_utils;
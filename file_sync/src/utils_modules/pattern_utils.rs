@@ -0,0 +1,137 @@
+use crate::common::*;
+
+#[doc = "파일 경로를 '/' 구분자를 사용하는 상대경로 문자열로 변환해주는 함수"]
+/// # Arguments
+/// * `base_path` - 기준이 되는 디렉토리 경로
+/// * `file_path` - 변환할 파일의 경로
+///
+/// # Returns
+/// * Option<String> - `file_path` 가 `base_path` 하위에 존재하지 않는 경우에는 None
+pub fn get_relative_path_str(base_path: &Path, file_path: &Path) -> Option<String> {
+    let relative_path: &Path = file_path.strip_prefix(base_path).ok()?;
+
+    let components: Option<Vec<&str>> = relative_path
+        .components()
+        .map(|component| match component {
+            std::path::Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    match components {
+        Some(components) if !components.is_empty() => Some(components.join("/")),
+        _ => None,
+    }
+}
+
+#[doc = "상대경로가 모니터링 패턴 목록과 일치하는지 확인해주는 함수"]
+/// 패턴은 감시 디렉토리 기준 상대경로에 대해 비교되며 `*` 는 디렉토리 구분자를 넘지 않는다. (`**` 는 하위 디렉토리 전체)
+/// `!` 로 시작하는 패턴은 제외 패턴이며, '/' 가 없는 제외 패턴은 파일 이름에도 적용된다. (예: `!*.bak`)
+///
+/// # Arguments
+/// * `patterns`        - 모니터링 대상 패턴 목록 (예: `synonyms.txt`, `analysis/**/*.txt`, `!*.bak`)
+/// * `relative_path`   - 비교 대상이 되는 상대경로
+///
+/// # Returns
+/// * Result<bool, anyhow::Error> - 포함 패턴 중 하나와 일치하고 제외 패턴과 일치하지 않는 경우 True
+pub fn is_match_file_patterns(
+    patterns: &[String],
+    relative_path: &str,
+) -> Result<bool, anyhow::Error> {
+    let match_options: glob::MatchOptions = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    let file_name: &str = relative_path.rsplit('/').next().unwrap_or(relative_path);
+
+    let mut include_yn: bool = false;
+
+    for pattern in patterns {
+        if let Some(exclude_pattern) = pattern.strip_prefix('!') {
+            let glob_pattern: glob::Pattern = glob::Pattern::new(exclude_pattern)
+                .map_err(|e| anyhow!("[Error][is_match_file_patterns()] Invalid pattern '{}': {:?}", pattern, e))?;
+
+            let exclude_yn: bool = glob_pattern.matches_with(relative_path, match_options)
+                || (!exclude_pattern.contains('/') && glob_pattern.matches_with(file_name, match_options));
+
+            if exclude_yn {
+                return Ok(false);
+            }
+        } else if !include_yn {
+            let glob_pattern: glob::Pattern = glob::Pattern::new(pattern)
+                .map_err(|e| anyhow!("[Error][is_match_file_patterns()] Invalid pattern '{}': {:?}", pattern, e))?;
+
+            include_yn = glob_pattern.matches_with(relative_path, match_options);
+        }
+    }
+
+    Ok(include_yn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.to_string()).collect()
+    }
+
+    #[test]
+    fn single_star_does_not_cross_directories() {
+        let patterns: Vec<String> = to_patterns(&["*.txt"]);
+
+        assert!(is_match_file_patterns(&patterns, "synonyms.txt").unwrap());
+        assert!(!is_match_file_patterns(&patterns, "analysis/synonyms.txt").unwrap());
+    }
+
+    #[test]
+    fn double_star_matches_sub_directories() {
+        let patterns: Vec<String> = to_patterns(&["analysis/**/*.txt"]);
+
+        assert!(is_match_file_patterns(&patterns, "analysis/synonyms.txt").unwrap());
+        assert!(is_match_file_patterns(&patterns, "analysis/ko/user/synonyms.txt").unwrap());
+        assert!(!is_match_file_patterns(&patterns, "dict/synonyms.txt").unwrap());
+    }
+
+    #[test]
+    fn exclude_pattern_wins_over_include() {
+        let patterns: Vec<String> = to_patterns(&["analysis/**", "!analysis/tmp/*"]);
+
+        assert!(is_match_file_patterns(&patterns, "analysis/synonyms.txt").unwrap());
+        assert!(!is_match_file_patterns(&patterns, "analysis/tmp/synonyms.txt").unwrap());
+    }
+
+    #[test]
+    fn exclude_pattern_without_separator_matches_file_name() {
+        let patterns: Vec<String> = to_patterns(&["**", "!*.bak"]);
+
+        assert!(is_match_file_patterns(&patterns, "analysis/ko/synonyms.txt").unwrap());
+        assert!(!is_match_file_patterns(&patterns, "analysis/ko/synonyms.txt.bak").unwrap());
+        assert!(!is_match_file_patterns(&patterns, "synonyms.bak").unwrap());
+    }
+
+    #[test]
+    fn no_include_pattern_matches_nothing() {
+        assert!(!is_match_file_patterns(&[], "synonyms.txt").unwrap());
+        assert!(!is_match_file_patterns(&to_patterns(&["!*.bak"]), "synonyms.txt").unwrap());
+    }
+
+    #[test]
+    fn invalid_pattern_is_error() {
+        assert!(is_match_file_patterns(&to_patterns(&["!analysis/[*.txt"]), "synonyms.txt").is_err());
+    }
+
+    #[test]
+    fn relative_path_uses_slash_separator() {
+        let base_path: &Path = Path::new("/data/dict");
+
+        assert_eq!(
+            get_relative_path_str(base_path, Path::new("/data/dict/analysis/synonyms.txt")),
+            Some("analysis/synonyms.txt".to_string())
+        );
+        assert_eq!(get_relative_path_str(base_path, Path::new("/data/other/synonyms.txt")), None);
+        assert_eq!(get_relative_path_str(base_path, base_path), None);
+    }
+}
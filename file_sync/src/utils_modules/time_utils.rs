@@ -12,17 +12,12 @@ pub fn get_currnet_utc_naivedatetime() -> NaiveDateTime {
     utc_now.naive_local()
 }
 
-#[doc = "Function that returns the current UTC time as a string"]
-/// # Arguments
-/// * `fmt` - 문자열 포멧터
-///
-/// # Returns
-/// * Result<String, anyhow::Error> - naivedate 인스턴스를 문자열로 변환한 데이터
-pub fn get_current_utc_naivedate_str(fmt: &str) -> Result<String, anyhow::Error> {
-    let curr_time = get_current_utc_naivedate();
-    let curr_time_str = get_str_from_naivedate(curr_time, fmt)?;
-
-    Ok(curr_time_str)
+#[doc = "Functions that return the current UNIX time in seconds"]
+pub fn get_current_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
 }
 
 #[doc = "Function that returns the current UTC time as a string"]
@@ -65,6 +60,21 @@ pub fn get_str_from_naivedate(naive_date: NaiveDate, fmt: &str) -> Result<String
     Ok(result_date)
 }
 
+#[doc = "Function that converts the 'SystemTime' (ex. file mtime) to the UTC string format"]
+/// # Arguments
+/// * `system_time` - SystemTime 인스턴스
+/// * `fmt`         - 문자열 포멧터
+///
+/// # Returns
+/// * Result<String, anyhow::Error> - system_time 을 UTC 기준 문자열로 변환한 데이터
+pub fn get_utc_str_from_systemtime(
+    system_time: SystemTime,
+    fmt: &str,
+) -> Result<String, anyhow::Error> {
+    let utc_time: DateTime<Utc> = DateTime::<Utc>::from(system_time);
+    Ok(utc_time.format(fmt).to_string())
+}
+
 #[doc = "주어진 날짜 문자열이 유효한지 확인하고, UTC 기준으로 오늘과의 날짜 차이를 계산"]
 /// # Arguments
 /// * `date_str` - 날짜를 나타내는 문자열